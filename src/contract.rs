@@ -9,17 +9,33 @@ use cosmwasm_std::{
 };
 use cosmwasm_storage::{PrefixedStorage, ReadonlyPrefixedStorage};
 
-use secret_toolkit::utils::{pad_handle_result, pad_query_result};
+use secret_toolkit::utils::{pad_handle_result, pad_query_result, space_pad};
 
+use crate::error::{token_error, ContractError};
 use crate::expiration::Expiration;
-use crate::msg::{AccessLevel, Burn, ContractStatus, Cw721Approval, Cw721OwnerOfResponse, HandleAnswer, HandleMsg, InitMsg, Mint, QueryAnswer, QueryMsg, ResponseStatus::Success, Send, Snip721Approval, Transfer, ViewerInfo, TokenData};
-use crate::rand::sha_256;
+use crate::msg::{
+    AccessLevel, Burn, BurnOutcome, BurnResult, ContractStatus, Cw721Approval,
+    Cw721OwnerOfResponse, HandleAnswer, HandleMsg, InboxSecret, InitMsg, Mint, OutboxSecret,
+    QueryAnswer, QueryMsg, QueryWithPermit, ResponseStatus::Success, RetrievedSecret,
+    ScopedKeyInfo, ScopedQuery, SecretPadding, SecretRequestInfo, Send, Snip721Approval, TokenData,
+    Transfer, TxFilter, TxRetention, ViewerInfo,
+};
+use crate::permit::{validate, Permit, TokenPermissions};
+use crate::rand::{sha_256, Prng};
+use crate::receiver::{batch_receive_nft_msg, receive_nft_msg};
 use crate::state::{
-    get_txs, json_may_load, json_save, load, may_load, remove, save, store_burn, store_mint,
-    AuthList, Config, Permission, PermissionType, ReceiveRegistration, BLOCK_KEY,
-    CONFIG_KEY, MINTERS_KEY, PREFIX_ALL_PERMISSIONS, PREFIX_AUTHLIST, PREFIX_INFOS,
-    PREFIX_MAP_TO_ID, PREFIX_MAP_TO_INDEX, PREFIX_OWNED, PREFIX_OWNER_PRIV, PREFIX_PRIV_META,
-    PREFIX_PUB_META, PREFIX_VIEW_KEY, PRNG_SEED_KEY, TOKENS_KEY,
+    add_secret_request, add_to_inbox, add_to_inventory, get_all_perms, get_auth_tokens, get_inbox,
+    get_inbox_pos, get_inventory, get_inventory_len, get_inventory_pos, get_secret_requests,
+    get_token_count, get_txs, json_may_load, json_save, load, load_config, may_load,
+    may_load_all_perm, may_load_token, migrate_legacy_approvals, migrate_legacy_inventory,
+    prune_txs, remove, remove_from_inbox, remove_from_inventory, save, save_token, set_all_perm,
+    set_auth_tokens, set_token_count, store_burn, store_mint, store_transfer, Config,
+    MigrationProgress, MintInfo, Permission, PermissionType, ReceiveRegistration, ScopedKey,
+    SecretRequest, StoredTx, BLOCK_KEY, CONFIG_KEY, MIGRATION_KEY, MINTERS_KEY, MY_ADDRESS_KEY,
+    PREFIX_INFOS, PREFIX_MAP_TO_ID, PREFIX_MAP_TO_INDEX, PREFIX_MINT_INFO, PREFIX_OWNER_PRIV,
+    PREFIX_PRIV_META, PREFIX_PUB_META, PREFIX_RECEIVERS, PREFIX_RETRIEVED, PREFIX_SCOPED_KEYS,
+    PREFIX_SECRET_REQUESTS, PREFIX_TXS, PREFIX_VIEW_KEY, PRNG_SEED_KEY, REQUEST_CNT_KEY,
+    SCHEMA_VERSION,
 };
 use crate::token::{Metadata, Token};
 use crate::viewing_key::{ViewingKey, VIEWING_KEY_SIZE};
@@ -43,11 +59,15 @@ pub fn init<S: Storage, A: Api, Q: Querier>(
     env: Env,
     msg: InitMsg,
 ) -> InitResult {
-    let tokens: HashSet<String> = HashSet::new();
     let admin = msg.admin.unwrap_or(env.message.sender);
     let admin_raw = deps.api.canonical_address(&admin)?;
     let prng_seed: Vec<u8> = sha_256(base64::encode(msg.entropy).as_bytes()).to_vec();
     let init_config = msg.config.unwrap_or_default();
+    if let Some(padding) = init_config.secret_padding.as_ref() {
+        if padding.min_bucket == 0 || padding.max_secret_size == 0 {
+            return Err(ContractError::InvalidSecretPadding.into());
+        }
+    }
 
     let config = Config {
         name: msg.name,
@@ -63,15 +83,28 @@ pub fn init<S: Storage, A: Api, Q: Querier>(
         minter_may_update_metadata: init_config.minter_may_update_metadata.unwrap_or(true),
         owner_may_update_metadata: init_config.owner_may_update_metadata.unwrap_or(false),
         burn_is_enabled: init_config.enable_burn.unwrap_or(false),
+        retrieval_window: init_config.retrieval_window,
+        tx_retention: init_config.tx_retention.unwrap_or_default(),
+        private_tx_records: init_config.private_tx_records.unwrap_or(false),
+        secret_padding: init_config.secret_padding,
+        transfers_enabled: init_config.transfers_enabled.unwrap_or(false),
+        gifting_enabled: init_config.gifting_enabled.unwrap_or(false),
+        open_minting: init_config.open_minting.unwrap_or(true),
+        metadata_queries_enabled: init_config.metadata_queries_enabled.unwrap_or(false),
+        burn_reveals_secret: init_config.burn_reveals_secret.unwrap_or(true),
+        schema_version: SCHEMA_VERSION,
     };
 
     let minters = vec![admin_raw];
     save(&mut deps.storage, CONFIG_KEY, &config)?;
-    save(&mut deps.storage, TOKENS_KEY, &tokens)?;
+    set_token_count(&mut deps.storage, 0)?;
     save(&mut deps.storage, MINTERS_KEY, &minters)?;
     save(&mut deps.storage, PRNG_SEED_KEY, &prng_seed)?;
     // TODO remove this after BlockInfo becomes available to queries
     save(&mut deps.storage, BLOCK_KEY, &env.block)?;
+    // queries need the contract's address to validate permits
+    let my_address_raw = deps.api.canonical_address(&env.contract.address)?;
+    save(&mut deps.storage, MY_ADDRESS_KEY, &my_address_raw)?;
 
     // perform the post init callback if needed
     let messages: Vec<CosmosMsg> = if let Some(callback) = msg.post_init_callback {
@@ -106,7 +139,17 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
 ) -> HandleResult {
     // TODO remove this after BlockInfo becomes available to queries
     save(&mut deps.storage, BLOCK_KEY, &env.block)?;
-    let mut config: Config = load(&deps.storage, CONFIG_KEY)?;
+    // contracts instantiated before permits were supported did not store their address
+    if may_load::<CanonicalAddr, _>(&deps.storage, MY_ADDRESS_KEY)?.is_none() {
+        let my_address_raw = deps.api.canonical_address(&env.contract.address)?;
+        save(&mut deps.storage, MY_ADDRESS_KEY, &my_address_raw)?;
+    }
+    let mut config = load_config(&deps.storage)?;
+    // burn responses hold the burned tokens' secrets
+    let secret_padding = match msg {
+        HandleMsg::BurnNft { .. } | HandleMsg::BatchBurnNft { .. } => config.secret_padding.clone(),
+        _ => None,
+    };
 
     let response = match msg {
         HandleMsg::MintNft {
@@ -114,6 +157,7 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
             owner,
             public_metadata,
             private_metadata,
+            public_teaser,
             memo,
             ..
         } => mint(
@@ -125,6 +169,7 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
             owner,
             public_metadata,
             private_metadata,
+            public_teaser,
             memo,
         ),
         HandleMsg::BatchMintNft { mut mints, .. } => batch_mint(
@@ -134,6 +179,34 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
             ContractStatus::Normal.to_u8(),
             &mut mints,
         ),
+        HandleMsg::CreateSecretRequest {
+            description,
+            responder,
+            expires,
+            ..
+        } => create_secret_request(
+            deps,
+            env,
+            &config,
+            ContractStatus::Normal.to_u8(),
+            description,
+            responder,
+            expires,
+        ),
+        HandleMsg::FulfillSecretRequest {
+            request_id,
+            code,
+            private_metadata,
+            ..
+        } => fulfill_secret_request(
+            deps,
+            env,
+            &mut config,
+            ContractStatus::Normal.to_u8(),
+            request_id,
+            &code,
+            private_metadata,
+        ),
         HandleMsg::SetPublicMetadata {
             token_id, metadata, ..
         } => set_public_metadata(
@@ -161,6 +234,13 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
             ContractStatus::StopTransactions.to_u8(),
             &token_id,
         ),
+        HandleMsg::RotateTokenLink { token_id, .. } => rotate_token_link(
+            deps,
+            env,
+            &config,
+            ContractStatus::StopTransactions.to_u8(),
+            &token_id,
+        ),
         HandleMsg::MakeOwnershipPrivate { .. } => {
             make_owner_private(deps, env, &config, ContractStatus::StopTransactions.to_u8())
         }
@@ -259,6 +339,7 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
         HandleMsg::TransferNft {
             recipient,
             token_id,
+            recipient_only,
             memo,
             ..
         } => transfer_nft(
@@ -268,6 +349,7 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
             ContractStatus::Normal.to_u8(),
             recipient,
             token_id,
+            recipient_only,
             memo,
         ),
         HandleMsg::BatchTransferNft { transfers, .. } => batch_transfer_nft(
@@ -281,6 +363,7 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
             contract,
             token_id,
             msg,
+            recipient_only,
             memo,
             ..
         } => send_nft(
@@ -291,6 +374,7 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
             contract,
             token_id,
             msg,
+            recipient_only,
             memo,
         ),
         HandleMsg::BatchSendNft { sends, .. } => batch_send_nft(
@@ -320,12 +404,27 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
             token_id,
             memo,
         ),
-        HandleMsg::BatchBurnNft { mut burns, .. } => batch_burn_nft(
+        HandleMsg::BatchBurnNft {
+            mut burns,
+            best_effort,
+            ..
+        } => batch_burn_nft(
             deps,
             env,
             &mut config,
             ContractStatus::Normal.to_u8(),
             &mut burns,
+            best_effort.unwrap_or(false),
+        ),
+        HandleMsg::ForgetRetrieved { .. } => {
+            forget_retrieved(deps, env, &config, ContractStatus::StopTransactions.to_u8())
+        }
+        HandleMsg::PruneHistory { keep, .. } => prune_history(
+            deps,
+            env,
+            &config,
+            ContractStatus::StopTransactions.to_u8(),
+            keep,
         ),
         HandleMsg::CreateViewingKey { entropy, .. } => create_key(
             deps,
@@ -341,6 +440,38 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
             ContractStatus::StopTransactions.to_u8(),
             key,
         ),
+        HandleMsg::RevokePermit { permit_name, .. } => revoke_permit(
+            deps,
+            env,
+            &config,
+            ContractStatus::StopTransactions.to_u8(),
+            &permit_name,
+        ),
+        HandleMsg::CreateScopedKey {
+            name,
+            entropy,
+            queries,
+            token_ids,
+            expires,
+            ..
+        } => create_scoped_key(
+            deps,
+            env,
+            &config,
+            ContractStatus::StopTransactions.to_u8(),
+            name,
+            &entropy,
+            queries,
+            token_ids,
+            expires,
+        ),
+        HandleMsg::RevokeScopedKey { name, .. } => revoke_scoped_key(
+            deps,
+            env,
+            &config,
+            ContractStatus::StopTransactions.to_u8(),
+            &name,
+        ),
         HandleMsg::AddMinters { minters, .. } => add_minters(
             deps,
             env,
@@ -369,13 +500,72 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
             ContractStatus::StopTransactions.to_u8(),
             &address,
         ),
+        HandleMsg::SetTxRetention { retention, .. } => set_tx_retention(
+            deps,
+            env,
+            &mut config,
+            ContractStatus::StopTransactions.to_u8(),
+            retention,
+        ),
         HandleMsg::SetContractStatus { level, .. } => {
             set_contract_status(deps, env, &mut config, level)
         }
+        HandleMsg::Migrate { batch_size, .. } => migrate_storage(
+            deps,
+            env,
+            &mut config,
+            ContractStatus::StopAll.to_u8(),
+            batch_size,
+        ),
+    };
+    let response = if let Some(padding) = secret_padding {
+        response.map(|mut resp| {
+            resp.data = resp.data.map(|mut data| {
+                pad_to_bucket(&mut data.0, &padding);
+                data
+            });
+            resp
+        })
+    } else {
+        response
     };
     pad_handle_result(response, BLOCK_SIZE)
 }
 
+/// pads a response holding secrets with spaces to the smallest bucket that can hold it
+///
+/// # Arguments
+///
+/// * `data` - a mutable reference to the serialized response
+/// * `padding` - a reference to the secret padding buckets
+fn pad_to_bucket(data: &mut Vec<u8>, padding: &SecretPadding) {
+    let mut bucket = padding.min_bucket as usize;
+    while bucket < data.len() {
+        bucket = bucket.saturating_mul(2);
+    }
+    space_pad(data, bucket);
+}
+
+/// Returns StdResult<()>
+///
+/// verifies that private metadata does not exceed the max secret size
+///
+/// # Arguments
+///
+/// * `config` - a reference to the Config
+/// * `metadata` - a reference to the private metadata
+fn check_secret_size(config: &Config, metadata: &Metadata) -> StdResult<()> {
+    if let Some(padding) = config.secret_padding.as_ref() {
+        if to_binary(metadata)?.len() > padding.max_secret_size as usize {
+            return Err(ContractError::SecretTooLarge {
+                max_size: padding.max_secret_size,
+            }
+            .into());
+        }
+    }
+    Ok(())
+}
+
 /// Returns HandleResult
 ///
 /// mint a new token
@@ -390,6 +580,7 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
 /// * `owner` - optional owner of this token, if not specified, use the minter's address
 /// * `public_metadata` - optional public metadata viewable by everyone
 /// * `private_metadata` - optional private metadata viewable only by owner and whitelist
+/// * `public_teaser` - optionally true if the public metadata is viewable before burning
 /// * `memo` - optional memo for the mint tx
 #[allow(clippy::too_many_arguments)]
 pub fn mint<S: Storage, A: Api, Q: Querier>(
@@ -401,24 +592,21 @@ pub fn mint<S: Storage, A: Api, Q: Querier>(
     owner: Option<HumanAddr>,
     public_metadata: Option<Metadata>,
     private_metadata: Option<Metadata>,
+    public_teaser: Option<bool>,
     memo: Option<String>,
 ) -> HandleResult {
-    check_status(config.status, priority)?;
-    let verified_owner = if owner.is_none() || owner.as_ref().unwrap() == &env.message.sender {
-        env.message.sender.clone()
-    } else {
-        return Err(StdError::generic_err("You can only mint tokens for yourself"));
-    };
+    check_status(config, priority)?;
     let sender_human = &env.message.sender;
     let sender_raw = deps.api.canonical_address(sender_human)?;
     let mut mints = vec![Mint {
         token_id,
-        owner: Some(verified_owner),
+        owner,
         public_metadata,
         private_metadata,
+        public_teaser,
         memo,
     }];
-    let mut minted = mint_list(deps, &env.block, config, &sender_raw, &mut mints)?;
+    let mut minted = mint_list(deps, &env.block, config, &sender_raw, &mut mints, false)?;
     let minted_str = minted.pop().unwrap_or_else(String::new);
     Ok(HandleResponse {
         messages: vec![],
@@ -447,9 +635,9 @@ pub fn batch_mint<S: Storage, A: Api, Q: Querier>(
     priority: u8,
     mints: &mut Vec<Mint>,
 ) -> HandleResult {
-    check_status(config.status, priority)?;
+    check_status(config, priority)?;
     let sender_raw = deps.api.canonical_address(&env.message.sender)?;
-    let minted = mint_list(deps, &env.block, config, &sender_raw, mints)?;
+    let minted = mint_list(deps, &env.block, config, &sender_raw, mints, false)?;
     Ok(HandleResponse {
         messages: vec![],
         log: vec![log("minted", format!("{:?}", &minted))],
@@ -459,6 +647,126 @@ pub fn batch_mint<S: Storage, A: Api, Q: Querier>(
     })
 }
 
+/// Returns HandleResult
+///
+/// creates a request for a secret to be minted for the sender
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a reference to the Config
+/// * `priority` - u8 representation of highest ContractStatus level this action is permitted
+/// * `description` - description of the requested secret
+/// * `responder` - optional address that must fulfill the request
+/// * `expires` - optional Expiration of the request
+pub fn create_secret_request<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    priority: u8,
+    description: String,
+    responder: Option<HumanAddr>,
+    expires: Option<Expiration>,
+) -> HandleResult {
+    check_status(config, priority)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    let request_id: u32 = may_load(&deps.storage, REQUEST_CNT_KEY)?.unwrap_or(0);
+    let prng_seed: Vec<u8> = load(&deps.storage, PRNG_SEED_KEY)?;
+    // include the request id so codes created in the same block by the same address differ
+    let code = ViewingKey::new(&env, &prng_seed, &request_id.to_le_bytes());
+    let request = SecretRequest {
+        requester: sender_raw.clone(),
+        description,
+        responder: responder
+            .map(|r| deps.api.canonical_address(&r))
+            .transpose()?,
+        expires: expires.unwrap_or_default(),
+        code: code.to_hashed(),
+        token_id: None,
+    };
+    let mut req_store = PrefixedStorage::new(PREFIX_SECRET_REQUESTS, &mut deps.storage);
+    save(&mut req_store, &request_id.to_le_bytes(), &request)?;
+    add_secret_request(&mut deps.storage, &sender_raw, request_id)?;
+    save(&mut deps.storage, REQUEST_CNT_KEY, &(request_id + 1))?;
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::CreateSecretRequest {
+            request_id,
+            code: code.0,
+        })?),
+    })
+}
+
+/// Returns HandleResult
+///
+/// mints the secret of a request for the requester.  The code is only accepted once
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a mutable reference to the Config
+/// * `priority` - u8 representation of highest ContractStatus level this action is permitted
+/// * `request_id` - id of the request to fulfill
+/// * `code` - the request's one-time code
+/// * `private_metadata` - the requested secret
+pub fn fulfill_secret_request<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &mut Config,
+    priority: u8,
+    request_id: u32,
+    code: &str,
+    private_metadata: Metadata,
+) -> HandleResult {
+    check_status(config, priority)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    let req_key = request_id.to_le_bytes();
+    let req_store = ReadonlyPrefixedStorage::new(PREFIX_SECRET_REQUESTS, &deps.storage);
+    let may_request: Option<SecretRequest> = may_load(&req_store, &req_key)?;
+    // do not reveal whether a request exists to someone without its code
+    let mut request = match may_request {
+        Some(req) if ViewingKey(code.to_string()).check_viewing_key(&req.code) => req,
+        _ => return Err(ContractError::SecretRequestInvalid { request_id }.into()),
+    };
+    if let Some(responder) = request.responder.as_ref() {
+        if *responder != sender_raw {
+            return Err(ContractError::SecretRequestResponderOnly { request_id }.into());
+        }
+    }
+    if request.token_id.is_some() {
+        return Err(ContractError::SecretRequestFulfilled { request_id }.into());
+    }
+    if request.expires.is_expired(&env.block) {
+        return Err(ContractError::SecretRequestExpired { request_id }.into());
+    }
+    // a sequential id could be guessed, letting anyone who may burn tokens reveal the secret
+    let token_id = gen_token_id(&deps.storage, &env, code)?;
+    let mut mints = vec![Mint {
+        token_id: Some(token_id),
+        owner: Some(deps.api.human_address(&request.requester)?),
+        public_metadata: None,
+        private_metadata: Some(private_metadata),
+        public_teaser: None,
+        memo: None,
+    }];
+    // the request authorizes the responder to mint for the requester
+    let mut minted = mint_list(deps, &env.block, config, &sender_raw, &mut mints, true)?;
+    let minted_str = minted.pop().unwrap_or_else(String::new);
+    request.token_id = Some(minted_str.clone());
+    let mut req_store = PrefixedStorage::new(PREFIX_SECRET_REQUESTS, &mut deps.storage);
+    save(&mut req_store, &req_key, &request)?;
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![log("minted", &minted_str)],
+        data: Some(to_binary(&HandleAnswer::FulfillSecretRequest {
+            token_id: minted_str,
+        })?),
+    })
+}
+
 /// Returns HandleResult
 ///
 /// sets new public metadata
@@ -479,7 +787,7 @@ pub fn set_public_metadata<S: Storage, A: Api, Q: Querier>(
     token_id: &str,
     metadata: &Metadata,
 ) -> HandleResult {
-    check_status(config.status, priority)?;
+    check_status(config, priority)?;
     let sender_raw = deps.api.canonical_address(&env.message.sender)?;
     set_metadata(
         &mut deps.storage,
@@ -518,7 +826,8 @@ pub fn set_private_metadata<S: Storage, A: Api, Q: Querier>(
     token_id: &str,
     metadata: &Metadata,
 ) -> HandleResult {
-    check_status(config.status, priority)?;
+    check_status(config, priority)?;
+    check_secret_size(config, metadata)?;
     let sender_raw = deps.api.canonical_address(&env.message.sender)?;
     set_metadata(
         &mut deps.storage,
@@ -555,34 +864,27 @@ pub fn reveal<S: Storage, A: Api, Q: Querier>(
     priority: u8,
     token_id: &str,
 ) -> HandleResult {
-    check_status(config.status, priority)?;
+    check_status(config, priority)?;
     if !config.sealed_metadata_is_enabled {
-        return Err(StdError::generic_err(
-            "Sealed metadata functionality is not enabled for this contract",
-        ));
+        return Err(ContractError::SealedMetadataDisabled.into());
     }
     let sender_raw = deps.api.canonical_address(&env.message.sender)?;
-    let custom_err = format!("You do not own token {}", token_id);
-    // if token supply is private, don't leak that the token id does not exist
-    // instead just say they do not own that token
-    let opt_err = if config.token_supply_is_public {
-        None
-    } else {
-        Some(&*custom_err)
-    };
-    let (mut token, idx) = get_token(&deps.storage, token_id, opt_err)?;
-    if token.unwrapped {
-        return Err(StdError::generic_err(
-            "This token has already been unwrapped",
+    let (mut token, idx) = get_token(&deps.storage, token_id, config.token_supply_is_public)?;
+    if token.owner != sender_raw {
+        return Err(token_error(
+            config.token_supply_is_public,
+            ContractError::NotTokenOwner {
+                token_id: token_id.to_string(),
+            },
         ));
     }
-    if token.owner != sender_raw {
-        return Err(StdError::generic_err(custom_err));
+    if token.unwrapped {
+        return Err(ContractError::AlreadyUnwrapped.into());
     }
     token.unwrapped = true;
     let token_key = idx.to_le_bytes();
     let mut info_store = PrefixedStorage::new(PREFIX_INFOS, &mut deps.storage);
-    json_save(&mut info_store, &token_key, &token)?;
+    save_token(&mut info_store, &token_key, &token)?;
     if !config.unwrap_to_private {
         let mut priv_store = PrefixedStorage::new(PREFIX_PRIV_META, &mut deps.storage);
         let may_priv: Option<Metadata> = may_load(&priv_store, &token_key)?;
@@ -599,6 +901,56 @@ pub fn reveal<S: Storage, A: Api, Q: Querier>(
     })
 }
 
+/// Returns HandleResult
+///
+/// moves a token to a new randomly generated id, leaving its info and metadata at the same
+/// index.  The old id will behave as if the token does not exist
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a reference to the Config
+/// * `priority` - u8 representation of highest status level this action is permitted at
+/// * `token_id` - token id String slice of token whose id should be rotated
+pub fn rotate_token_link<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    priority: u8,
+    token_id: &str,
+) -> HandleResult {
+    check_status(config, priority)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    let (token, idx) = get_token(&deps.storage, token_id, config.token_supply_is_public)?;
+    if token.owner != sender_raw {
+        return Err(token_error(
+            config.token_supply_is_public,
+            ContractError::NotTokenOwner {
+                token_id: token_id.to_string(),
+            },
+        ));
+    }
+    let new_id = gen_token_id(&deps.storage, &env, token_id)?;
+    let map2idx = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_INDEX, &deps.storage);
+    let may_used: Option<u32> = may_load(&map2idx, new_id.as_bytes())?;
+    if may_used.is_some() {
+        return Err(ContractError::TokenIdCollision.into());
+    }
+    let mut map2idx = PrefixedStorage::new(PREFIX_MAP_TO_INDEX, &mut deps.storage);
+    remove(&mut map2idx, token_id.as_bytes());
+    save(&mut map2idx, new_id.as_bytes(), &idx)?;
+    let mut map2id = PrefixedStorage::new(PREFIX_MAP_TO_ID, &mut deps.storage);
+    save(&mut map2id, &idx.to_le_bytes(), &new_id)?;
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::RotateTokenLink {
+            token_id: new_id,
+        })?),
+    })
+}
+
 /// Returns HandleResult
 ///
 /// grants/revokes trasfer permission on a token
@@ -624,46 +976,44 @@ pub fn approve_revoke<S: Storage, A: Api, Q: Querier>(
     expires: Option<Expiration>,
     is_approve: bool,
 ) -> HandleResult {
-    check_status(config.status, priority)?;
+    check_status(config, priority)?;
     let address_raw = deps.api.canonical_address(spender)?;
     let sender_raw = deps.api.canonical_address(&env.message.sender)?;
-    let custom_err = format!(
-        "Not authorized to grant/revoke transfer permission for token {}",
-        token_id
-    );
-    // if token supply is private, don't leak that the token id does not exist
-    // instead just say they are not authorized for that token
-    let opt_err = if config.token_supply_is_public {
-        None
-    } else {
-        Some(&*custom_err)
-    };
-    let (token, idx) = get_token(&deps.storage, token_id, opt_err)?;
-    let mut all_perm: Option<Vec<Permission>> = None;
+    let (token, idx) = get_token(&deps.storage, token_id, config.token_supply_is_public)?;
     let mut from_oper = false;
     let transfer_idx = PermissionType::Transfer.to_usize();
     // if not called by the owner, check if message sender has operator status
     if token.owner != sender_raw {
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let may_list: Option<Vec<Permission>> = json_may_load(&all_store, token.owner.as_slice())?;
-        if let Some(list) = may_list.clone() {
-            if let Some(perm) = list.iter().find(|&p| p.address == sender_raw) {
-                if let Some(exp) = perm.expirations[transfer_idx] {
-                    if exp.is_expired(&env.block) {
-                        return Err(StdError::generic_err(format!(
+        if let Some(exp) =
+            may_load_all_perm(&deps.storage, &token.owner, &sender_raw, transfer_idx)?
+        {
+            if exp.is_expired(&env.block) {
+                return Err(token_error(
+                    config.token_supply_is_public,
+                    ContractError::TokenAccessExpired {
+                        token_id: token_id.to_string(),
+                        detail: format!(
                             "Transfer authority for all tokens of {} has expired",
                             &deps.api.human_address(&token.owner)?
-                        )));
-                    } else {
-                        from_oper = true;
-                    }
-                }
+                        ),
+                    },
+                ));
+            } else {
+                from_oper = true;
             }
         }
         if !from_oper {
-            return Err(StdError::generic_err(custom_err));
+            return Err(token_error(
+                config.token_supply_is_public,
+                ContractError::TokenUnauthorized {
+                    token_id: token_id.to_string(),
+                    detail: format!(
+                        "Not authorized to grant/revoke transfer permission for token {}",
+                        token_id
+                    ),
+                },
+            ));
         }
-        all_perm = may_list;
     }
     let mut accesses: [Option<AccessLevel>; 3] = [None, None, None];
     let response: HandleAnswer;
@@ -689,7 +1039,6 @@ pub fn approve_revoke<S: Storage, A: Api, Q: Querier>(
         &address_raw,
         &owner,
         &mut proc_info,
-        all_perm,
     )?;
     let res = HandleResponse {
         messages: vec![],
@@ -715,7 +1064,7 @@ pub fn make_owner_private<S: Storage, A: Api, Q: Querier>(
     config: &Config,
     priority: u8,
 ) -> HandleResult {
-    check_status(config.status, priority)?;
+    check_status(config, priority)?;
     let sender_raw = deps.api.canonical_address(&env.message.sender)?;
     // only need to do this if the contract has public ownership
     if config.owner_is_public {
@@ -756,23 +1105,17 @@ pub fn set_global_approval<S: Storage, A: Api, Q: Querier>(
     view_private_metadata: Option<AccessLevel>,
     expires: Option<Expiration>,
 ) -> HandleResult {
-    check_status(config.status, priority)?;
+    check_status(config, priority)?;
     let token_given: bool;
     // use this "address" to represent global permission
     let global_raw = CanonicalAddr(Binary::from(b"public"));
     let sender_raw = deps.api.canonical_address(&env.message.sender)?;
-    let mut custom_err = String::new();
+    let mut given_id = String::new();
     let (token, idx) = if let Some(id) = token_id {
         token_given = true;
-        custom_err = format!("You do not own token {}", id);
-        // if token supply is private, don't leak that the token id does not exist
-        // instead just say they do not own that token
-        let opt_err = if config.token_supply_is_public {
-            None
-        } else {
-            Some(&*custom_err)
-        };
-        get_token(&deps.storage, &id, opt_err)?
+        let found = get_token(&deps.storage, &id, config.token_supply_is_public)?;
+        given_id = id;
+        found
     } else {
         token_given = false;
         (
@@ -780,13 +1123,18 @@ pub fn set_global_approval<S: Storage, A: Api, Q: Querier>(
                 owner: sender_raw.clone(),
                 permissions: Vec::new(),
                 unwrapped: false,
+                public_teaser: false,
+                recipient_only: false,
             },
             0,
         )
     };
     // if trying to set token permissions when you are not the owner
     if token_given && token.owner != sender_raw {
-        return Err(StdError::generic_err(custom_err));
+        return Err(token_error(
+            config.token_supply_is_public,
+            ContractError::NotTokenOwner { token_id: given_id },
+        ));
     }
     let mut accesses: [Option<AccessLevel>; 3] = [None, None, None];
     accesses[PermissionType::ViewOwner.to_usize()] = view_owner;
@@ -805,7 +1153,6 @@ pub fn set_global_approval<S: Storage, A: Api, Q: Querier>(
         &global_raw,
         &sender_raw,
         &mut proc_info,
-        None,
     )?;
     Ok(HandleResponse {
         messages: vec![],
@@ -847,22 +1194,16 @@ pub fn set_whitelisted_approval<S: Storage, A: Api, Q: Querier>(
     expires: Option<Expiration>,
     response_type: SetAppResp,
 ) -> HandleResult {
-    check_status(config.status, priority)?;
+    check_status(config, priority)?;
     let token_given: bool;
     let address_raw = deps.api.canonical_address(address)?;
     let sender_raw = deps.api.canonical_address(&env.message.sender)?;
-    let mut custom_err = String::new();
+    let mut given_id = String::new();
     let (token, idx) = if let Some(id) = token_id {
         token_given = true;
-        custom_err = format!("You do not own token {}", id);
-        // if token supply is private, don't leak that the token id does not exist
-        // instead just say they do not own that token
-        let opt_err = if config.token_supply_is_public {
-            None
-        } else {
-            Some(&*custom_err)
-        };
-        get_token(&deps.storage, &id, opt_err)?
+        let found = get_token(&deps.storage, &id, config.token_supply_is_public)?;
+        given_id = id;
+        found
     } else {
         token_given = false;
         (
@@ -870,13 +1211,18 @@ pub fn set_whitelisted_approval<S: Storage, A: Api, Q: Querier>(
                 owner: sender_raw.clone(),
                 permissions: Vec::new(),
                 unwrapped: false,
+                public_teaser: false,
+                recipient_only: false,
             },
             0,
         )
     };
     // if trying to set token permissions when you are not the owner
     if token_given && token.owner != sender_raw {
-        return Err(StdError::generic_err(custom_err));
+        return Err(token_error(
+            config.token_supply_is_public,
+            ContractError::NotTokenOwner { token_id: given_id },
+        ));
     }
     let mut accesses: [Option<AccessLevel>; 3] = [None, None, None];
     accesses[PermissionType::ViewOwner.to_usize()] = view_owner;
@@ -896,7 +1242,6 @@ pub fn set_whitelisted_approval<S: Storage, A: Api, Q: Querier>(
         &address_raw,
         &sender_raw,
         &mut proc_info,
-        None,
     )?;
     let response = match response_type {
         SetAppResp::SetWhitelistedApproval => {
@@ -924,25 +1269,28 @@ pub fn set_whitelisted_approval<S: Storage, A: Api, Q: Querier>(
 /// * `config` - a mutable reference to the Config
 /// * `priority` - u8 representation of highest ContractStatus level this action is permitted
 /// * `burns` - a mutable reference to the list of burns to perform
+/// * `best_effort` - true if tokens that can not be burned should be skipped and reported
 pub fn batch_burn_nft<S: Storage, A: Api, Q: Querier>(
     deps: &mut Extern<S, A, Q>,
     env: Env,
     config: &mut Config,
     priority: u8,
     burns: &mut Vec<Burn>,
+    best_effort: bool,
 ) -> HandleResult {
-    check_status(config.status, priority)?;
+    check_status(config, priority)?;
     if !config.burn_is_enabled {
-        return Err(StdError::generic_err(
-            "Burn functionality is not enabled for this token",
-        ));
+        return Err(ContractError::BurnDisabled.into());
     }
     let sender_raw = deps.api.canonical_address(&env.message.sender)?;
-    burn_list(deps, &env.block, config, &sender_raw, burns)?;
+    let (secrets, results) = burn_list(deps, &env.block, config, &sender_raw, burns, best_effort)?;
     let res = HandleResponse {
         messages: vec![],
         log: vec![],
-        data: Some(to_binary(&HandleAnswer::BatchBurnNft { status: Success })?),
+        data: Some(to_binary(&HandleAnswer::BatchBurnNft {
+            secrets,
+            results: if best_effort { Some(results) } else { None },
+        })?),
     };
     Ok(res)
 }
@@ -967,14 +1315,14 @@ fn burn_nft<S: Storage, A: Api, Q: Querier>(
     token_id: String,
     memo: Option<String>,
 ) -> HandleResult {
-    check_status(config.status, priority)?;
+    check_status(config, priority)?;
     let sender_raw = deps.api.canonical_address(&env.message.sender)?;
     let mut burns = vec![Burn {
-        token_ids: vec![token_id],
+        token_ids: vec![token_id.clone()],
         memo,
     }];
-    let secrets = burn_list(deps, &env.block, config, &sender_raw, &mut burns)?;
-    let secret = secrets.first().unwrap().clone();
+    let (secrets, _results) = burn_list(deps, &env.block, config, &sender_raw, &mut burns, false)?;
+    let secret = secrets.into_iter().next();
 
     let res = HandleResponse {
         messages: vec![],
@@ -986,54 +1334,73 @@ fn burn_nft<S: Storage, A: Api, Q: Querier>(
 
 /// Returns HandleResult
 ///
-/// transfer many tokens
+/// removes the secrets retained from the message sender's burns
 ///
 /// # Arguments
 ///
 /// * `deps` - mutable reference to Extern containing all the contract's external dependencies
 /// * `env` - Env of contract's environment
-/// * `config` - a mutable reference to the Config
+/// * `config` - a reference to the Config
 /// * `priority` - u8 representation of highest ContractStatus level this action is permitted
-/// * `transfers` - list of transfers to perform
-pub fn batch_transfer_nft<S: Storage, A: Api, Q: Querier>(
-    _deps: &mut Extern<S, A, Q>,
-    _env: Env,
-    _config: &mut Config,
-    _priority: u8,
-    _transfers: Vec<Transfer>,
+pub fn forget_retrieved<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    priority: u8,
 ) -> HandleResult {
-    Err(StdError::generic_err("Token transfer not permitted"))
+    check_status(config, priority)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    let mut retrieved_store = PrefixedStorage::new(PREFIX_RETRIEVED, &mut deps.storage);
+    remove(&mut retrieved_store, sender_raw.as_slice());
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::ForgetRetrieved {
+            status: Success,
+        })?),
+    })
 }
 
 /// Returns HandleResult
 ///
-/// transfer a token
+/// removes the message sender's transaction history that is outside the retention limits
 ///
 /// # Arguments
 ///
 /// * `deps` - mutable reference to Extern containing all the contract's external dependencies
 /// * `env` - Env of contract's environment
-/// * `config` - a mutable reference to the Config
+/// * `config` - a reference to the Config
 /// * `priority` - u8 representation of highest ContractStatus level this action is permitted
-/// * `recipient` - the address receiving the token
-/// * `token_id` - token id String of token to be transferred
-/// * `memo` - optional memo for the mint tx
-pub fn transfer_nft<S: Storage, A: Api, Q: Querier>(
-    _deps: &mut Extern<S, A, Q>,
-    _env: Env,
-    _config: &mut Config,
-    _priority: u8,
-    _recipient: HumanAddr,
-    _token_id: String,
-    _memo: Option<String>,
+/// * `keep` - optional max number of the sender's most recent txs to keep
+pub fn prune_history<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    priority: u8,
+    keep: Option<u32>,
 ) -> HandleResult {
-    Err(StdError::generic_err("Token transfer not permitted"))
+    check_status(config, priority)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    let pruned = prune_txs(
+        &mut deps.storage,
+        &sender_raw,
+        &config.tx_retention,
+        env.block.height,
+        keep,
+    )?;
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::PruneHistory {
+            status: Success,
+            pruned,
+        })?),
+    })
 }
 
 /// Returns HandleResult
 ///
-/// sends tokens to contracts, and calls those contracts' ReceiveNft.  Will error if any
-/// contract has not registered its ReceiveNft
+/// transfer many tokens
 ///
 /// # Arguments
 ///
@@ -1041,17 +1408,111 @@ pub fn transfer_nft<S: Storage, A: Api, Q: Querier>(
 /// * `env` - Env of contract's environment
 /// * `config` - a mutable reference to the Config
 /// * `priority` - u8 representation of highest ContractStatus level this action is permitted
-/// * `sends` - list of SendNfts to perform
-fn batch_send_nft<S: Storage, A: Api, Q: Querier>(
-    _deps: &mut Extern<S, A, Q>,
-    _env: Env,
-    _config: &mut Config,
-    _priority: u8,
-    _sends: Vec<Send>,
-) -> HandleResult {
-    Err(StdError::generic_err("Token transfer not permitted"))
-}
-
+/// * `transfers` - list of transfers to perform
+pub fn batch_transfer_nft<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &mut Config,
+    priority: u8,
+    transfers: Vec<Transfer>,
+) -> HandleResult {
+    check_status(config, priority)?;
+    if !transfers_supported(config) {
+        return Err(ContractError::TransferUnsupported.into());
+    }
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    let _m = send_list(deps, &env, config, &sender_raw, Some(transfers), None)?;
+
+    let res = HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::BatchTransferNft {
+            status: Success,
+        })?),
+    };
+    Ok(res)
+}
+
+/// Returns HandleResult
+///
+/// transfer a token
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a mutable reference to the Config
+/// * `priority` - u8 representation of highest ContractStatus level this action is permitted
+/// * `recipient` - the address receiving the token
+/// * `token_id` - token id String of token to be transferred
+/// * `recipient_only` - optionally true if only the recipient may burn the token
+/// * `memo` - optional memo for the mint tx
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_nft<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &mut Config,
+    priority: u8,
+    recipient: HumanAddr,
+    token_id: String,
+    recipient_only: Option<bool>,
+    memo: Option<String>,
+) -> HandleResult {
+    check_status(config, priority)?;
+    if !transfers_supported(config) {
+        return Err(ContractError::TransferUnsupported.into());
+    }
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    let transfers = Some(vec![Transfer {
+        recipient,
+        token_ids: vec![token_id],
+        recipient_only,
+        memo,
+    }]);
+    let _m = send_list(deps, &env, config, &sender_raw, transfers, None)?;
+
+    let res = HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::TransferNft { status: Success })?),
+    };
+    Ok(res)
+}
+
+/// Returns HandleResult
+///
+/// sends tokens to contracts, and calls those contracts' ReceiveNft.  Will error if any
+/// contract has not registered its ReceiveNft
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a mutable reference to the Config
+/// * `priority` - u8 representation of highest ContractStatus level this action is permitted
+/// * `sends` - list of SendNfts to perform
+fn batch_send_nft<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &mut Config,
+    priority: u8,
+    sends: Vec<Send>,
+) -> HandleResult {
+    check_status(config, priority)?;
+    if !transfers_supported(config) {
+        return Err(ContractError::TransferUnsupported.into());
+    }
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    let messages = send_list(deps, &env, config, &sender_raw, None, Some(sends))?;
+
+    let res = HandleResponse {
+        messages,
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::BatchSendNft { status: Success })?),
+    };
+    Ok(res)
+}
+
 /// Returns HandleResult
 ///
 /// sends a token to a contract, and calls that contract's ReceiveNft.  Will error if the
@@ -1066,19 +1527,40 @@ fn batch_send_nft<S: Storage, A: Api, Q: Querier>(
 /// * `contract` - the address of the contract receiving the token
 /// * `token_id` - ID String of the token that was sent
 /// * `msg` - optional msg used to control ReceiveNft logic
+/// * `recipient_only` - optionally true if only the receiving contract may burn the token
 /// * `memo` - optional memo for the mint tx
 #[allow(clippy::too_many_arguments)]
 fn send_nft<S: Storage, A: Api, Q: Querier>(
-    _deps: &mut Extern<S, A, Q>,
-    _env: Env,
-    _onfig: &mut Config,
-    _riority: u8,
-    _contract: HumanAddr,
-    _token_id: String,
-    _msg: Option<Binary>,
-    _memo: Option<String>,
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &mut Config,
+    priority: u8,
+    contract: HumanAddr,
+    token_id: String,
+    msg: Option<Binary>,
+    recipient_only: Option<bool>,
+    memo: Option<String>,
 ) -> HandleResult {
-    Err(StdError::generic_err("Token transfer not permitted"))
+    check_status(config, priority)?;
+    if !transfers_supported(config) {
+        return Err(ContractError::TransferUnsupported.into());
+    }
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    let sends = Some(vec![Send {
+        contract,
+        token_ids: vec![token_id],
+        msg,
+        recipient_only,
+        memo,
+    }]);
+    let messages = send_list(deps, &env, config, &sender_raw, None, sends)?;
+
+    let res = HandleResponse {
+        messages,
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::SendNft { status: Success })?),
+    };
+    Ok(res)
 }
 
 /// Returns HandleResult
@@ -1094,14 +1576,32 @@ fn send_nft<S: Storage, A: Api, Q: Querier>(
 /// * `code_hash` - code hash String of the registering contract
 /// * `impl_batch` - optionally true if the contract also implements BatchReceiveNft
 pub fn register_receive_nft<S: Storage, A: Api, Q: Querier>(
-    _deps: &mut Extern<S, A, Q>,
-    _env: Env,
-    _config: &Config,
-    _priority: u8,
-    _code_hash: String,
-    _impl_batch: Option<bool>,
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    priority: u8,
+    code_hash: String,
+    impl_batch: Option<bool>,
 ) -> HandleResult {
-    Err(StdError::generic_err("Token transfer not permitted. ReceiveNft registration not supported."))
+    check_status(config, priority)?;
+    if !transfers_supported(config) {
+        return Err(ContractError::ReceiveUnsupported.into());
+    }
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    let regrec = ReceiveRegistration {
+        code_hash,
+        impl_batch: impl_batch.unwrap_or(false),
+    };
+    let mut store = PrefixedStorage::new(PREFIX_RECEIVERS, &mut deps.storage);
+    save(&mut store, sender_raw.as_slice(), &regrec)?;
+    let res = HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::RegisterReceiveNft {
+            status: Success,
+        })?),
+    };
+    Ok(res)
 }
 
 /// Returns HandleResult
@@ -1122,7 +1622,7 @@ pub fn create_key<S: Storage, A: Api, Q: Querier>(
     priority: u8,
     entropy: &str,
 ) -> HandleResult {
-    check_status(config.status, priority)?;
+    check_status(config, priority)?;
     let prng_seed: Vec<u8> = load(&deps.storage, PRNG_SEED_KEY)?;
     let key = ViewingKey::new(&env, &prng_seed, entropy.as_ref());
     let message_sender = deps.api.canonical_address(&env.message.sender)?;
@@ -1155,12 +1655,12 @@ pub fn set_key<S: Storage, A: Api, Q: Querier>(
     _priority: u8,
     _key: String,
 ) -> HandleResult {
-    return Err(StdError::generic_err("Viewing keys cannot be set, they must be generated"));
+    Err(ContractError::SetViewingKeyUnsupported.into())
 }
 
 /// Returns HandleResult
 ///
-/// add a list of minters
+/// revokes a query permit the sender signed
 ///
 /// # Arguments
 ///
@@ -1168,20 +1668,27 @@ pub fn set_key<S: Storage, A: Api, Q: Querier>(
 /// * `env` - Env of contract's environment
 /// * `config` - a reference to the Config
 /// * `priority` - u8 representation of highest ContractStatus level this action is permitted
-/// * `new_minters` - list of minter addresses to add
-pub fn add_minters<S: Storage, A: Api, Q: Querier>(
-    _deps: &mut Extern<S, A, Q>,
-    _env: Env,
-    _config: &Config,
-    _priority: u8,
-    _new_minters: &[HumanAddr],
+/// * `permit_name` - string slice of the name of the permit to revoke
+pub fn revoke_permit<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    priority: u8,
+    permit_name: &str,
 ) -> HandleResult {
-    Err(StdError::generic_err("Minting is not restricted, minter list not supported"))
+    check_status(config, priority)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    crate::permit::revoke_permit(&mut deps.storage, &sender_raw, permit_name)?;
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::RevokePermit { status: Success })?),
+    })
 }
 
 /// Returns HandleResult
 ///
-/// remove a list of minters
+/// creates a named viewing key limited to some queries and optionally some tokens
 ///
 /// # Arguments
 ///
@@ -1189,20 +1696,57 @@ pub fn add_minters<S: Storage, A: Api, Q: Querier>(
 /// * `env` - Env of contract's environment
 /// * `config` - a reference to the Config
 /// * `priority` - u8 representation of highest ContractStatus level this action is permitted
-/// * `no_minters` - list of minter addresses to remove
-pub fn remove_minters<S: Storage, A: Api, Q: Querier>(
-    _deps: &mut Extern<S, A, Q>,
-    _env: Env,
-    _config: &Config,
-    _priority: u8,
-    _no_minters: &[HumanAddr],
+/// * `name` - name of the key
+/// * `entropy` - string slice of the input String to be used as entropy in randomization
+/// * `queries` - list of the queries the key may be used for
+/// * `token_ids` - optional list of the only tokens the key may be used for
+/// * `expires` - optional Expiration of the key
+#[allow(clippy::too_many_arguments)]
+pub fn create_scoped_key<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    priority: u8,
+    name: String,
+    entropy: &str,
+    queries: Vec<ScopedQuery>,
+    token_ids: Option<Vec<String>>,
+    expires: Option<Expiration>,
 ) -> HandleResult {
-    Err(StdError::generic_err("Minting is not restricted, minter list not supported"))
+    check_status(config, priority)?;
+    if queries.is_empty() {
+        return Err(ContractError::ScopedKeyEmpty.into());
+    }
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    let prng_seed: Vec<u8> = load(&deps.storage, PRNG_SEED_KEY)?;
+    let mut key_store = PrefixedStorage::new(PREFIX_SCOPED_KEYS, &mut deps.storage);
+    let mut keys: Vec<ScopedKey> =
+        may_load(&key_store, sender_raw.as_slice())?.unwrap_or_else(Vec::new);
+    if keys.iter().any(|k| k.name == name) {
+        return Err(ContractError::ScopedKeyExists { name }.into());
+    }
+    // include the name so keys created in the same block with the same entropy differ
+    let key = ViewingKey::new(&env, &prng_seed, format!("{}:{}", name, entropy).as_bytes());
+    keys.push(ScopedKey {
+        name,
+        key: key.to_hashed(),
+        queries,
+        token_ids,
+        expires: expires.unwrap_or_default(),
+    });
+    save(&mut key_store, sender_raw.as_slice(), &keys)?;
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::ViewingKey {
+            key: format!("{}", key),
+        })?),
+    })
 }
 
 /// Returns HandleResult
 ///
-/// define the exact list of minters
+/// revokes a scoped viewing key the sender created
 ///
 /// # Arguments
 ///
@@ -1210,173 +1754,600 @@ pub fn remove_minters<S: Storage, A: Api, Q: Querier>(
 /// * `env` - Env of contract's environment
 /// * `config` - a reference to the Config
 /// * `priority` - u8 representation of highest ContractStatus level this action is permitted
-/// * `human_minters` - exact list of minter addresses
-pub fn set_minters<S: Storage, A: Api, Q: Querier>(
-    _deps: &mut Extern<S, A, Q>,
-    _env: Env,
-    _config: &Config,
-    _priority: u8,
-    _human_minters: &[HumanAddr],
+/// * `name` - string slice of the name of the key to revoke
+pub fn revoke_scoped_key<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    priority: u8,
+    name: &str,
 ) -> HandleResult {
-    Err(StdError::generic_err("Minting is not restricted, minter list not supported"))
+    check_status(config, priority)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    let mut key_store = PrefixedStorage::new(PREFIX_SCOPED_KEYS, &mut deps.storage);
+    let mut keys: Vec<ScopedKey> =
+        may_load(&key_store, sender_raw.as_slice())?.unwrap_or_else(Vec::new);
+    let pos = keys.iter().position(|k| k.name == name).ok_or_else(|| {
+        StdError::from(ContractError::ScopedKeyNotFound {
+            name: name.to_string(),
+        })
+    })?;
+    keys.swap_remove(pos);
+    if keys.is_empty() {
+        remove(&mut key_store, sender_raw.as_slice());
+    } else {
+        save(&mut key_store, sender_raw.as_slice(), &keys)?;
+    }
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::RevokeScopedKey {
+            status: Success,
+        })?),
+    })
 }
 
 /// Returns HandleResult
 ///
-/// change the admin address
+/// add a list of minters
 ///
 /// # Arguments
 ///
 /// * `deps` - mutable reference to Extern containing all the contract's external dependencies
 /// * `env` - Env of contract's environment
-/// * `config` - a mutable reference to the Config
+/// * `config` - a reference to the Config
 /// * `priority` - u8 representation of highest ContractStatus level this action is permitted
-/// * `address` - new admin address
-pub fn change_admin<S: Storage, A: Api, Q: Querier>(
+/// * `new_minters` - list of minter addresses to add
+pub fn add_minters<S: Storage, A: Api, Q: Querier>(
     deps: &mut Extern<S, A, Q>,
     env: Env,
-    config: &mut Config,
+    config: &Config,
     priority: u8,
-    address: &HumanAddr,
+    new_minters: &[HumanAddr],
 ) -> HandleResult {
-    check_status(config.status, priority)?;
-    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
-    if config.admin != sender_raw {
-        return Err(StdError::generic_err(
-            "This is an admin command and can only be run from the admin address",
-        ));
+    let mut minters = minters_prep(deps, &env, config, priority)?;
+    let old_len = minters.len();
+    for minter in new_minters {
+        let minter_raw = deps.api.canonical_address(minter)?;
+        if !minters.contains(&minter_raw) {
+            minters.push(minter_raw);
+        }
     }
-    let new_admin = deps.api.canonical_address(address)?;
-    if new_admin != config.admin {
-        config.admin = new_admin;
-        save(&mut deps.storage, CONFIG_KEY, &config)?;
+    // only save if the list changed
+    if old_len != minters.len() {
+        save(&mut deps.storage, MINTERS_KEY, &minters)?;
     }
     Ok(HandleResponse {
         messages: vec![],
         log: vec![],
-        data: Some(to_binary(&HandleAnswer::ChangeAdmin { status: Success })?),
+        data: Some(to_binary(&HandleAnswer::AddMinters { status: Success })?),
     })
 }
 
 /// Returns HandleResult
 ///
-/// set the contract status level
+/// remove a list of minters
 ///
 /// # Arguments
 ///
 /// * `deps` - mutable reference to Extern containing all the contract's external dependencies
 /// * `env` - Env of contract's environment
-/// * `config` - a mutable reference to the Config
-/// * `level` - new ContractStatus
-pub fn set_contract_status<S: Storage, A: Api, Q: Querier>(
+/// * `config` - a reference to the Config
+/// * `priority` - u8 representation of highest ContractStatus level this action is permitted
+/// * `no_minters` - list of minter addresses to remove
+pub fn remove_minters<S: Storage, A: Api, Q: Querier>(
     deps: &mut Extern<S, A, Q>,
     env: Env,
-    config: &mut Config,
-    level: ContractStatus,
+    config: &Config,
+    priority: u8,
+    no_minters: &[HumanAddr],
 ) -> HandleResult {
-    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
-    if config.admin != sender_raw {
-        return Err(StdError::generic_err(
-            "This is an admin command and can only be run from the admin address",
-        ));
+    let mut minters = minters_prep(deps, &env, config, priority)?;
+    let old_len = minters.len();
+    for minter in no_minters {
+        let minter_raw = deps.api.canonical_address(minter)?;
+        minters.retain(|m| *m != minter_raw);
     }
-    let new_status = level.to_u8();
-    if config.status != new_status {
-        config.status = new_status;
-        save(&mut deps.storage, CONFIG_KEY, &config)?;
+    // only save if the list changed
+    if old_len != minters.len() {
+        save(&mut deps.storage, MINTERS_KEY, &minters)?;
     }
     Ok(HandleResponse {
         messages: vec![],
         log: vec![],
-        data: Some(to_binary(&HandleAnswer::SetContractStatus {
-            status: Success,
-        })?),
+        data: Some(to_binary(&HandleAnswer::RemoveMinters { status: Success })?),
     })
 }
 
-/////////////////////////////////////// Query /////////////////////////////////////
-/// Returns QueryResult
+/// Returns HandleResult
+///
+/// define the exact list of minters
 ///
 /// # Arguments
 ///
-/// * `deps` - reference to Extern containing all the contract's external dependencies
-/// * `msg` - QueryMsg passed in with the query call
-pub fn query<S: Storage, A: Api, Q: Querier>(deps: &Extern<S, A, Q>, msg: QueryMsg) -> QueryResult {
-    let response = match msg {
-        QueryMsg::ContractInfo {} => query_contract_info(&deps.storage),
-        QueryMsg::ContractConfig {} => query_config(&deps.storage),
-        QueryMsg::Minters {} => query_minters(deps),
-        QueryMsg::NumTokens { viewer } => query_num_tokens(deps, viewer),
-        QueryMsg::AllTokens {
-            viewer,
-            start_after,
-            limit,
-        } => query_all_tokens(deps, viewer, start_after, limit),
-        QueryMsg::OwnerOf {
-            token_id,
-            viewer,
-            include_expired,
-        } => query_owner_of(deps, &token_id, viewer, include_expired),
-        QueryMsg::NftInfo { token_id: _ } => query_nft_info(),
-        QueryMsg::PrivateMetadata { token_id, viewer } => {
-            query_private_meta(deps, &token_id, viewer)
-        }
-        QueryMsg::AllNftInfo {
-            token_id,
-            viewer,
-            include_expired,
-        } => query_all_nft_info(deps, &token_id, viewer, include_expired),
-        QueryMsg::NftDossier {
-            token_id,
-            viewer,
-            include_expired,
-        } => query_nft_dossier(deps, &token_id, viewer, include_expired),
-        QueryMsg::TokenApprovals {
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a reference to the Config
+/// * `priority` - u8 representation of highest ContractStatus level this action is permitted
+/// * `human_minters` - exact list of minter addresses
+pub fn set_minters<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    priority: u8,
+    human_minters: &[HumanAddr],
+) -> HandleResult {
+    minters_prep(deps, &env, config, priority)?;
+    let mut minters: Vec<CanonicalAddr> = Vec::new();
+    for minter in human_minters {
+        let minter_raw = deps.api.canonical_address(minter)?;
+        if !minters.contains(&minter_raw) {
+            minters.push(minter_raw);
+        }
+    }
+    save(&mut deps.storage, MINTERS_KEY, &minters)?;
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::SetMinters { status: Success })?),
+    })
+}
+
+/// Returns StdResult<Vec<CanonicalAddr>>
+///
+/// verifies that the minter list can be changed by the sender and returns the current list
+///
+/// # Arguments
+///
+/// * `deps` - a reference to Extern containing all the contract's external dependencies
+/// * `env` - a reference to the Env of contract's environment
+/// * `config` - a reference to the Config
+/// * `priority` - u8 representation of highest ContractStatus level this action is permitted
+fn minters_prep<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    env: &Env,
+    config: &Config,
+    priority: u8,
+) -> StdResult<Vec<CanonicalAddr>> {
+    check_status(config, priority)?;
+    if config.open_minting {
+        return Err(ContractError::MintersUnsupported.into());
+    }
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    if config.admin != sender_raw {
+        return Err(ContractError::AdminOnly.into());
+    }
+    Ok(may_load(&deps.storage, MINTERS_KEY)?.unwrap_or_else(Vec::new))
+}
+
+/// Returns HandleResult
+///
+/// change the admin address
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a mutable reference to the Config
+/// * `priority` - u8 representation of highest ContractStatus level this action is permitted
+/// * `address` - new admin address
+pub fn change_admin<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &mut Config,
+    priority: u8,
+    address: &HumanAddr,
+) -> HandleResult {
+    check_status(config, priority)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    if config.admin != sender_raw {
+        return Err(ContractError::AdminOnly.into());
+    }
+    let new_admin = deps.api.canonical_address(address)?;
+    if new_admin != config.admin {
+        config.admin = new_admin;
+        save(&mut deps.storage, CONFIG_KEY, &config)?;
+    }
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::ChangeAdmin { status: Success })?),
+    })
+}
+
+/// Returns HandleResult
+///
+/// migrate up to batch_size stored records to the current storage schema, resuming where the
+/// previous call left off.  Token infos, inventories and approvals are migrated in mint order,
+/// followed by tx records
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a mutable reference to the Config
+/// * `priority` - u8 representation of highest ContractStatus level this action is permitted
+/// * `batch_size` - maximum number of records to migrate
+pub fn migrate_storage<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &mut Config,
+    priority: u8,
+    batch_size: u32,
+) -> HandleResult {
+    check_status(config, priority)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    if config.admin != sender_raw {
+        return Err(ContractError::AdminOnly.into());
+    }
+    let mut complete = config.schema_version >= SCHEMA_VERSION;
+    if !complete {
+        let mut progress: MigrationProgress =
+            may_load(&deps.storage, MIGRATION_KEY)?.unwrap_or_default();
+        let mut remaining = batch_size;
+        while remaining > 0 && progress.tokens < config.mint_cnt {
+            let token_key = progress.tokens.to_le_bytes();
+            let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
+            let may_token = may_load_token(&info_store, &token_key)?;
+            // burned tokens no longer have an info record
+            if let Some(token) = may_token {
+                let mut info_store = PrefixedStorage::new(PREFIX_INFOS, &mut deps.storage);
+                save_token(&mut info_store, &token_key, &token)?;
+                migrate_legacy_inventory(&mut deps.storage, &token.owner)?;
+                migrate_legacy_approvals(&mut deps.storage, &token.owner)?;
+            }
+            progress.tokens += 1;
+            remaining -= 1;
+        }
+        while remaining > 0 && progress.txs < config.tx_cnt {
+            let tx_key = progress.txs.to_le_bytes();
+            let tx_store = ReadonlyPrefixedStorage::new(PREFIX_TXS, &deps.storage);
+            let may_tx: Option<StoredTx> = json_may_load(&tx_store, &tx_key)?;
+            if let Some(tx) = may_tx {
+                let mut tx_store = PrefixedStorage::new(PREFIX_TXS, &mut deps.storage);
+                json_save(&mut tx_store, &tx_key, &tx)?;
+            }
+            progress.txs += 1;
+            remaining -= 1;
+        }
+        complete = progress.tokens >= config.mint_cnt && progress.txs >= config.tx_cnt;
+        if complete {
+            // persist the count so the legacy token list is no longer needed
+            let count = get_token_count(&deps.storage)?;
+            set_token_count(&mut deps.storage, count)?;
+            remove(&mut deps.storage, MIGRATION_KEY);
+            config.schema_version = SCHEMA_VERSION;
+        } else {
+            save(&mut deps.storage, MIGRATION_KEY, &progress)?;
+        }
+        // always re-save so a legacy config is rewritten in the current layout
+        save(&mut deps.storage, CONFIG_KEY, &config)?;
+    }
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::Migrate {
+            status: Success,
+            complete,
+        })?),
+    })
+}
+
+/// Returns HandleResult
+///
+/// set the limits on how much transaction history is kept for each address
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a mutable reference to the Config
+/// * `priority` - u8 representation of highest ContractStatus level this action is permitted
+/// * `retention` - new retention limits
+pub fn set_tx_retention<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &mut Config,
+    priority: u8,
+    retention: TxRetention,
+) -> HandleResult {
+    check_status(config, priority)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    if config.admin != sender_raw {
+        return Err(ContractError::AdminOnly.into());
+    }
+    if config.tx_retention != retention {
+        config.tx_retention = retention;
+        save(&mut deps.storage, CONFIG_KEY, &config)?;
+    }
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::SetTxRetention {
+            status: Success,
+        })?),
+    })
+}
+
+/// Returns HandleResult
+///
+/// set the contract status level
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a mutable reference to the Config
+/// * `level` - new ContractStatus
+pub fn set_contract_status<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &mut Config,
+    level: ContractStatus,
+) -> HandleResult {
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    if config.admin != sender_raw {
+        return Err(ContractError::AdminOnly.into());
+    }
+    let new_status = level.to_u8();
+    if config.status != new_status {
+        config.status = new_status;
+        save(&mut deps.storage, CONFIG_KEY, &config)?;
+    }
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::SetContractStatus {
+            status: Success,
+        })?),
+    })
+}
+
+/////////////////////////////////////// Query /////////////////////////////////////
+/// Returns QueryResult
+///
+/// # Arguments
+///
+/// * `deps` - reference to Extern containing all the contract's external dependencies
+/// * `msg` - QueryMsg passed in with the query call
+pub fn query<S: Storage, A: Api, Q: Querier>(deps: &Extern<S, A, Q>, msg: QueryMsg) -> QueryResult {
+    // these queries can display a token's private metadata
+    let holds_secret = matches!(
+        msg,
+        QueryMsg::PrivateMetadata { .. }
+            | QueryMsg::NftDossier { .. }
+            | QueryMsg::RetrievedSecrets { .. }
+            | QueryMsg::WithPermit {
+                query: QueryWithPermit::PrivateMetadata { .. }
+                    | QueryWithPermit::NftDossier { .. }
+                    | QueryWithPermit::RetrievedSecrets {},
+                ..
+            }
+    );
+    let response = match msg {
+        QueryMsg::ContractInfo {} => query_contract_info(&deps.storage),
+        QueryMsg::ContractConfig {} => query_config(&deps.storage),
+        QueryMsg::Minters {} => query_minters(deps),
+        QueryMsg::NumTokens { viewer } => query_num_tokens(deps, viewer, None),
+        QueryMsg::AllTokens {
+            viewer,
+            start_after,
+            limit,
+        } => query_all_tokens(deps, viewer, None, start_after, limit),
+        QueryMsg::OwnerOf {
+            token_id,
+            viewer,
+            include_expired,
+        } => query_owner_of(deps, &token_id, viewer, None, include_expired),
+        QueryMsg::NftInfo { token_id } => query_nft_info(&deps.storage, &token_id),
+        QueryMsg::PrivateMetadata { token_id, viewer } => {
+            query_private_meta(deps, &token_id, viewer, None)
+        }
+        QueryMsg::AllNftInfo {
+            token_id,
+            viewer,
+            include_expired,
+        } => query_all_nft_info(deps, &token_id, viewer, None, include_expired),
+        QueryMsg::NftDossier {
+            token_id,
+            viewer,
+            include_expired,
+        } => query_nft_dossier(deps, &token_id, viewer, None, include_expired),
+        QueryMsg::TokenApprovals {
             token_id,
             viewing_key,
             include_expired,
-        } => query_token_approvals(deps, &token_id, viewing_key, include_expired),
+        } => query_token_approvals(deps, &token_id, Some(viewing_key), None, include_expired),
         QueryMsg::InventoryApprovals {
             address,
             viewing_key,
             include_expired,
-        } => query_inventory_approvals(deps, &address, viewing_key, include_expired),
+        } => query_inventory_approvals(deps, &address, Some(viewing_key), None, include_expired),
         QueryMsg::ApprovedForAll {
             owner,
             viewing_key,
             include_expired,
-        } => query_approved_for_all(deps, &owner, viewing_key, include_expired),
+        } => query_approved_for_all(deps, &owner, viewing_key, None, include_expired),
         QueryMsg::Tokens {
             owner,
             viewer,
             viewing_key,
             start_after,
             limit,
-        } => query_tokens(deps, &owner, viewer, viewing_key, start_after, limit),
+        } => query_tokens(deps, &owner, viewer, viewing_key, None, start_after, limit),
         QueryMsg::VerifyTransferApproval {
             token_ids,
             address,
             viewing_key,
-        } => query_verify_approval(deps, &token_ids, &address, viewing_key),
+        } => query_verify_approval(deps, &token_ids, &address, Some(viewing_key), None),
         QueryMsg::IsUnwrapped { token_id } => query_is_unwrapped(&deps.storage, &token_id),
+        QueryMsg::CanRetrieve { token_id, viewer } => {
+            query_can_retrieve(deps, &token_id, viewer, None)
+        }
+        QueryMsg::RetrievedSecrets {
+            address,
+            viewing_key,
+        } => query_retrieved_secrets(deps, &address, Some(viewing_key), None),
         QueryMsg::TransactionHistory {
             address,
             viewing_key,
             page,
             page_size,
-        } => query_transactions(deps, &address, viewing_key, page, page_size),
+            cursor,
+            filter,
+        } => query_transactions(
+            deps,
+            &address,
+            Some(viewing_key),
+            None,
+            page,
+            page_size,
+            cursor,
+            filter,
+        ),
         QueryMsg::RegisteredCodeHash { contract } => query_code_hash(deps, &contract),
+        QueryMsg::MigrationStatus { viewer } => query_migration_status(deps, viewer),
+        QueryMsg::ScopedKeys {
+            address,
+            viewing_key,
+        } => query_scoped_keys(deps, &address, Some(viewing_key), None),
+        QueryMsg::Outbox {
+            address,
+            viewing_key,
+            start_after,
+            limit,
+        } => query_outbox(deps, &address, Some(viewing_key), None, start_after, limit),
+        QueryMsg::Inbox {
+            address,
+            viewing_key,
+            cursor,
+            limit,
+        } => query_inbox(deps, &address, Some(viewing_key), None, cursor, limit),
+        QueryMsg::SecretRequests {
+            address,
+            viewing_key,
+            page,
+            page_size,
+        } => query_secret_requests(deps, &address, Some(viewing_key), None, page, page_size),
+        QueryMsg::WithPermit { permit, query } => permit_queries(deps, &permit, query),
+    };
+    let response = if holds_secret {
+        response.and_then(|mut data| {
+            if let Some(padding) = load_config(&deps.storage)?.secret_padding {
+                pad_to_bucket(&mut data.0, &padding);
+            }
+            Ok(data)
+        })
+    } else {
+        response
     };
     pad_query_result(response, BLOCK_SIZE)
 }
 
+/// Returns QueryResult from validating a permit and then using its signer as the querier
+///
+/// # Arguments
+///
+/// * `deps` - a reference to Extern containing all the contract's external dependencies
+/// * `permit` - a reference to the permit used to authenticate the query
+/// * `query` - QueryWithPermit to perform
+pub fn permit_queries<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    permit: &Permit,
+    query: QueryWithPermit,
+) -> QueryResult {
+    // TODO remove this when env becomes available to queries
+    let my_address_raw: CanonicalAddr = load(&deps.storage, MY_ADDRESS_KEY)?;
+    let my_address = deps.api.human_address(&my_address_raw)?;
+    let querier = validate(deps, permit, &my_address)?;
+    // the owner permission grants every query, the history permission only the tx history
+    let permitted = permit.has_permission(&TokenPermissions::Owner)
+        || (matches!(query, QueryWithPermit::TransactionHistory { .. })
+            && permit.has_permission(&TokenPermissions::History));
+    if !permitted {
+        return Err(ContractError::PermitUnauthorized.into());
+    }
+    let address = deps.api.human_address(&querier)?;
+    let from_permit = Some(querier);
+    match query {
+        QueryWithPermit::NumTokens {} => query_num_tokens(deps, None, from_permit),
+        QueryWithPermit::AllTokens { start_after, limit } => {
+            query_all_tokens(deps, None, from_permit, start_after, limit)
+        }
+        QueryWithPermit::OwnerOf {
+            token_id,
+            include_expired,
+        } => query_owner_of(deps, &token_id, None, from_permit, include_expired),
+        QueryWithPermit::PrivateMetadata { token_id } => {
+            query_private_meta(deps, &token_id, None, from_permit)
+        }
+        QueryWithPermit::AllNftInfo {
+            token_id,
+            include_expired,
+        } => query_all_nft_info(deps, &token_id, None, from_permit, include_expired),
+        QueryWithPermit::NftDossier {
+            token_id,
+            include_expired,
+        } => query_nft_dossier(deps, &token_id, None, from_permit, include_expired),
+        QueryWithPermit::TokenApprovals {
+            token_id,
+            include_expired,
+        } => query_token_approvals(deps, &token_id, None, from_permit, include_expired),
+        QueryWithPermit::InventoryApprovals { include_expired } => {
+            query_inventory_approvals(deps, &address, None, from_permit, include_expired)
+        }
+        QueryWithPermit::ApprovedForAll { include_expired } => {
+            query_approved_for_all(deps, &address, None, from_permit, include_expired)
+        }
+        QueryWithPermit::Tokens {
+            owner,
+            start_after,
+            limit,
+        } => query_tokens(deps, &owner, None, None, from_permit, start_after, limit),
+        QueryWithPermit::VerifyTransferApproval { token_ids } => {
+            query_verify_approval(deps, &token_ids, &address, None, from_permit)
+        }
+        QueryWithPermit::CanRetrieve { token_id } => {
+            query_can_retrieve(deps, &token_id, None, from_permit)
+        }
+        QueryWithPermit::RetrievedSecrets {} => {
+            query_retrieved_secrets(deps, &address, None, from_permit)
+        }
+        QueryWithPermit::TransactionHistory {
+            page,
+            page_size,
+            cursor,
+            filter,
+        } => query_transactions(
+            deps,
+            &address,
+            None,
+            from_permit,
+            page,
+            page_size,
+            cursor,
+            filter,
+        ),
+        QueryWithPermit::ScopedKeys {} => query_scoped_keys(deps, &address, None, from_permit),
+        QueryWithPermit::Outbox { start_after, limit } => {
+            query_outbox(deps, &address, None, from_permit, start_after, limit)
+        }
+        QueryWithPermit::Inbox { cursor, limit } => {
+            query_inbox(deps, &address, None, from_permit, cursor, limit)
+        }
+        QueryWithPermit::SecretRequests { page, page_size } => {
+            query_secret_requests(deps, &address, None, from_permit, page, page_size)
+        }
+    }
+}
+
 /// Returns QueryResult displaying the contract's name and symbol
 ///
 /// # Arguments
 ///
 /// * `storage` - a reference to the contract's storage
 pub fn query_contract_info<S: ReadonlyStorage>(storage: &S) -> QueryResult {
-    let config: Config = load(storage, CONFIG_KEY)?;
+    let config = load_config(storage)?;
 
     to_binary(&QueryAnswer::ContractInfo {
         name: config.name,
@@ -1390,7 +2361,7 @@ pub fn query_contract_info<S: ReadonlyStorage>(storage: &S) -> QueryResult {
 ///
 /// * `storage` - a reference to the contract's storage
 pub fn query_config<S: ReadonlyStorage>(storage: &S) -> QueryResult {
-    let config: Config = load(storage, CONFIG_KEY)?;
+    let config = load_config(storage)?;
 
     to_binary(&QueryAnswer::ContractConfig {
         token_supply_is_public: config.token_supply_is_public,
@@ -1400,6 +2371,15 @@ pub fn query_config<S: ReadonlyStorage>(storage: &S) -> QueryResult {
         minter_may_update_metadata: config.minter_may_update_metadata,
         owner_may_update_metadata: config.owner_may_update_metadata,
         burn_is_enabled: config.burn_is_enabled,
+        retrieval_window: config.retrieval_window,
+        tx_retention: config.tx_retention,
+        private_tx_records: config.private_tx_records,
+        secret_padding: config.secret_padding,
+        transfers_enabled: config.transfers_enabled,
+        gifting_enabled: config.gifting_enabled,
+        open_minting: config.open_minting,
+        metadata_queries_enabled: config.metadata_queries_enabled,
+        burn_reveals_secret: config.burn_reveals_secret,
     })
 }
 
@@ -1408,8 +2388,19 @@ pub fn query_config<S: ReadonlyStorage>(storage: &S) -> QueryResult {
 /// # Arguments
 ///
 /// * `deps` - a reference to Extern containing all the contract's external dependencies
-pub fn query_minters<S: Storage, A: Api, Q: Querier>(_deps: &Extern<S, A, Q>) -> QueryResult {
-    Err(StdError::generic_err("Minting is not restricted, minter list not supported"))
+pub fn query_minters<S: Storage, A: Api, Q: Querier>(deps: &Extern<S, A, Q>) -> QueryResult {
+    let config = load_config(&deps.storage)?;
+    if config.open_minting {
+        return Err(ContractError::MintersUnsupported.into());
+    }
+    let minters: Vec<CanonicalAddr> =
+        may_load(&deps.storage, MINTERS_KEY)?.unwrap_or_else(Vec::new);
+    to_binary(&QueryAnswer::Minters {
+        minters: minters
+            .iter()
+            .map(|m| deps.api.human_address(m))
+            .collect::<StdResult<Vec<HumanAddr>>>()?,
+    })
 }
 
 /// Returns QueryResult displaying the number of tokens the contract controls
@@ -1418,16 +2409,16 @@ pub fn query_minters<S: Storage, A: Api, Q: Querier>(_deps: &Extern<S, A, Q>) ->
 ///
 /// * `deps` - a reference to Extern containing all the contract's external dependencies
 /// * `viewer` - optional address and key making an authenticated query request
+/// * `from_permit` - address derived from an Owner permit, if applicable
 pub fn query_num_tokens<S: Storage, A: Api, Q: Querier>(
     deps: &Extern<S, A, Q>,
     viewer: Option<ViewerInfo>,
+    from_permit: Option<CanonicalAddr>,
 ) -> QueryResult {
     // authenticate permission to view token supply
-    check_view_supply(deps, viewer)?;
-    let tokens: HashSet<String> = may_load(&deps.storage, TOKENS_KEY)?.unwrap_or_else(HashSet::new);
-    to_binary(&QueryAnswer::NumTokens {
-        count: tokens.len() as u32,
-    })
+    check_view_supply(deps, viewer, from_permit)?;
+    let count = get_token_count(&deps.storage)?;
+    to_binary(&QueryAnswer::NumTokens { count })
 }
 
 /// Returns QueryResult displaying the list of tokens that the contract controls
@@ -1436,16 +2427,42 @@ pub fn query_num_tokens<S: Storage, A: Api, Q: Querier>(
 ///
 /// * `deps` - a reference to Extern containing all the contract's external dependencies
 /// * `viewer` - optional address and key making an authenticated query request
-/// * `start_after` - optionally only display token ids that come after this String in
-///                   lexicographical order
+/// * `from_permit` - address derived from an Owner permit, if applicable
+/// * `start_after` - optionally only display token ids that were minted after this one
 /// * `limit` - optional max number of tokens to display
 pub fn query_all_tokens<S: Storage, A: Api, Q: Querier>(
-    _deps: &Extern<S, A, Q>,
-    _viewer: Option<ViewerInfo>,
-    _start_after: Option<String>,
-    _limit: Option<u32>,
+    deps: &Extern<S, A, Q>,
+    viewer: Option<ViewerInfo>,
+    from_permit: Option<CanonicalAddr>,
+    start_after: Option<String>,
+    limit: Option<u32>,
 ) -> QueryResult {
-    Err(StdError::generic_err("Tokens cannot be queried"))
+    let config = load_config(&deps.storage)?;
+    if !config.metadata_queries_enabled {
+        return Err(ContractError::TokensQueryUnsupported.into());
+    }
+    // authenticate permission to view token supply
+    check_view_supply(deps, viewer, from_permit)?;
+    let mut i = match start_after {
+        Some(id) => {
+            let map2idx = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_INDEX, &deps.storage);
+            let idx: u32 = may_load(&map2idx, id.as_bytes())?
+                .ok_or_else(|| StdError::from(ContractError::TokenNotFound { token_id: id }))?;
+            idx.saturating_add(1)
+        }
+        None => 0,
+    };
+    let cut_off = limit.unwrap_or(300);
+    let mut tokens = Vec::new();
+    let map2id = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_ID, &deps.storage);
+    // token indices are never reused, so skip the ones that were burned
+    while (tokens.len() as u32) < cut_off && i < config.mint_cnt {
+        if let Some(id) = may_load::<String, _>(&map2id, &i.to_le_bytes())? {
+            tokens.push(id);
+        }
+        i += 1;
+    }
+    to_binary(&QueryAnswer::TokenList { tokens })
 }
 
 /// Returns QueryResult displaying the owner of the input token if the requester is authorized
@@ -1456,38 +2473,67 @@ pub fn query_all_tokens<S: Storage, A: Api, Q: Querier>(
 /// * `deps` - a reference to Extern containing all the contract's external dependencies
 /// * `token_id` - string slice of the token id
 /// * `viewer` - optional address and key making an authenticated query request
+/// * `from_permit` - address derived from an Owner permit, if applicable
 /// * `include_expired` - optionally true if the Approval lists should include expired Approvals
 pub fn query_owner_of<S: Storage, A: Api, Q: Querier>(
     deps: &Extern<S, A, Q>,
     token_id: &str,
     viewer: Option<ViewerInfo>,
+    from_permit: Option<CanonicalAddr>,
     include_expired: Option<bool>,
 ) -> QueryResult {
-    let (may_owner, approvals, _idx) =
-        process_cw721_owner_of(deps, token_id, viewer, include_expired)?;
+    let (may_owner, approvals, _idx) = process_cw721_owner_of(
+        deps,
+        token_id,
+        viewer,
+        from_permit,
+        include_expired,
+        ScopedQuery::OwnerOf,
+    )?;
     if let Some(owner) = may_owner {
         return to_binary(&QueryAnswer::OwnerOf { owner, approvals });
     }
-    Err(StdError::generic_err(format!(
-        "You are not authorized to view the owner of token {}",
-        token_id
-    )))
+    Err(perm_error(
+        deps,
+        ContractError::TokenUnauthorized {
+            token_id: token_id.to_string(),
+            detail: format!(
+                "You are not authorized to view the owner of token {}",
+                token_id
+            ),
+        },
+    ))
 }
 
-/// Returns QueryResult displaying the public metadata of a token
+/// Returns QueryResult displaying the public metadata of a token if its creator allowed it
+/// to be viewed before the token is burned, or if the contract enables metadata queries
 ///
 /// # Arguments
 ///
 /// * `storage` - a reference to the contract's storage
 /// * `token_id` - string slice of the token id
-pub fn query_nft_info() -> QueryResult {
-    Err(StdError::generic_err("Token must be burned to retrieve metadata."))
+pub fn query_nft_info<S: ReadonlyStorage>(storage: &S, token_id: &str) -> QueryResult {
+    let config = load_config(storage)?;
+    if config.metadata_queries_enabled {
+        return to_binary(&get_public_metadata(storage, token_id)?);
+    }
+    let (token, _idx) = get_token(storage, token_id, config.token_supply_is_public)?;
+    if !token.public_teaser {
+        return Err(token_error(
+            config.token_supply_is_public,
+            ContractError::TokenUnauthorized {
+                token_id: token_id.to_string(),
+                detail: "Token must be burned to retrieve metadata.".to_string(),
+            },
+        ));
+    }
+    to_binary(&get_public_metadata(storage, token_id)?)
 }
 
 fn get_public_metadata<S: ReadonlyStorage>(
     storage: &S, token_id: &str,
 ) -> StdResult<QueryAnswer> {
-    let config: Config = load(storage, CONFIG_KEY)?;
+    let config = load_config(storage)?;
     let map2idx = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_INDEX, storage);
     let may_idx: Option<u32> = may_load(&map2idx, token_id.as_bytes())?;
     // if token id was found
@@ -1507,10 +2553,10 @@ fn get_public_metadata<S: ReadonlyStorage>(
     // token id wasn't found
     // if the token supply is public, let them know the token does not exist
     if config.token_supply_is_public {
-        return Err(StdError::generic_err(format!(
-            "Token ID: {} not found",
-            token_id
-        )));
+        return Err(ContractError::TokenNotFound {
+            token_id: token_id.to_string(),
+        }
+        .into());
     }
     // otherwise, just return empty metadata
     Ok(QueryAnswer::NftInfo {
@@ -1521,37 +2567,91 @@ fn get_public_metadata<S: ReadonlyStorage>(
 }
 
 /// Returns QueryResult displaying the private metadata of a token if permitted to
-/// view it
+/// view it.  If the contract does not enable metadata queries, the token must be burned
+/// to retrieve its private metadata
 ///
 /// # Arguments
 ///
 /// * `deps` - a reference to Extern containing all the contract's external dependencies
 /// * `token_id` - string slice of the token id
 /// * `viewer` - optional address and key making an authenticated query request
+/// * `from_permit` - address derived from an Owner permit, if applicable
 pub fn query_private_meta<S: Storage, A: Api, Q: Querier>(
-    _deps: &Extern<S, A, Q>,
-    _token_id: &str,
-    _viewer: Option<ViewerInfo>,
+    deps: &Extern<S, A, Q>,
+    token_id: &str,
+    viewer: Option<ViewerInfo>,
+    from_permit: Option<CanonicalAddr>,
 ) -> QueryResult {
-    Err(StdError::generic_err("Token must be burned to retrieve metadata."))
+    let config = load_config(&deps.storage)?;
+    if !config.metadata_queries_enabled {
+        return Err(ContractError::NotBurned.into());
+    }
+    let prep_info = query_token_prep(
+        deps,
+        token_id,
+        viewer,
+        from_permit,
+        ScopedQuery::PrivateMetadata,
+    )?;
+    let opt_viewer = if prep_info.viewer_given {
+        Some(&prep_info.viewer_raw)
+    } else {
+        None
+    };
+    check_perm_core(
+        deps,
+        &prep_info.block,
+        &prep_info.token,
+        token_id,
+        opt_viewer,
+        PermissionType::ViewMetadata.to_usize(),
+        &mut Vec::new(),
+        &prep_info.err_msg,
+    )?;
+    // don't display if private metadata is sealed
+    if !prep_info.token.unwrapped {
+        return Err(token_error(
+            config.token_supply_is_public,
+            ContractError::TokenSealed {
+                token_id: token_id.to_string(),
+                detail: "Sealed metadata must be unwrapped by calling Reveal before it can be \
+                         viewed"
+                    .to_string(),
+            },
+        ));
+    }
+    let meta_store = ReadonlyPrefixedStorage::new(PREFIX_PRIV_META, &deps.storage);
+    let meta: Metadata = may_load(&meta_store, &prep_info.idx.to_le_bytes())?.unwrap_or(Metadata {
+        name: None,
+        description: None,
+        image: None,
+    });
+    to_binary(&QueryAnswer::PrivateMetadata {
+        name: meta.name,
+        description: meta.description,
+        image: meta.image,
+    })
 }
 
 fn get_private_metadata<S: ReadonlyStorage>(
     storage: &S, token_id: &str,
 ) -> StdResult<QueryAnswer> {
-    let config: Config = load(storage, CONFIG_KEY)?;
-    let (token, idx) = get_token(storage, token_id, None)?;
+    let config = load_config(storage)?;
+    let (token, idx) = get_token(storage, token_id, config.token_supply_is_public)?;
 
     let meta_store = ReadonlyPrefixedStorage::new(PREFIX_PRIV_META, storage);
     let meta: Option<Metadata> = may_load(&meta_store, &idx.to_le_bytes())?;
 
-    if meta.is_some() {
+    if let Some(data) = meta {
         if config.sealed_metadata_is_enabled && !token.unwrapped {
-            panic!(
-                "Sealed metadata must be unwrapped by calling Reveal before it can be viewed",
-            );
+            return Err(token_error(
+                config.token_supply_is_public,
+                ContractError::TokenSealed {
+                    token_id: token_id.to_string(),
+                    detail: "Sealed metadata must be unwrapped by calling Reveal before it can be viewed".to_string(),
+                },
+            ));
         }
-        let data = meta.unwrap();
         return Ok(QueryAnswer::PrivateMetadata {
             name: data.name,
             description: data.description,
@@ -1573,14 +2673,23 @@ fn get_private_metadata<S: ReadonlyStorage>(
 /// * `deps` - a reference to Extern containing all the contract's external dependencies
 /// * `token_id` - string slice of the token id
 /// * `viewer` - optional address and key making an authenticated query request
+/// * `from_permit` - address derived from an Owner permit, if applicable
 /// * `include_expired` - optionally true if the Approval lists should include expired Approvals
 pub fn query_all_nft_info<S: Storage, A: Api, Q: Querier>(
     deps: &Extern<S, A, Q>,
     token_id: &str,
     viewer: Option<ViewerInfo>,
+    from_permit: Option<CanonicalAddr>,
     include_expired: Option<bool>,
 ) -> QueryResult {
-    let (owner, approvals, idx) = process_cw721_owner_of(deps, token_id, viewer, include_expired)?;
+    let (owner, approvals, idx) = process_cw721_owner_of(
+        deps,
+        token_id,
+        viewer,
+        from_permit,
+        include_expired,
+        ScopedQuery::AllNftInfo,
+    )?;
     let meta_store = ReadonlyPrefixedStorage::new(PREFIX_PUB_META, &deps.storage);
     let info: Option<Metadata> = may_load(&meta_store, &idx.to_le_bytes())?;
     let access = Cw721OwnerOfResponse { owner, approvals };
@@ -1596,14 +2705,17 @@ pub fn query_all_nft_info<S: Storage, A: Api, Q: Querier>(
 /// * `deps` - a reference to Extern containing all the contract's external dependencies
 /// * `token_id` - string slice of the token id
 /// * `viewer` - optional address and key making an authenticated query request
+/// * `from_permit` - address derived from an Owner permit, if applicable
 /// * `include_expired` - optionally true if the Approval lists should include expired Approvals
 pub fn query_nft_dossier<S: Storage, A: Api, Q: Querier>(
     deps: &Extern<S, A, Q>,
     token_id: &str,
     viewer: Option<ViewerInfo>,
+    from_permit: Option<CanonicalAddr>,
     include_expired: Option<bool>,
 ) -> QueryResult {
-    let mut prep_info = query_token_prep(deps, token_id, viewer)?;
+    let mut prep_info =
+        query_token_prep(deps, token_id, viewer, from_permit, ScopedQuery::NftDossier)?;
     let incl_exp = include_expired.unwrap_or(false);
     let owner_slice = prep_info.token.owner.as_slice();
     let opt_viewer = if prep_info.viewer_given {
@@ -1628,7 +2740,6 @@ pub fn query_nft_dossier<S: Storage, A: Api, Q: Querier>(
         &prep_info.token,
         token_id,
         opt_viewer,
-        owner_slice,
         perm_type_info.view_owner_idx,
         &mut Vec::new(),
         &prep_info.err_msg,
@@ -1651,13 +2762,12 @@ pub fn query_nft_dossier<S: Storage, A: Api, Q: Querier>(
         &prep_info.token,
         token_id,
         opt_viewer,
-        owner_slice,
         perm_type_info.view_meta_idx,
         &mut Vec::new(),
         &prep_info.err_msg,
     ) {
         if let StdError::GenericErr { msg, .. } = err {
-            display_private_metadata_error = Some(msg);
+            display_private_metadata_error = Some(ContractError::msg_of(msg));
         }
         None
     } else if !prep_info.token.unwrapped {
@@ -1679,9 +2789,7 @@ pub fn query_nft_dossier<S: Storage, A: Api, Q: Querier>(
         incl_exp,
         &perm_type_info,
     )?;
-    let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-    let mut all_perm: Vec<Permission> =
-        json_may_load(&all_store, owner_slice)?.unwrap_or_else(Vec::new);
+    let mut all_perm = get_all_perms(&deps.storage, &prep_info.token.owner)?;
     let (inventory_approv, all_owner_exp, all_meta_exp) = gen_snip721_approvals(
         &deps.api,
         &prep_info.block,
@@ -1731,28 +2839,42 @@ pub fn query_nft_dossier<S: Storage, A: Api, Q: Querier>(
 ///
 /// * `deps` - a reference to Extern containing all the contract's external dependencies
 /// * `token_id` - string slice of the token id
-/// * `viewing_key` - the token owner's viewing key String
+/// * `viewing_key` - the token owner's viewing key String, if not using a permit
+/// * `from_permit` - address derived from an Owner permit, if applicable
 /// * `include_expired` - optionally true if the Approval lists should include expired Approvals
 pub fn query_token_approvals<S: Storage, A: Api, Q: Querier>(
     deps: &Extern<S, A, Q>,
     token_id: &str,
-    viewing_key: String,
+    viewing_key: Option<String>,
+    from_permit: Option<CanonicalAddr>,
     include_expired: Option<bool>,
 ) -> QueryResult {
-    let config: Config = load(&deps.storage, CONFIG_KEY)?;
-    let custom_err = format!(
-        "You are not authorized to view approvals for token {}",
-        token_id
-    );
-    // if token supply is private, don't leak that the token id does not exist
-    // instead just say they are not authorized for that token
-    let opt_err = if config.token_supply_is_public {
-        None
-    } else {
-        Some(&*custom_err)
+    let config = load_config(&deps.storage)?;
+    let (mut token, _idx) = get_token(&deps.storage, token_id, config.token_supply_is_public)?;
+    // a wrong key must look the same as a token that does not exist
+    let access = KeyAccess {
+        query: ScopedQuery::TokenApprovals,
+        token_ids: vec![token_id],
     };
-    let (mut token, _idx) = get_token(&deps.storage, token_id, opt_err)?;
-    check_key(&deps.storage, &token.owner, viewing_key)?;
+    check_querier(
+        &deps.storage,
+        &token.owner,
+        viewing_key,
+        from_permit,
+        &access,
+    )
+    .map_err(|_| {
+        token_error(
+            config.token_supply_is_public,
+            ContractError::TokenUnauthorized {
+                token_id: token_id.to_string(),
+                detail: format!(
+                    "You are not authorized to view approvals for token {}",
+                    token_id
+                ),
+            },
+        )
+    })?;
     let owner_slice = token.owner.as_slice();
     let own_priv_store = ReadonlyPrefixedStorage::new(PREFIX_OWNER_PRIV, &deps.storage);
     let global_pass: bool =
@@ -1777,9 +2899,7 @@ pub fn query_token_approvals<S: Storage, A: Api, Q: Querier>(
         incl_exp,
         &perm_type_info,
     )?;
-    let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-    let mut all_perm: Vec<Permission> =
-        json_may_load(&all_store, owner_slice)?.unwrap_or_else(Vec::new);
+    let mut all_perm = get_all_perms(&deps.storage, &token.owner)?;
     let (_inventory_approv, all_owner_exp, all_meta_exp) =
         gen_snip721_approvals(&deps.api, &block, &mut all_perm, incl_exp, &perm_type_info)?;
     // determine if ownership is public
@@ -1812,19 +2932,25 @@ pub fn query_token_approvals<S: Storage, A: Api, Q: Querier>(
 ///
 /// * `deps` - a reference to Extern containing all the contract's external dependencies
 /// * `address` - a reference to the address whose approvals should be displayed
-/// * `viewing_key` - the token owner's viewing key String
+/// * `viewing_key` - the token owner's viewing key String, if not using a permit
+/// * `from_permit` - address derived from an Owner permit, if applicable
 /// * `include_expired` - optionally true if the Approval lists should include expired Approvals
 pub fn query_inventory_approvals<S: Storage, A: Api, Q: Querier>(
     deps: &Extern<S, A, Q>,
     address: &HumanAddr,
-    viewing_key: String,
+    viewing_key: Option<String>,
+    from_permit: Option<CanonicalAddr>,
     include_expired: Option<bool>,
 ) -> QueryResult {
     let owner_raw = deps.api.canonical_address(address)?;
     let owner_slice = owner_raw.as_slice();
-    check_key(&deps.storage, &owner_raw, viewing_key)?;
+    let access = KeyAccess {
+        query: ScopedQuery::InventoryApprovals,
+        token_ids: Vec::new(),
+    };
+    check_querier(&deps.storage, &owner_raw, viewing_key, from_permit, &access)?;
     let own_priv_store = ReadonlyPrefixedStorage::new(PREFIX_OWNER_PRIV, &deps.storage);
-    let config: Config = load(&deps.storage, CONFIG_KEY)?;
+    let config = load_config(&deps.storage)?;
     let global_pass: bool =
         may_load(&own_priv_store, owner_slice)?.unwrap_or(config.owner_is_public);
     // TODO remove this when BlockInfo becomes available to queries
@@ -1833,9 +2959,7 @@ pub fn query_inventory_approvals<S: Storage, A: Api, Q: Querier>(
         time: 1,
         chain_id: "secret-2".to_string(),
     });
-    let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-    let mut all_perm: Vec<Permission> =
-        json_may_load(&all_store, owner_slice)?.unwrap_or_else(Vec::new);
+    let mut all_perm = get_all_perms(&deps.storage, &owner_raw)?;
     let perm_type_info = PermissionTypeInfo {
         view_owner_idx: PermissionType::ViewOwner.to_usize(),
         view_meta_idx: PermissionType::ViewMetadata.to_usize(),
@@ -1877,16 +3001,22 @@ pub fn query_inventory_approvals<S: Storage, A: Api, Q: Querier>(
 /// * `deps` - a reference to Extern containing all the contract's external dependencies
 /// * `owner` - a reference to the address whose transfer ALL list should be displayed
 /// * `viewing_key` - optional String of the owner's viewing key
+/// * `from_permit` - address derived from an Owner permit, if applicable
 /// * `include_expired` - optionally true if the Approval list should include expired Approvals
 pub fn query_approved_for_all<S: Storage, A: Api, Q: Querier>(
     deps: &Extern<S, A, Q>,
     owner: &HumanAddr,
     viewing_key: Option<String>,
+    from_permit: Option<CanonicalAddr>,
     include_expired: Option<bool>,
 ) -> QueryResult {
     let owner_raw = deps.api.canonical_address(owner)?;
-    if let Some(key) = viewing_key {
-        check_key(&deps.storage, &owner_raw, key)?;
+    if viewing_key.is_some() || from_permit.is_some() {
+        let access = KeyAccess {
+            query: ScopedQuery::ApprovedForAll,
+            token_ids: Vec::new(),
+        };
+        check_querier(&deps.storage, &owner_raw, viewing_key, from_permit, &access)?;
     } else {
         return to_binary(&QueryAnswer::ApprovedForAll {
             operators: Vec::new(),
@@ -1899,9 +3029,7 @@ pub fn query_approved_for_all<S: Storage, A: Api, Q: Querier>(
         chain_id: "secret-2".to_string(),
     });
     let mut operators: Vec<Cw721Approval> = Vec::new();
-    let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-    let all_perm: Vec<Permission> =
-        json_may_load(&all_store, owner_raw.as_slice())?.unwrap_or_else(Vec::new);
+    let all_perm = get_all_perms(&deps.storage, &owner_raw)?;
     gen_cw721_approvals(
         &deps.api,
         &block,
@@ -1914,205 +3042,755 @@ pub fn query_approved_for_all<S: Storage, A: Api, Q: Querier>(
     to_binary(&QueryAnswer::ApprovedForAll { operators })
 }
 
-/// Returns QueryResult displaying an optionally paginated list of all tokens belonging to
-/// the owner address.  It will only display the tokens that the querier has view_owner
-/// approval
+/// Returns QueryResult displaying an optionally paginated list of all tokens belonging to
+/// the owner address.  It will only display the tokens that the querier has view_owner
+/// approval.  Tokens are listed in inventory order, and removing a token moves the last token
+/// into its place, so pages are not stable across burns and transfers
+///
+/// # Arguments
+///
+/// * `deps` - a reference to Extern containing all the contract's external dependencies
+/// * `owner` - a reference to the address whose tokens should be displayed
+/// * `viewer` - optional address of the querier if different from the owner
+/// * `viewing_key` - optional viewing key String
+/// * `from_permit` - address derived from an Owner permit, if applicable
+/// * `start_after` - optionally only display token ids that come after this token id in
+///                   the owner's inventory
+/// * `limit` - optional max number of tokens to display
+#[allow(clippy::too_many_arguments)]
+pub fn query_tokens<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    owner: &HumanAddr,
+    viewer: Option<HumanAddr>,
+    viewing_key: Option<String>,
+    from_permit: Option<CanonicalAddr>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> QueryResult {
+    let mut is_viewer = false;
+    let mut is_owner = false;
+    let owner_raw = deps.api.canonical_address(owner)?;
+    let owner_slice = owner_raw.as_slice();
+    let size = limit.unwrap_or(30) as usize;
+    let (viewer_raw, vwr_given) = if let Some(vwr) = viewer {
+        (deps.api.canonical_address(&vwr)?, true)
+    } else {
+        (CanonicalAddr(Binary::from(b"notused")), false)
+    };
+    // a permit already identifies the querier
+    if let Some(permit_raw) = from_permit.as_ref() {
+        if *permit_raw == owner_raw {
+            is_owner = true;
+        } else {
+            is_viewer = true;
+        }
+    // if we need to validate an address
+    } else if let Some(key) = viewing_key {
+        // if a viewer was supplied, they're saying they aren't the owner so check
+        // if the key matches the viewer address first
+        let access = KeyAccess {
+            query: ScopedQuery::Tokens,
+            token_ids: Vec::new(),
+        };
+        if vwr_given && check_scoped_key(&deps.storage, &viewer_raw, key.clone(), &access).is_ok() {
+            is_viewer = true;
+            // check if this is the owner's key if we need to
+        } else {
+            check_scoped_key(&deps.storage, &owner_raw, key, &access)?;
+            is_owner = true;
+        }
+    }
+    let querier = if is_viewer {
+        from_permit.as_ref().or(Some(&viewer_raw))
+    } else if is_owner {
+        Some(&owner_raw)
+    } else {
+        None
+    };
+    // if querier is different than the owner, check if ownership is public
+    let mut known_pass = if !is_owner {
+        let config = load_config(&deps.storage)?;
+        let own_priv_store = ReadonlyPrefixedStorage::new(PREFIX_OWNER_PRIV, &deps.storage);
+        let pass: bool = may_load(&own_priv_store, owner_slice)?.unwrap_or(config.owner_is_public);
+        pass
+    } else {
+        true
+    };
+    // TODO remove this when BlockInfo becomes available to queries
+    let block = if !known_pass {
+        let b: BlockInfo = may_load(&deps.storage, BLOCK_KEY)?.unwrap_or_else(|| BlockInfo {
+            height: 1,
+            time: 1,
+            chain_id: "secret-2".to_string(),
+        });
+        b
+    } else {
+        BlockInfo {
+            height: 1,
+            time: 1,
+            chain_id: "secret-2".to_string(),
+        }
+    };
+    let exp_idx = PermissionType::ViewOwner.to_usize();
+    let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
+    let mut oper_for: Vec<CanonicalAddr> = Vec::new();
+    let mut tokens: Vec<String> = Vec::new();
+    let map2id = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_ID, &deps.storage);
+    // returns true if the querier may see that the owner has this token
+    let mut may_list = |idx: u32, id: &str, known_pass: &mut bool| -> StdResult<bool> {
+        // only check permissions if not public or owner
+        if *known_pass {
+            return Ok(true);
+        }
+        let may_token = may_load_token(&info_store, &idx.to_le_bytes())?;
+        if let Some(token) = may_token {
+            let list_it = check_perm_core(
+                deps,
+                &block,
+                &token,
+                id,
+                querier,
+                exp_idx,
+                &mut oper_for,
+                "",
+            )
+            .is_ok();
+            // if querier is found to have ALL permission, no need to check permission ever again
+            if !oper_for.is_empty() {
+                *known_pass = true;
+            }
+            return Ok(list_it);
+        }
+        Ok(false)
+    };
+    // resume after the cursor's position in the owner's inventory.  Don't leak the ownership
+    // of a token the querier is not permitted to see
+    let mut pos = 0u32;
+    if let Some(after) = start_after {
+        let cursor_err = || -> StdError {
+            ContractError::InvalidCursor {
+                token_id: after.clone(),
+            }
+            .into()
+        };
+        let map2idx = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_INDEX, &deps.storage);
+        let may_idx: Option<u32> = may_load(&map2idx, after.as_bytes())?;
+        let idx = may_idx.ok_or_else(cursor_err)?;
+        let after_pos =
+            get_inventory_pos(&deps.storage, &owner_raw, idx)?.ok_or_else(cursor_err)?;
+        if !may_list(idx, &after, &mut known_pass)? {
+            return Err(cursor_err());
+        }
+        pos = after_pos + 1;
+    }
+    let owned_cnt = get_inventory_len(&deps.storage, &owner_raw)?;
+    while tokens.len() < size && pos < owned_cnt {
+        let page = get_inventory(&deps.storage, &owner_raw, pos, size as u32)?;
+        if page.is_empty() {
+            break;
+        }
+        pos += page.len() as u32;
+        for idx in page {
+            let may_id: Option<String> = may_load(&map2id, &idx.to_le_bytes())?;
+            if let Some(id) = may_id {
+                if may_list(idx, &id, &mut known_pass)? {
+                    tokens.push(id);
+                    if tokens.len() == size {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    to_binary(&QueryAnswer::TokenList { tokens })
+}
+
+/// Returns QueryResult displaying true if the token has been unwrapped.  If sealed metadata
+/// is not enabled, all tokens are considered unwrapped
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+pub fn query_is_unwrapped<S: ReadonlyStorage>(storage: &S, token_id: &str) -> QueryResult {
+    let config = load_config(storage)?;
+    let get_token_res = get_token(storage, token_id, config.token_supply_is_public);
+    match get_token_res {
+        // if the token id is not found, but token supply is private, just say
+        // the token's wrapped state is the same as a newly minted token
+        Err(err) if ContractError::code_of(&err).as_deref() == Some("token_unavailable") => {
+            to_binary(&QueryAnswer::IsUnwrapped {
+                token_is_unwrapped: !config.sealed_metadata_is_enabled,
+            })
+        }
+        Err(err) => Err(err),
+        Ok((token, _idx)) => to_binary(&QueryAnswer::IsUnwrapped {
+            token_is_unwrapped: token.unwrapped,
+        }),
+    }
+}
+
+/// Returns QueryResult displaying whether burning the token would currently reveal its
+/// secret, and if not, why the burn would fail.  If the token supply is private, a
+/// nonexistent token is reported the same way a burn would report it.  If burns do not reveal
+/// secrets, it only checks that the viewer owns the token or may transfer it, like a burn does
+///
+/// # Arguments
+///
+/// * `deps` - a reference to Extern containing all the contract's external dependencies
+/// * `token_id` - string slice of the token id
+/// * `viewer` - optional address and key making an authenticated query request
+/// * `from_permit` - address derived from an Owner permit, if applicable
+pub fn query_can_retrieve<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    token_id: &str,
+    viewer: Option<ViewerInfo>,
+    from_permit: Option<CanonicalAddr>,
+) -> QueryResult {
+    // authenticate the viewer first so a bad key is not reported as a burn failure
+    let access = KeyAccess {
+        query: ScopedQuery::CanRetrieve,
+        token_ids: vec![token_id],
+    };
+    let querier = get_querier(deps, viewer, from_permit, &access)?;
+    let config = load_config(&deps.storage)?;
+    let unavailable = |reason: String| {
+        to_binary(&QueryAnswer::CanRetrieve {
+            retrievable: false,
+            sealed: false,
+            reason: Some(reason),
+        })
+    };
+    if let Err(StdError::GenericErr { msg, .. }) =
+        check_status(&config, ContractStatus::Normal.to_u8())
+    {
+        return unavailable(ContractError::msg_of(msg));
+    }
+    let prep_info = match query_token_prep(deps, token_id, None, querier, ScopedQuery::CanRetrieve)
+    {
+        Ok(info) => info,
+        Err(StdError::GenericErr { msg, .. }) => return unavailable(ContractError::msg_of(msg)),
+        Err(err) => return Err(err),
+    };
+    // recipient-restricted tokens can only be burned by their owner
+    let not_owner = || {
+        let err = ContractError::NotTokenOwner {
+            token_id: token_id.to_string(),
+        };
+        unavailable(err.hide_token(config.token_supply_is_public).to_string())
+    };
+    let owner_only = prep_info.token.recipient_only
+        && (!prep_info.viewer_given || prep_info.token.owner != prep_info.viewer_raw);
+    // a burn that does not reveal the secret only needs the owner or a transfer approval
+    if !config.burn_reveals_secret {
+        let opt_viewer = if prep_info.viewer_given {
+            Some(&prep_info.viewer_raw)
+        } else {
+            None
+        };
+        match check_permission(
+            deps,
+            &prep_info.block,
+            &prep_info.token,
+            token_id,
+            opt_viewer,
+            PermissionType::Transfer,
+            &mut Vec::new(),
+            &prep_info.err_msg,
+            config.owner_is_public,
+        ) {
+            Ok(()) => (),
+            Err(StdError::GenericErr { msg, .. }) => {
+                return unavailable(ContractError::msg_of(msg))
+            }
+            Err(err) => return Err(err),
+        }
+        if owner_only {
+            return not_owner();
+        }
+        return to_binary(&QueryAnswer::CanRetrieve {
+            retrievable: false,
+            sealed: false,
+            reason: None,
+        });
+    }
+    if has_revealable_metadata(&deps.storage, &prep_info.token, prep_info.idx) {
+        if owner_only {
+            return not_owner();
+        }
+        return to_binary(&QueryAnswer::CanRetrieve {
+            retrievable: true,
+            sealed: false,
+            reason: None,
+        });
+    }
+    // let the owner know they can unseal it themselves
+    let reason = if prep_info.viewer_given && prep_info.token.owner == prep_info.viewer_raw {
+        "Sealed metadata must be unwrapped by calling Reveal before it can be viewed".to_string()
+    } else if config.token_supply_is_public {
+        format!("No revealable data was found for token {}", token_id)
+    } else {
+        // don't let anyone else tell a sealed token from one that does not exist
+        let err = ContractError::TokenUnavailable {
+            token_id: token_id.to_string(),
+        };
+        return unavailable(err.to_string());
+    };
+    to_binary(&QueryAnswer::CanRetrieve {
+        retrievable: false,
+        sealed: true,
+        reason: Some(reason),
+    })
+}
+
+/// Returns QueryResult displaying the secrets revealed by an address' burns that are
+/// still within the retrieval window
+///
+/// # Arguments
+///
+/// * `deps` - a reference to Extern containing all the contract's external dependencies
+/// * `address` - a reference to the address whose retained secrets should be displayed
+/// * `viewing_key` - viewing key String, if not using a permit
+/// * `from_permit` - address derived from an Owner permit, if applicable
+pub fn query_retrieved_secrets<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    address: &HumanAddr,
+    viewing_key: Option<String>,
+    from_permit: Option<CanonicalAddr>,
+) -> StdResult<Binary> {
+    let address_raw = deps.api.canonical_address(address)?;
+    let access = KeyAccess {
+        query: ScopedQuery::RetrievedSecrets,
+        token_ids: Vec::new(),
+    };
+    check_querier(
+        &deps.storage,
+        &address_raw,
+        viewing_key,
+        from_permit,
+        &access,
+    )?;
+    // TODO remove this when BlockInfo becomes available to queries
+    let block: BlockInfo = may_load(&deps.storage, BLOCK_KEY)?.unwrap_or_else(|| BlockInfo {
+        height: 1,
+        time: 1,
+        chain_id: "secret-2".to_string(),
+    });
+    let retrieved_store = ReadonlyPrefixedStorage::new(PREFIX_RETRIEVED, &deps.storage);
+    let mut secrets: Vec<RetrievedSecret> =
+        may_load(&retrieved_store, address_raw.as_slice())?.unwrap_or_else(Vec::new);
+    // expired secrets are only purged on the next write, so just hide them
+    secrets.retain(|s| s.expires_at > block.height);
+    to_binary(&QueryAnswer::RetrievedSecrets { secrets })
+}
+
+/// Returns QueryResult displaying an optionally paginated list of all transactions
+/// involving a specified address, displayed in reverse chronological order
+///
+/// # Arguments
+///
+/// * `deps` - a reference to Extern containing all the contract's external dependencies
+/// * `address` - a reference to the address whose transactions should be displayed
+/// * `viewing_key` - viewing key String, if not using a permit
+/// * `from_permit` - address derived from an Owner or History permit, if applicable
+/// * `page` - an optional page number.  If given, the most recent `page` times `page_size`
+///            transactions will be skipped
+/// * `page_size` - optional max number of transactions to display
+/// * `cursor` - optional tx_id of the last transaction already displayed
+/// * `filter` - optional criteria the displayed transactions must meet
+#[allow(clippy::too_many_arguments)]
+pub fn query_transactions<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    address: &HumanAddr,
+    viewing_key: Option<String>,
+    from_permit: Option<CanonicalAddr>,
+    page: Option<u32>,
+    page_size: Option<u32>,
+    cursor: Option<u64>,
+    filter: Option<TxFilter>,
+) -> StdResult<Binary> {
+    let address_raw = deps.api.canonical_address(address)?;
+    let access = KeyAccess {
+        query: ScopedQuery::TransactionHistory,
+        token_ids: Vec::new(),
+    };
+    check_querier(
+        &deps.storage,
+        &address_raw,
+        viewing_key,
+        from_permit,
+        &access,
+    )?;
+    let (txs, total) = get_txs(
+        &deps.api,
+        &deps.storage,
+        &address_raw,
+        page.unwrap_or(0),
+        page_size.unwrap_or(30),
+        cursor,
+        &filter.unwrap_or_default(),
+    )?;
+    to_binary(&QueryAnswer::TransactionHistory { total, txs })
+}
+
+/// Returns QueryResult after verifying that the specified address has transfer approval
+/// for all the listed tokens
+///
+/// # Arguments
+///
+/// * `deps` - a reference to Extern containing all the contract's external dependencies
+/// * `token_ids` - a list of token ids to check if the address has transfer approval
+/// * `address` - a reference to the address whose transfer approval is being verified
+/// * `viewing_key` - viewing key String, if not using a permit
+/// * `from_permit` - address derived from an Owner permit, if applicable
+pub fn query_verify_approval<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    token_ids: &[String],
+    address: &HumanAddr,
+    viewing_key: Option<String>,
+    from_permit: Option<CanonicalAddr>,
+) -> StdResult<Binary> {
+    let config = load_config(&deps.storage)?;
+    if !transfers_supported(&config) {
+        return Err(ContractError::TransferUnsupported.into());
+    }
+    let address_raw = deps.api.canonical_address(address)?;
+    let access = KeyAccess {
+        query: ScopedQuery::VerifyTransferApproval,
+        token_ids: token_ids.iter().map(|id| id.as_str()).collect(),
+    };
+    check_querier(
+        &deps.storage,
+        &address_raw,
+        viewing_key,
+        from_permit,
+        &access,
+    )?;
+    // TODO remove this when BlockInfo becomes available to queries
+    let block: BlockInfo = may_load(&deps.storage, BLOCK_KEY)?.unwrap_or_else(|| BlockInfo {
+        height: 1,
+        time: 1,
+        chain_id: "secret-2".to_string(),
+    });
+    let mut oper_for: Vec<CanonicalAddr> = Vec::new();
+    // an empty list is trivially approved
+    let first_unapproved_token = token_ids.iter().find(|id| {
+        match get_token_if_approved(deps, &block, id, &address_raw, &mut oper_for, &config) {
+            // without transfers, only sealed tokens can be transferred
+            Ok((token, _)) => !config.transfers_enabled && token.unwrapped,
+            Err(_) => true,
+        }
+    });
+    to_binary(&QueryAnswer::VerifyTransferApproval {
+        approved_for_all: first_unapproved_token.is_none(),
+        first_unapproved_token: first_unapproved_token.cloned(),
+    })
+}
+
+/// Returns QueryResult displaying the scoped viewing keys an address has created.  Only the
+/// address' full viewing key or a permit will be accepted for this query
+///
+/// # Arguments
+///
+/// * `deps` - a reference to Extern containing all the contract's external dependencies
+/// * `address` - a reference to the address whose scoped keys should be displayed
+/// * `viewing_key` - viewing key String, if not using a permit
+/// * `from_permit` - address derived from an Owner permit, if applicable
+pub fn query_scoped_keys<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    address: &HumanAddr,
+    viewing_key: Option<String>,
+    from_permit: Option<CanonicalAddr>,
+) -> QueryResult {
+    let address_raw = deps.api.canonical_address(address)?;
+    match from_permit {
+        Some(permit_raw) if permit_raw == address_raw => (),
+        Some(_) => return Err(ContractError::PermitUnauthorized.into()),
+        None => check_key(&deps.storage, &address_raw, viewing_key.unwrap_or_default())?,
+    }
+    let key_store = ReadonlyPrefixedStorage::new(PREFIX_SCOPED_KEYS, &deps.storage);
+    let keys: Vec<ScopedKey> =
+        may_load(&key_store, address_raw.as_slice())?.unwrap_or_else(Vec::new);
+    to_binary(&QueryAnswer::ScopedKeys {
+        keys: keys
+            .into_iter()
+            .map(|k| ScopedKeyInfo {
+                name: k.name,
+                queries: k.queries,
+                token_ids: k.token_ids,
+                expires: k.expires,
+            })
+            .collect(),
+    })
+}
+
+/// Returns QueryResult displaying an optionally paginated summary of the secrets in an
+/// address' inventory.  Private metadata is never displayed
 ///
 /// # Arguments
 ///
 /// * `deps` - a reference to Extern containing all the contract's external dependencies
-/// * `owner` - a reference to the address whose tokens should be displayed
-/// * `viewer` - optional address of the querier if different from the owner
-/// * `viewing_key` - optional viewing key String
-/// * `start_after` - optionally only display token ids that come after this String in
-///                   lexicographical order
-/// * `limit` - optional max number of tokens to display
-pub fn query_tokens<S: Storage, A: Api, Q: Querier>(
+/// * `address` - a reference to the address whose secrets should be displayed
+/// * `viewing_key` - viewing key String, if not using a permit
+/// * `from_permit` - address derived from an Owner permit, if applicable
+/// * `start_after` - optionally only display secrets after this token id in the inventory
+/// * `limit` - optional max number of secrets to display
+pub fn query_outbox<S: Storage, A: Api, Q: Querier>(
     deps: &Extern<S, A, Q>,
-    owner: &HumanAddr,
-    viewer: Option<HumanAddr>,
+    address: &HumanAddr,
     viewing_key: Option<String>,
+    from_permit: Option<CanonicalAddr>,
     start_after: Option<String>,
     limit: Option<u32>,
 ) -> QueryResult {
-    let mut is_viewer = false;
-    let mut is_owner = false;
-    let owner_raw = deps.api.canonical_address(owner)?;
-    let owner_slice = owner_raw.as_slice();
-    let after = start_after.unwrap_or_else(String::new);
-    let size = limit.unwrap_or(30) as usize;
-    let (viewer_raw, vwr_given) = if let Some(vwr) = viewer {
-        (deps.api.canonical_address(&vwr)?, true)
-    } else {
-        (CanonicalAddr(Binary::from(b"notused")), false)
-    };
-    // if we need to validate an address
-    if let Some(key) = viewing_key {
-        // if a viewer was supplied, they're saying they aren't the owner so check
-        // if the key matches the viewer address first
-        if vwr_given && check_key(&deps.storage, &viewer_raw, key.clone()).is_ok() {
-            is_viewer = true;
-            // check if this is the owner's key if we need to
-        } else {
-            check_key(&deps.storage, &owner_raw, key)?;
-            is_owner = true;
-        }
-    }
-    let querier = if is_viewer {
-        Some(&viewer_raw)
-    } else if is_owner {
-        Some(&owner_raw)
-    } else {
-        None
-    };
-    // get list of owner's tokens
-    let owned_store = ReadonlyPrefixedStorage::new(PREFIX_OWNED, &deps.storage);
-    let owned: HashSet<u32> = may_load(&owned_store, owner_slice)?.unwrap_or_else(HashSet::new);
-    // if querier is different than the owner, check if ownership is public
-    let mut known_pass = if !is_owner {
-        let config: Config = load(&deps.storage, CONFIG_KEY)?;
-        let own_priv_store = ReadonlyPrefixedStorage::new(PREFIX_OWNER_PRIV, &deps.storage);
-        let pass: bool = may_load(&own_priv_store, owner_slice)?.unwrap_or(config.owner_is_public);
-        pass
-    } else {
-        true
+    let owner_raw = deps.api.canonical_address(address)?;
+    let access = KeyAccess {
+        query: ScopedQuery::Outbox,
+        token_ids: Vec::new(),
     };
+    check_querier(&deps.storage, &owner_raw, viewing_key, from_permit, &access)?;
     // TODO remove this when BlockInfo becomes available to queries
-    let block = if !known_pass {
-        let b: BlockInfo = may_load(&deps.storage, BLOCK_KEY)?.unwrap_or_else(|| BlockInfo {
-            height: 1,
-            time: 1,
-            chain_id: "secret-2".to_string(),
-        });
-        b
-    } else {
-        BlockInfo {
-            height: 1,
-            time: 1,
-            chain_id: "secret-2".to_string(),
-        }
-    };
-    let exp_idx = PermissionType::ViewOwner.to_usize();
-    let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-    let mut list_it: bool;
-    let mut oper_for: Vec<CanonicalAddr> = Vec::new();
-    let mut tokens: Vec<String> = Vec::new();
+    let block: BlockInfo = may_load(&deps.storage, BLOCK_KEY)?.unwrap_or_else(|| BlockInfo {
+        height: 1,
+        time: 1,
+        chain_id: "secret-2".to_string(),
+    });
+    // resume after the cursor's position in the inventory
+    let mut pos = 0u32;
+    if let Some(after) = start_after {
+        let map2idx = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_INDEX, &deps.storage);
+        let may_idx: Option<u32> = may_load(&map2idx, after.as_bytes())?;
+        let may_pos = match may_idx {
+            Some(idx) => get_inventory_pos(&deps.storage, &owner_raw, idx)?,
+            None => None,
+        };
+        pos = may_pos
+            .ok_or_else(|| StdError::from(ContractError::InvalidCursor { token_id: after }))?
+            + 1;
+    }
+    let page = get_inventory(&deps.storage, &owner_raw, pos, limit.unwrap_or(30))?;
     let map2id = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_ID, &deps.storage);
-    for idx in owned.iter() {
-        let may_id: Option<String> = may_load(&map2id, &idx.to_le_bytes())?;
-        if let Some(id) = may_id {
-            // filter out ids before start_after
-            if id > after {
-                list_it = known_pass;
-                // only check permissions if not public or owner
-                if !known_pass {
-                    let may_token: Option<Token> = json_may_load(&info_store, &idx.to_le_bytes())?;
-                    if let Some(token) = may_token {
-                        list_it = check_perm_core(
-                            deps,
-                            &block,
-                            &token,
-                            &id,
-                            querier,
-                            owner_slice,
-                            exp_idx,
-                            &mut oper_for,
-                            "",
-                        )
-                            .is_ok();
-                        // if querier is found to have ALL permission, no need to check permission ever again
-                        if !oper_for.is_empty() {
-                            known_pass = true;
-                        }
-                    }
-                }
-                if list_it {
-                    tokens.push(id);
-                }
-            }
+    let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
+    let pub_store = ReadonlyPrefixedStorage::new(PREFIX_PUB_META, &deps.storage);
+    let mint_store = ReadonlyPrefixedStorage::new(PREFIX_MINT_INFO, &deps.storage);
+    let view_meta_idx = PermissionType::ViewMetadata.to_usize();
+    let mut secrets: Vec<OutboxSecret> = Vec::new();
+    for idx in page {
+        let token_key = idx.to_le_bytes();
+        let may_id: Option<String> = may_load(&map2id, &token_key)?;
+        let may_token = may_load_token(&info_store, &token_key)?;
+        if let (Some(token_id), Some(token)) = (may_id, may_token) {
+            let mint_info: Option<MintInfo> = may_load(&mint_store, &token_key)?;
+            let recipients = token
+                .permissions
+                .iter()
+                .filter(|p| {
+                    matches!(p.expirations[view_meta_idx], Some(exp) if !exp.is_expired(&block))
+                })
+                .map(|p| deps.api.human_address(&p.address))
+                .collect::<StdResult<Vec<HumanAddr>>>()?;
+            secrets.push(OutboxSecret {
+                token_id,
+                public_metadata: may_load(&pub_store, &token_key)?,
+                minted_at: mint_info.map(|m| m.height),
+                sealed: !token.unwrapped,
+                recipient_only: token.recipient_only,
+                recipients,
+            });
         }
     }
-    tokens.sort_unstable();
-    tokens.truncate(size);
-    to_binary(&QueryAnswer::TokenList { tokens })
+    to_binary(&QueryAnswer::Outbox { secrets })
 }
 
-/// Returns QueryResult displaying true if the token has been unwrapped.  If sealed metadata
-/// is not enabled, all tokens are considered unwrapped
+/// Returns QueryResult displaying an optionally paginated list of the secrets other addresses
+/// minted for an address that are still in its inventory.  The creator is only displayed if
+/// the creator made its ownership public, and the public metadata is only displayed if the
+/// creator allowed it to be viewed before the token is burned
 ///
 /// # Arguments
 ///
-/// * `storage` - a reference to the contract's storage
-pub fn query_is_unwrapped<S: ReadonlyStorage>(storage: &S, token_id: &str) -> QueryResult {
-    let config: Config = load(storage, CONFIG_KEY)?;
-    let get_token_res = get_token(storage, token_id, None);
-    match get_token_res {
-        Err(err) => match err {
-            // if the token id is not found, but token supply is private, just say
-            // the token's wrapped state is the same as a newly minted token
-            StdError::GenericErr { msg, .. }
-            if !config.token_supply_is_public && msg.contains("Token ID") =>
-                {
-                    to_binary(&QueryAnswer::IsUnwrapped {
-                        token_is_unwrapped: !config.sealed_metadata_is_enabled,
-                    })
+/// * `deps` - a reference to Extern containing all the contract's external dependencies
+/// * `address` - a reference to the address whose inbox should be displayed
+/// * `viewing_key` - viewing key String, if not using a permit
+/// * `from_permit` - address derived from an Owner permit, if applicable
+/// * `cursor` - optionally only display secrets after this token id in the inbox
+/// * `limit` - optional max number of secrets to display
+pub fn query_inbox<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    address: &HumanAddr,
+    viewing_key: Option<String>,
+    from_permit: Option<CanonicalAddr>,
+    cursor: Option<String>,
+    limit: Option<u32>,
+) -> QueryResult {
+    let recipient_raw = deps.api.canonical_address(address)?;
+    let access = KeyAccess {
+        query: ScopedQuery::Inbox,
+        token_ids: Vec::new(),
+    };
+    check_querier(
+        &deps.storage,
+        &recipient_raw,
+        viewing_key,
+        from_permit,
+        &access,
+    )?;
+    // resume after the cursor's position in the inbox
+    let mut pos = 0u32;
+    if let Some(after) = cursor {
+        let map2idx = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_INDEX, &deps.storage);
+        let may_idx: Option<u32> = may_load(&map2idx, after.as_bytes())?;
+        let may_pos = match may_idx {
+            Some(idx) => get_inbox_pos(&deps.storage, &recipient_raw, idx)?,
+            None => None,
+        };
+        pos = may_pos
+            .ok_or_else(|| StdError::from(ContractError::InvalidCursor { token_id: after }))?
+            + 1;
+    }
+    let page = get_inbox(&deps.storage, &recipient_raw, pos, limit.unwrap_or(30))?;
+    let config = load_config(&deps.storage)?;
+    let map2id = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_ID, &deps.storage);
+    let pub_store = ReadonlyPrefixedStorage::new(PREFIX_PUB_META, &deps.storage);
+    let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
+    let mint_store = ReadonlyPrefixedStorage::new(PREFIX_MINT_INFO, &deps.storage);
+    let priv_store = ReadonlyPrefixedStorage::new(PREFIX_OWNER_PRIV, &deps.storage);
+    // TODO remove this when BlockInfo becomes available to queries
+    let block: BlockInfo = may_load(&deps.storage, BLOCK_KEY)?.unwrap_or_else(|| BlockInfo {
+        height: 1,
+        time: 1,
+        chain_id: "secret-2".to_string(),
+    });
+    let global_raw = CanonicalAddr(Binary::from(b"public"));
+    let view_owner_idx = PermissionType::ViewOwner.to_usize();
+    let mut secrets: Vec<InboxSecret> = Vec::new();
+    for idx in page {
+        let token_key = idx.to_le_bytes();
+        if let Some(token_id) = may_load::<String, _>(&map2id, &token_key)? {
+            let mint_info: Option<MintInfo> = may_load(&mint_store, &token_key)?;
+            let mut creator: Option<HumanAddr> = None;
+            let mut minted_at: Option<u64> = None;
+            if let Some(info) = mint_info {
+                // the creator allows it if its ownership is public, or if it approved
+                // everyone to view the owner of all its tokens
+                let creator_is_public = may_load(&priv_store, info.creator.as_slice())?
+                    .unwrap_or(config.owner_is_public)
+                    || matches!(
+                        may_load_all_perm(&deps.storage, &info.creator, &global_raw, view_owner_idx)?,
+                        Some(exp) if !exp.is_expired(&block)
+                    );
+                if creator_is_public {
+                    creator = Some(deps.api.human_address(&info.creator)?);
                 }
-            _ => Err(err),
-        },
-        Ok((token, _idx)) => to_binary(&QueryAnswer::IsUnwrapped {
-            token_is_unwrapped: token.unwrapped,
-        }),
+                minted_at = Some(info.height);
+            }
+            // only show the teaser if the creator allowed viewing it before the burn
+            let teaser_is_public = config.metadata_queries_enabled
+                || matches!(may_load_token(&info_store, &token_key)?, Some(t) if t.public_teaser);
+            let public_metadata = if teaser_is_public {
+                may_load(&pub_store, &token_key)?
+            } else {
+                None
+            };
+            secrets.push(InboxSecret {
+                token_id,
+                creator,
+                public_metadata,
+                minted_at,
+            });
+        }
     }
+    to_binary(&QueryAnswer::Inbox { secrets })
 }
 
-/// Returns QueryResult displaying an optionally paginated list of all transactions
-/// involving a specified address, displayed in reverse chronological order
+/// Returns QueryResult displaying the secret requests an address created, most recent first
 ///
 /// # Arguments
 ///
 /// * `deps` - a reference to Extern containing all the contract's external dependencies
-/// * `address` - a reference to the address whose transactions should be displayed
-/// * `viewing_key` - viewing key String
-/// * `page` - an optional page number.  If given, the most recent `page` times `page_size`
-///            transactions will be skipped
-/// * `page_size` - optional max number of transactions to display
-pub fn query_transactions<S: Storage, A: Api, Q: Querier>(
+/// * `address` - a reference to the address whose requests should be displayed
+/// * `viewing_key` - viewing key String, if not using a permit
+/// * `from_permit` - address derived from an Owner permit, if applicable
+/// * `page` - optional page to display
+/// * `page_size` - optional number of requests to display
+pub fn query_secret_requests<S: Storage, A: Api, Q: Querier>(
     deps: &Extern<S, A, Q>,
     address: &HumanAddr,
-    viewing_key: String,
+    viewing_key: Option<String>,
+    from_permit: Option<CanonicalAddr>,
     page: Option<u32>,
     page_size: Option<u32>,
-) -> StdResult<Binary> {
-    let address_raw = deps.api.canonical_address(address)?;
-    check_key(&deps.storage, &address_raw, viewing_key)?;
-    let txs = get_txs(
-        &deps.api,
+) -> QueryResult {
+    let requester_raw = deps.api.canonical_address(address)?;
+    let access = KeyAccess {
+        query: ScopedQuery::SecretRequests,
+        token_ids: Vec::new(),
+    };
+    check_querier(
         &deps.storage,
-        &address_raw,
+        &requester_raw,
+        viewing_key,
+        from_permit,
+        &access,
+    )?;
+    let (ids, total) = get_secret_requests(
+        &deps.storage,
+        &requester_raw,
         page.unwrap_or(0),
         page_size.unwrap_or(30),
     )?;
-    to_binary(&QueryAnswer::TransactionHistory { txs })
+    let req_store = ReadonlyPrefixedStorage::new(PREFIX_SECRET_REQUESTS, &deps.storage);
+    let mut requests: Vec<SecretRequestInfo> = Vec::new();
+    for request_id in ids {
+        let request: SecretRequest = load(&req_store, &request_id.to_le_bytes())?;
+        requests.push(SecretRequestInfo {
+            request_id,
+            description: request.description,
+            responder: request
+                .responder
+                .map(|r| deps.api.human_address(&r))
+                .transpose()?,
+            expires: request.expires,
+            token_id: request.token_id,
+        });
+    }
+    to_binary(&QueryAnswer::SecretRequests { total, requests })
 }
 
-/// Returns QueryResult after verifying that the specified address has transfer approval
-/// for all the listed tokens
+/// Returns QueryResult displaying the progress of migrating stored records to the current
+/// storage schema
 ///
 /// # Arguments
 ///
 /// * `deps` - a reference to Extern containing all the contract's external dependencies
-/// * `token_ids` - a list of token ids to check if the address has transfer approval
-/// * `address` - a reference to the address whose transactions should be displayed
-/// * `viewing_key` - viewing key String
-pub fn query_verify_approval<S: Storage, A: Api, Q: Querier>(
-    _deps: &Extern<S, A, Q>,
-    token_ids: &[String],
-    _address: &HumanAddr,
-    _viewing_key: String,
-) -> StdResult<Binary> {
-    to_binary(&QueryAnswer::VerifyTransferApproval {
-        approved_for_all: false,
-        first_unapproved_token: Some(token_ids.first().unwrap().to_string()),
+/// * `viewer` - optional address and key making an authenticated query request
+pub fn query_migration_status<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    viewer: Option<ViewerInfo>,
+) -> QueryResult {
+    let config = load_config(&deps.storage)?;
+    let complete = config.schema_version >= SCHEMA_VERSION;
+    // only reveal the record counts to those allowed to view the token supply
+    let counts = if check_view_supply(deps, viewer, None).is_ok() {
+        let progress: MigrationProgress = if complete {
+            MigrationProgress {
+                tokens: config.mint_cnt,
+                txs: config.tx_cnt,
+            }
+        } else {
+            may_load(&deps.storage, MIGRATION_KEY)?.unwrap_or_default()
+        };
+        Some((progress, config.mint_cnt, config.tx_cnt))
+    } else {
+        None
+    };
+    to_binary(&QueryAnswer::MigrationStatus {
+        schema_version: config.schema_version,
+        target_version: SCHEMA_VERSION,
+        complete,
+        tokens_migrated: counts.as_ref().map(|c| c.0.tokens),
+        total_tokens: counts.as_ref().map(|c| c.1),
+        txs_migrated: counts.as_ref().map(|c| c.0.txs),
+        total_txs: counts.map(|c| c.2),
     })
 }
 
@@ -2124,13 +3802,26 @@ pub fn query_verify_approval<S: Storage, A: Api, Q: Querier>(
 /// * `deps` - a reference to Extern containing all the contract's external dependencies
 /// * `contract` - a reference to the contract's address whose code hash is being requested
 pub fn query_code_hash<S: Storage, A: Api, Q: Querier>(
-    _deps: &Extern<S, A, Q>,
-    _contract: &HumanAddr,
+    deps: &Extern<S, A, Q>,
+    contract: &HumanAddr,
 ) -> QueryResult {
-    to_binary(&QueryAnswer::RegisteredCodeHash {
-        code_hash: None,
-        also_implements_batch_receive_nft: false,
-    })
+    let config = load_config(&deps.storage)?;
+    if !transfers_supported(&config) {
+        return Err(ContractError::ReceiveUnsupported.into());
+    }
+    let contract_raw = deps.api.canonical_address(contract)?;
+    let store = ReadonlyPrefixedStorage::new(PREFIX_RECEIVERS, &deps.storage);
+    let registration: Option<ReceiveRegistration> = may_load(&store, contract_raw.as_slice())?;
+    match registration {
+        Some(reg) => to_binary(&QueryAnswer::RegisteredCodeHash {
+            code_hash: Some(reg.code_hash),
+            also_implements_batch_receive_nft: reg.impl_batch,
+        }),
+        None => to_binary(&QueryAnswer::RegisteredCodeHash {
+            code_hash: None,
+            also_implements_batch_receive_nft: false,
+        }),
+    }
 }
 
 // bundled info when prepping an authenticated token query
@@ -2159,19 +3850,24 @@ pub struct TokenQueryInfo {
 /// * `deps` - a reference to Extern containing all the contract's external dependencies
 /// * `token_id` - string slice of the token id
 /// * `viewer` - optional address and key making an authenticated query request
+/// * `from_permit` - address derived from an Owner permit, if applicable
+/// * `query` - the query being authenticated, to check the scope of a viewing key
 fn query_token_prep<S: Storage, A: Api, Q: Querier>(
     deps: &Extern<S, A, Q>,
     token_id: &str,
     viewer: Option<ViewerInfo>,
+    from_permit: Option<CanonicalAddr>,
+    query: ScopedQuery,
 ) -> StdResult<TokenQueryInfo> {
-    let (viewer_raw, viewer_given) = if let Some(vwr) = viewer {
-        let viewer_raw = deps.api.canonical_address(&vwr.address)?;
-        check_key(&deps.storage, &viewer_raw, vwr.viewing_key)?;
-        (viewer_raw, true)
-    } else {
-        (CanonicalAddr(Binary::from(b"notused")), false)
+    let access = KeyAccess {
+        query,
+        token_ids: vec![token_id],
+    };
+    let (viewer_raw, viewer_given) = match get_querier(deps, viewer, from_permit, &access)? {
+        Some(viewer_raw) => (viewer_raw, true),
+        None => (CanonicalAddr(Binary::from(b"notused")), false),
     };
-    let config: Config = load(&deps.storage, CONFIG_KEY)?;
+    let config = load_config(&deps.storage)?;
     // TODO remove this when BlockInfo becomes available to queries
     let block: BlockInfo = may_load(&deps.storage, BLOCK_KEY)?.unwrap_or_else(|| BlockInfo {
         height: 1,
@@ -2182,14 +3878,7 @@ fn query_token_prep<S: Storage, A: Api, Q: Querier>(
         "You are not authorized to perform this action on token {}",
         token_id
     );
-    // if token supply is private, don't leak that the token id does not exist
-    // instead just say they are not authorized for that token
-    let opt_err = if config.token_supply_is_public {
-        None
-    } else {
-        Some(&*err_msg)
-    };
-    let (token, idx) = get_token(&deps.storage, token_id, opt_err)?;
+    let (token, idx) = get_token(&deps.storage, token_id, config.token_supply_is_public)?;
     Ok(TokenQueryInfo {
         viewer_raw,
         viewer_given,
@@ -2209,14 +3898,18 @@ fn query_token_prep<S: Storage, A: Api, Q: Querier>(
 /// * `deps` - a reference to Extern containing all the contract's external dependencies
 /// * `token_id` - string slice of the token id
 /// * `viewer` - optional address and key making an authenticated query request
+/// * `from_permit` - address derived from an Owner permit, if applicable
 /// * `include_expired` - optionally true if the Approval lists should include expired Approvals
+/// * `query` - the query being authenticated, to check the scope of a viewing key
 fn process_cw721_owner_of<S: Storage, A: Api, Q: Querier>(
     deps: &Extern<S, A, Q>,
     token_id: &str,
     viewer: Option<ViewerInfo>,
+    from_permit: Option<CanonicalAddr>,
     include_expired: Option<bool>,
+    query: ScopedQuery,
 ) -> StdResult<(Option<HumanAddr>, Vec<Cw721Approval>, u32)> {
-    let prep_info = query_token_prep(deps, token_id, viewer)?;
+    let prep_info = query_token_prep(deps, token_id, viewer, from_permit, query)?;
     let opt_viewer = if prep_info.viewer_given {
         Some(&prep_info.viewer_raw)
     } else {
@@ -2280,9 +3973,7 @@ fn get_owner_of_resp<S: Storage, A: Api, Q: Querier>(
                 transfer_idx,
                 include_expired,
             )?;
-            let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-            let all_perm: Vec<Permission> =
-                json_may_load(&all_store, token.owner.as_slice())?.unwrap_or_else(Vec::new);
+            let all_perm = get_all_perms(&deps.storage, &token.owner)?;
             gen_cw721_approvals(
                 &deps.api,
                 block,
@@ -2409,23 +4100,26 @@ fn gen_snip721_approvals<A: Api>(
 ///
 /// * `deps` - a reference to Extern containing all the contract's external dependencies
 /// * `viewer` - optional address and key making an authenticated query request
+/// * `from_permit` - address derived from an Owner permit, if applicable
 fn check_view_supply<S: Storage, A: Api, Q: Querier>(
     deps: &Extern<S, A, Q>,
     viewer: Option<ViewerInfo>,
+    from_permit: Option<CanonicalAddr>,
 ) -> StdResult<()> {
-    let config: Config = load(&deps.storage, CONFIG_KEY)?;
+    let config = load_config(&deps.storage)?;
     let mut is_auth = config.token_supply_is_public;
     if !is_auth {
-        if let Some(vwr) = viewer {
-            let viewer_raw = deps.api.canonical_address(&vwr.address)?;
-            if config.admin == viewer_raw {
-                is_auth = true;
-            }
+        let querier = match from_permit {
+            Some(permit_raw) => Some(permit_raw),
+            None => viewer
+                .map(|vwr| deps.api.canonical_address(&vwr.address))
+                .transpose()?,
+        };
+        if querier.as_ref() == Some(&config.admin) {
+            is_auth = true;
         }
         if !is_auth {
-            return Err(StdError::generic_err(
-                "The token supply of this contract is private",
-            ));
+            return Err(ContractError::PrivateSupply.into());
         }
     }
     Ok(())
@@ -2452,9 +4146,122 @@ fn check_key<S: ReadonlyStorage>(
     if input_key.check_viewing_key(&load_key) {
         return Ok(());
     }
-    Err(StdError::generic_err(
-        "Wrong viewing key for this address or viewing key not set",
-    ))
+    Err(ContractError::WrongViewingKey.into())
+}
+
+/// the query a viewing key is presented for and the tokens it is about
+struct KeyAccess<'a> {
+    /// the query being authenticated
+    query: ScopedQuery,
+    /// the tokens the query is about, if any
+    token_ids: Vec<&'a str>,
+}
+
+/// Returns StdResult<()> result of validating either an address' full viewing key, or one
+/// of its scoped keys that allows the requested access
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `address` - a reference to the address whose key should be validated
+/// * `viewing_key` - String key used for authentication
+/// * `access` - a reference to the access the key is being used for
+fn check_scoped_key<S: ReadonlyStorage>(
+    storage: &S,
+    address: &CanonicalAddr,
+    viewing_key: String,
+    access: &KeyAccess,
+) -> StdResult<()> {
+    let input_key = ViewingKey(viewing_key);
+    if check_key(storage, address, input_key.0.clone()).is_ok() {
+        return Ok(());
+    }
+    let key_store = ReadonlyPrefixedStorage::new(PREFIX_SCOPED_KEYS, storage);
+    let keys: Vec<ScopedKey> = may_load(&key_store, address.as_slice())?.unwrap_or_else(Vec::new);
+    let scoped = keys
+        .iter()
+        .find(|k| input_key.check_viewing_key(&k.key))
+        .ok_or_else(|| StdError::from(ContractError::WrongViewingKey))?;
+    // TODO remove this when BlockInfo becomes available to queries
+    let block: BlockInfo = may_load(storage, BLOCK_KEY)?.unwrap_or_else(|| BlockInfo {
+        height: 1,
+        time: 1,
+        chain_id: "secret-2".to_string(),
+    });
+    if scoped.expires.is_expired(&block) {
+        return Err(ContractError::ScopedKeyExpired {
+            name: scoped.name.clone(),
+        }
+        .into());
+    }
+    // a key limited to some tokens can only be used for queries about those tokens
+    let tokens_allowed = match scoped.token_ids.as_ref() {
+        Some(ids) => {
+            !access.token_ids.is_empty()
+                && access
+                    .token_ids
+                    .iter()
+                    .all(|id| ids.iter().any(|allowed| allowed == id))
+        }
+        None => true,
+    };
+    if !tokens_allowed || !scoped.queries.contains(&access.query) {
+        return Err(ContractError::ScopedKeyUnauthorized {
+            name: scoped.name.clone(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Returns StdResult<()> result of authenticating an address with either its viewing key
+/// or a permit it signed
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `address` - a reference to the address being authenticated
+/// * `viewing_key` - optional String key used for authentication
+/// * `from_permit` - address derived from a permit, if applicable
+/// * `access` - a reference to the access a viewing key is being used for
+fn check_querier<S: ReadonlyStorage>(
+    storage: &S,
+    address: &CanonicalAddr,
+    viewing_key: Option<String>,
+    from_permit: Option<CanonicalAddr>,
+    access: &KeyAccess,
+) -> StdResult<()> {
+    match from_permit {
+        Some(permit_raw) if permit_raw == *address => Ok(()),
+        Some(_) => Err(ContractError::PermitUnauthorized.into()),
+        None => check_scoped_key(storage, address, viewing_key.unwrap_or_default(), access),
+    }
+}
+
+/// Returns StdResult<Option<CanonicalAddr>> of the authenticated querier, if one was given
+///
+/// # Arguments
+///
+/// * `deps` - a reference to Extern containing all the contract's external dependencies
+/// * `viewer` - optional address and key making an authenticated query request
+/// * `from_permit` - address derived from a permit, if applicable
+/// * `access` - a reference to the access a viewing key is being used for
+fn get_querier<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    viewer: Option<ViewerInfo>,
+    from_permit: Option<CanonicalAddr>,
+    access: &KeyAccess,
+) -> StdResult<Option<CanonicalAddr>> {
+    if from_permit.is_some() {
+        return Ok(from_permit);
+    }
+    viewer
+        .map(|vwr| {
+            let viewer_raw = deps.api.canonical_address(&vwr.address)?;
+            check_scoped_key(&deps.storage, &viewer_raw, vwr.viewing_key, access)?;
+            Ok(viewer_raw)
+        })
+        .transpose()
 }
 
 /// Returns StdResult<()>
@@ -2501,7 +4308,6 @@ pub fn check_permission<S: Storage, A: Api, Q: Querier>(
         token,
         token_id,
         opt_sender,
-        owner_slice,
         exp_idx,
         oper_for,
         custom_err,
@@ -2519,7 +4325,6 @@ pub fn check_permission<S: Storage, A: Api, Q: Querier>(
 /// * `token` - a reference to the token
 /// * `token_id` - token ID String slice
 /// * `opt_sender` - a optional reference to the address trying to get access to the token
-/// * `owner_slice` - the owner of the token represented as a byte slice
 /// * `exp_idx` - permission type we are checking represented as usize
 /// * `oper_for` - a mutable reference to a list of owners that gave the sender "all" permission
 /// * `custom_err` - string slice of the error msg to return if not permitted
@@ -2530,15 +4335,25 @@ fn check_perm_core<S: Storage, A: Api, Q: Querier>(
     token: &Token,
     token_id: &str,
     opt_sender: Option<&CanonicalAddr>,
-    owner_slice: &[u8],
     exp_idx: usize,
     oper_for: &mut Vec<CanonicalAddr>,
     custom_err: &str,
 ) -> StdResult<()> {
     // if did not already pass with "all" permission for this owner
     if !oper_for.contains(&token.owner) {
+        let mut expired = false;
         let mut err_msg = custom_err;
         let mut expired_msg = String::new();
+        let fail = |expired: bool, detail: &str| {
+            let token_id = token_id.to_string();
+            let detail = detail.to_string();
+            let err = if expired {
+                ContractError::TokenAccessExpired { token_id, detail }
+            } else {
+                ContractError::TokenUnauthorized { token_id, detail }
+            };
+            perm_error(deps, err)
+        };
         let global_raw = CanonicalAddr(Binary::from(b"public"));
         let sender = opt_sender.unwrap_or(&global_raw);
         // if this is the owner, all is good
@@ -2561,12 +4376,13 @@ fn check_perm_core<S: Storage, A: Api, Q: Querier>(
                         if perm.address != global_raw {
                             expired_msg
                                 .push_str(&format!("Access to token {} has expired", token_id));
+                            expired = true;
                             err_msg = &expired_msg;
                         }
                         // if both were expired, there can't be any ALL permissions
                         // so just exit early
                         if one_expired {
-                            return Err(StdError::generic_err(err_msg));
+                            return Err(fail(expired, err_msg));
                         } else {
                             one_expired = true;
                         }
@@ -2581,103 +4397,149 @@ fn check_perm_core<S: Storage, A: Api, Q: Querier>(
             }
         }
         // check if the entire permission type is public or the sender has ALL permission.
-        // Can't use find because even if the global or sender permission expired, you
-        // still want to see if the other is still valid
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let may_list: Option<Vec<Permission>> = json_may_load(&all_store, owner_slice)?;
-        found_one = false;
-        if let Some(list) = may_list {
-            for perm in &list {
-                if perm.address == *sender || perm.address == global_raw {
-                    if let Some(exp) = perm.expirations[exp_idx] {
-                        if !exp.is_expired(block) {
-                            oper_for.push(token.owner.clone());
-                            return Ok(());
-                            // if the permission expired
-                        } else {
-                            // if this is the sender let them know the permission expired
-                            if perm.address != global_raw {
-                                expired_msg.push_str(&format!(
-                                    "Access to all tokens of {} has expired",
-                                    &deps.api.human_address(&token.owner)?
-                                ));
-                                err_msg = &expired_msg;
-                            }
-                            // if both were expired, just exit early
-                            if one_expired {
-                                return Err(StdError::generic_err(err_msg));
-                            } else {
-                                one_expired = true;
-                            }
-                        }
-                    }
-                    // we can quit if we found both the sender and the global
-                    if found_one {
-                        return Err(StdError::generic_err(err_msg));
-                    } else {
-                        found_one = true;
-                    }
+        // Even if the global or sender permission expired, you still want to see if the
+        // other is still valid
+        let mut check_addrs = vec![sender];
+        if *sender != global_raw {
+            check_addrs.push(&global_raw);
+        }
+        for address in check_addrs {
+            if let Some(exp) = may_load_all_perm(&deps.storage, &token.owner, address, exp_idx)? {
+                if !exp.is_expired(block) {
+                    oper_for.push(token.owner.clone());
+                    return Ok(());
+                }
+                // if this is the sender let them know the permission expired
+                if *address != global_raw {
+                    expired_msg.push_str(&format!(
+                        "Access to all tokens of {} has expired",
+                        &deps.api.human_address(&token.owner)?
+                    ));
+                    expired = true;
+                    err_msg = &expired_msg;
+                }
+                // if both were expired, just exit early
+                if one_expired {
+                    return Err(fail(expired, err_msg));
+                } else {
+                    one_expired = true;
                 }
             }
         }
-        return Err(StdError::generic_err(err_msg));
+        return Err(fail(expired, err_msg));
+    }
+    Ok(())
+}
+
+/// Returns StdError for a failed permission check that hides which check failed if the
+/// token supply is private
+///
+/// # Arguments
+///
+/// * `deps` - a reference to Extern containing all the contract's external dependencies
+/// * `err` - the reason the permission check failed
+fn perm_error<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    err: ContractError,
+) -> StdError {
+    match load_config(&deps.storage) {
+        Ok(config) => token_error(config.token_supply_is_public, err),
+        Err(err) => err,
+    }
+}
+
+/// Returns StdResult<(Token, u32)>
+///
+/// returns the token information if the token can be burned to reveal its secret
+///
+/// # Arguments
+///
+/// * `deps` - a reference to Extern containing all the contract's external dependencies
+/// * `token_id` - token ID String slice
+/// * `config` - a reference to the Config
+fn get_token_if_permitted<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    token_id: &str,
+    config: &Config,
+) -> StdResult<(Token, u32)> {
+    let (token, idx) = get_token(&deps.storage, token_id, config.token_supply_is_public)?;
+    if has_revealable_metadata(&deps.storage, &token, idx) {
+        Ok((token, idx))
+    } else {
+        Err(token_error(
+            config.token_supply_is_public,
+            ContractError::TokenSealed {
+                token_id: token_id.to_string(),
+                detail: format!("No revealable data was found for token {}", token_id),
+            },
+        ))
     }
-    Ok(())
 }
 
 /// Returns StdResult<(Token, u32)>
 ///
-/// returns the token information if the sender has authorization
+/// returns the token information if the sender owns the token or has been approved to
+/// transfer it
 ///
 /// # Arguments
 ///
 /// * `deps` - a reference to Extern containing all the contract's external dependencies
 /// * `block` - a reference to the current BlockInfo
 /// * `token_id` - token ID String slice
-/// * `sender` - a optional reference to the address trying to get access to the token
-/// * `perm_type` - PermissionType we are checking
+/// * `sender` - a reference to the address trying to transfer or burn the token
 /// * `oper_for` - a mutable reference to a list of owners that gave the sender "all" permission
 /// * `config` - a reference to the Config
-fn get_token_if_permitted<S: Storage, A: Api, Q: Querier>(
+fn get_token_if_approved<S: Storage, A: Api, Q: Querier>(
     deps: &Extern<S, A, Q>,
+    block: &BlockInfo,
     token_id: &str,
+    sender: &CanonicalAddr,
+    oper_for: &mut Vec<CanonicalAddr>,
     config: &Config,
 ) -> StdResult<(Token, u32)> {
-    let custom_err = format!(
+    let (token, idx) = get_token(&deps.storage, token_id, config.token_supply_is_public)?;
+    let err_msg = format!(
         "You are not authorized to perform this action on token {}",
         token_id
     );
-    // if token supply is private, don't leak that the token id does not exist
-    // instead just say they are not authorized for that token
-    let opt_err = if config.token_supply_is_public {
-        None
-    } else {
-        Some(&*custom_err)
-    };
-    let (token, idx) = get_token(&deps.storage, token_id, opt_err)?;
-    if has_revealable_metadata(deps, &token_id).unwrap() {
-        Ok((token, idx))
-    } else {
-        Err(StdError::generic_err(format!(
-            "No revealable data was found for token {}",
-            token_id
-        )))
-    }
+    check_permission(
+        deps,
+        block,
+        &token,
+        token_id,
+        Some(sender),
+        PermissionType::Transfer,
+        oper_for,
+        &err_msg,
+        config.owner_is_public,
+    )?;
+    Ok((token, idx))
 }
 
-fn has_revealable_metadata<S: Storage, A: Api, Q: Querier>(
-    deps: &Extern<S, A, Q>,
-    token_id: &str,
-) -> StdResult<bool> {
-    let prep_info = query_token_prep(deps, token_id, None)?;
+/// Returns bool
+///
+/// returns true if tokens can be transferred, either freely or, if the contract opted in to
+/// gifting, while they are sealed
+///
+/// # Arguments
+///
+/// * `config` - a reference to the Config
+fn transfers_supported(config: &Config) -> bool {
+    config.transfers_enabled || (config.gifting_enabled && config.sealed_metadata_is_enabled)
+}
 
-    let priv_store = ReadonlyPrefixedStorage::new(PREFIX_PRIV_META, &deps.storage);
-    let result = &priv_store.get(&prep_info.idx.to_le_bytes());
-    let has_private_metadata = match result {
-        Some(..) => true,
-        None => false,
-    };
-    Ok(!has_private_metadata || prep_info.token.unwrapped)
+/// Returns bool
+///
+/// returns true if the token has no private metadata or it has been unwrapped
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `token` - a reference to the token
+/// * `idx` - the token's index
+fn has_revealable_metadata<S: ReadonlyStorage>(storage: &S, token: &Token, idx: u32) -> bool {
+    let priv_store = ReadonlyPrefixedStorage::new(PREFIX_PRIV_META, storage);
+    priv_store.get(&idx.to_le_bytes()).is_none() || token.unwrapped
 }
 
 /// Returns StdResult<(Token, u32)>
@@ -2688,42 +4550,66 @@ fn has_revealable_metadata<S: Storage, A: Api, Q: Querier>(
 ///
 /// * `storage` - a reference to contract's storage
 /// * `token_id` - token id string slice
-/// * `custom_err` - optional custom error message to use if don't want to reveal that a token
-///                  does not exist
+/// * `supply_is_public` - true if it is ok to reveal that a token does not exist
 fn get_token<S: ReadonlyStorage>(
     storage: &S,
     token_id: &str,
-    custom_err: Option<&str>,
+    supply_is_public: bool,
 ) -> StdResult<(Token, u32)> {
-    let default_err: String;
-    let not_found = if let Some(err) = custom_err {
-        err
-    } else {
-        default_err = format!("Token ID: {} not found", token_id);
-        &*default_err
-    };
     let map2idx = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_INDEX, storage);
-    let idx: u32 =
-        may_load(&map2idx, token_id.as_bytes())?.ok_or_else(|| StdError::generic_err(not_found))?;
+    let idx: u32 = may_load(&map2idx, token_id.as_bytes())?.ok_or_else(|| {
+        token_error(
+            supply_is_public,
+            ContractError::TokenNotFound {
+                token_id: token_id.to_string(),
+            },
+        )
+    })?;
     let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, storage);
-    let token: Token = json_may_load(&info_store, &idx.to_le_bytes())?.ok_or_else(|| {
-        StdError::generic_err(format!("Unable to find token info for {}", token_id))
+    let token = may_load_token(&info_store, &idx.to_le_bytes())?.ok_or_else(|| {
+        StdError::from(ContractError::MissingTokenInfo {
+            token_id: token_id.to_string(),
+        })
     })?;
     Ok((token, idx))
 }
 
+/// Returns StdResult<String>
+///
+/// generates a new random token id from the prng seed
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `env` - a reference to the Env of contract's environment
+/// * `entropy` - string slice of additional entropy
+fn gen_token_id<S: ReadonlyStorage>(storage: &S, env: &Env, entropy: &str) -> StdResult<String> {
+    let prng_seed: Vec<u8> = load(storage, PRNG_SEED_KEY)?;
+    let mut rng_entropy = Vec::with_capacity(16 + env.message.sender.len() + entropy.len());
+    rng_entropy.extend_from_slice(&env.block.height.to_be_bytes());
+    rng_entropy.extend_from_slice(&env.block.time.to_be_bytes());
+    rng_entropy.extend_from_slice(env.message.sender.0.as_bytes());
+    rng_entropy.extend_from_slice(entropy.as_bytes());
+    let mut rng = Prng::new(&prng_seed, &rng_entropy);
+    Ok(hex::encode(&rng.rand_bytes()[..16]))
+}
+
 /// Returns StdResult<()> that will error if the priority level of the action is not
-/// equal to or greater than the current contract status level
+/// equal to or greater than the current contract status level.  A contract whose storage
+/// has not been migrated to the current schema is treated as having StopAll status
 ///
 /// # Arguments
 ///
-/// * `contract_status` - u8 representation of the current contract status
+/// * `config` - a reference to the Config
 /// * `priority` - u8 representing the highest status level this action may execute at
-fn check_status(contract_status: u8, priority: u8) -> StdResult<()> {
+fn check_status(config: &Config, priority: u8) -> StdResult<()> {
+    let contract_status = if config.schema_version < SCHEMA_VERSION {
+        ContractStatus::StopAll.to_u8()
+    } else {
+        config.status
+    };
     if priority < contract_status {
-        return Err(StdError::generic_err(
-            "The contract admin has temporarily disabled this action",
-        ));
+        return Err(ContractError::ContractDisabled.into());
     }
     Ok(())
 }
@@ -2748,27 +4634,29 @@ fn set_metadata<S: Storage>(
     metadata: &Metadata,
     config: &Config,
 ) -> StdResult<()> {
-    let custom_err = format!("Not authorized to update metadata of token {}", token_id);
-    // if token supply is private, don't leak that the token id does not exist
-    // instead just say they are not authorized for that token
-    let opt_err = if config.token_supply_is_public {
-        None
-    } else {
-        Some(&*custom_err)
-    };
-    let (token, idx) = get_token(storage, token_id, opt_err)?;
-    // do not allow the altering of sealed metadata
-    if !token.unwrapped && prefix == PREFIX_PRIV_META {
-        return Err(StdError::generic_err(
-            "The private metadata of a sealed token can not be modified",
-        ));
-    }
+    let (token, idx) = get_token(storage, token_id, config.token_supply_is_public)?;
     if !(token.owner == *sender && config.owner_may_update_metadata) {
         let minters: Vec<CanonicalAddr> = may_load(storage, MINTERS_KEY)?.unwrap_or_else(Vec::new);
         if !(minters.contains(sender) && config.minter_may_update_metadata) {
-            return Err(StdError::generic_err(custom_err));
+            return Err(token_error(
+                config.token_supply_is_public,
+                ContractError::TokenUnauthorized {
+                    token_id: token_id.to_string(),
+                    detail: format!("Not authorized to update metadata of token {}", token_id),
+                },
+            ));
         }
     }
+    // do not allow the altering of sealed metadata
+    if !token.unwrapped && prefix == PREFIX_PRIV_META {
+        return Err(token_error(
+            config.token_supply_is_public,
+            ContractError::TokenSealed {
+                token_id: token_id.to_string(),
+                detail: "The private metadata of a sealed token can not be modified".to_string(),
+            },
+        ));
+    }
     let mut meta_store = PrefixedStorage::new(prefix, storage);
     save(&mut meta_store, &idx.to_le_bytes(), metadata)?;
     Ok(())
@@ -2854,16 +4742,13 @@ pub struct ProcessAccInfo {
 /// * `address` - a reference to the address being granted/revoked permission
 /// * `owner` - a reference to the permission owner's address
 /// * `proc_info` - a mutable reference to the ProcessAccInfo
-/// * `all_perm_in` - when from an operator, the all_perms have already been read
 fn process_accesses<S: Storage>(
     storage: &mut S,
     env: &Env,
     address: &CanonicalAddr,
     owner: &CanonicalAddr,
     proc_info: &mut ProcessAccInfo,
-    all_perm_in: Option<Vec<Permission>>,
 ) -> StdResult<()> {
-    let owner_slice = owner.as_slice();
     let expiration = proc_info.expires.unwrap_or_default();
     let expirations = vec![expiration; 3];
     let mut alt_all_perm = AlterPermTable::default();
@@ -2873,15 +4758,12 @@ fn process_accesses<S: Storage>(
     let mut add_load_list = Vec::new();
     let mut load_all = false;
     let mut load_all_exp = vec![Expiration::AtHeight(0); 3];
-    let mut all_perm = if proc_info.from_oper {
-        all_perm_in.ok_or_else(|| StdError::generic_err("Unable to get operator list"))?
-    } else {
-        Vec::new()
-    };
-    let mut oper_pos = 0usize;
-    let mut found_perm = false;
-    let mut tried_oper = false;
     let num_perm_types = PermissionType::ViewOwner.num_types();
+    // look up the "all" permissions this address already has
+    let mut all_perm: [Option<Expiration>; 3] = [None; 3];
+    for (i, perm) in all_perm.iter_mut().enumerate() {
+        *perm = may_load_all_perm(storage, owner, address, i)?;
+    }
 
     // do every permission type
     for i in 0..num_perm_types {
@@ -2889,64 +4771,42 @@ fn process_accesses<S: Storage>(
             match acc {
                 AccessLevel::ApproveToken | AccessLevel::RevokeToken => {
                     if !proc_info.token_given {
-                        return Err(StdError::generic_err(
-                            "Attempted to grant/revoke permission for a token, but did not specify a token ID",
-                        ));
+                        return Err(ContractError::MissingTokenId.into());
                     }
                     let is_approve = matches!(acc, AccessLevel::ApproveToken);
-                    // load the "all" permissions if we haven't already and see if the address is there
-                    if !tried_oper {
-                        if !proc_info.from_oper {
-                            let all_store =
-                                ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, storage);
-                            all_perm =
-                                json_may_load(&all_store, owner_slice)?.unwrap_or_else(Vec::new);
-                        }
-                        if let Some(pos) = all_perm.iter().position(|p| p.address == *address) {
-                            found_perm = true;
-                            oper_pos = pos;
-                        }
-                        tried_oper = true;
-                    }
                     // if this address has "all" permission
-                    if found_perm {
-                        if let Some(op) = all_perm.get(oper_pos) {
-                            if let Some(exp) = op.expirations[i] {
-                                if !exp.is_expired(&env.block) {
-                                    // don't allow one operator to change to another
-                                    // operator's permissions
-                                    if proc_info.from_oper {
-                                        // if adding, don't do anything
-                                        if is_approve {
-                                            return Ok(());
-                                            // if revoking, throw error
-                                        } else {
-                                            return Err(StdError::generic_err(
-                                                "Can not revoke transfer permission from an existing operator",
-                                            ));
-                                        }
-                                    }
-                                    // if you are granting token approval to an existing
-                                    // operator, but not changing the expiration, nothing
-                                    // needs to be done
-                                    if is_approve && expirations[i] == exp {
-                                        return Ok(());
-                                    }
-                                    // need to put all the other tokens in the AuthList
-                                    alt_auth_list.full[i] = true;
-                                    // going to load all the other tokens
-                                    load_all = true;
-                                    // and use the "all" expiration as the token permission expirations
-                                    load_all_exp[i] = exp;
-                                    // add this address to all the other token permissions
-                                    alt_load_tok_perm.add[i] = true;
-                                    alt_load_tok_perm.has_update = true;
+                    if let Some(exp) = all_perm[i] {
+                        if !exp.is_expired(&env.block) {
+                            // don't allow one operator to change to another
+                            // operator's permissions
+                            if proc_info.from_oper {
+                                // if adding, don't do anything
+                                if is_approve {
+                                    return Ok(());
+                                    // if revoking, throw error
+                                } else {
+                                    return Err(ContractError::RevokeOperator.into());
                                 }
-                                // remove "all" permission
-                                alt_all_perm.remove[i] = true;
-                                alt_all_perm.has_update = true;
                             }
+                            // if you are granting token approval to an existing
+                            // operator, but not changing the expiration, nothing
+                            // needs to be done
+                            if is_approve && expirations[i] == exp {
+                                return Ok(());
+                            }
+                            // need to put all the other tokens in the AuthList
+                            alt_auth_list.full[i] = true;
+                            // going to load all the other tokens
+                            load_all = true;
+                            // and use the "all" expiration as the token permission expirations
+                            load_all_exp[i] = exp;
+                            // add this address to all the other token permissions
+                            alt_load_tok_perm.add[i] = true;
+                            alt_load_tok_perm.has_update = true;
                         }
+                        // remove "all" permission
+                        alt_all_perm.remove[i] = true;
+                        alt_all_perm.has_update = true;
                     }
                     if is_approve {
                         // add permission for this token
@@ -2990,29 +4850,22 @@ fn process_accesses<S: Storage>(
                     }
                 }
             }
+            // lazily clean up an expired "all" permission that is not being changed
+        } else if matches!(all_perm[i], Some(exp) if exp.is_expired(&env.block)) {
+            alt_all_perm.remove[i] = true;
+            alt_all_perm.has_update = true;
         }
     }
     // update "all" permissions
     if alt_all_perm.has_update {
-        // load "all" permissions if we haven't already
-        if !tried_oper {
-            let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, storage);
-            all_perm = json_may_load(&all_store, owner_slice)?.unwrap_or_else(Vec::new);
-        }
-        // if there was an update to the "all" permissions
-        if alter_perm_list(
-            &mut all_perm,
-            &alt_all_perm,
-            address,
-            &expirations,
-            num_perm_types,
-        ) {
-            let mut all_store = PrefixedStorage::new(PREFIX_ALL_PERMISSIONS, storage);
-            // if deleted last permitted address
-            if all_perm.is_empty() {
-                remove(&mut all_store, owner_slice);
-            } else {
-                json_save(&mut all_store, owner_slice, &all_perm)?;
+        for (i, old_exp) in all_perm.iter().enumerate() {
+            if alt_all_perm.add[i] {
+                // only save if it is a new permission or the expiration changed
+                if *old_exp != Some(expirations[i]) {
+                    set_all_perm(storage, owner, address, i, Some(expirations[i]))?;
+                }
+            } else if alt_all_perm.remove[i] && old_exp.is_some() {
+                set_all_perm(storage, owner, address, i, None)?;
             }
         }
     }
@@ -3029,7 +4882,7 @@ fn process_accesses<S: Storage>(
             num_perm_types,
         ) {
             let mut info_store = PrefixedStorage::new(PREFIX_INFOS, storage);
-            json_save(
+            save_token(
                 &mut info_store,
                 &proc_info.idx.to_le_bytes(),
                 &proc_info.token,
@@ -3038,41 +4891,24 @@ fn process_accesses<S: Storage>(
     }
     // update the owner's AuthLists
     if alt_auth_list.has_update {
-        // get the AuthLists for this address
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, storage);
-        let mut auth_list: Vec<AuthList> =
-            may_load(&auth_store, owner_slice)?.unwrap_or_else(Vec::new);
-        let mut new_auth = AuthList {
-            address: address.clone(),
-            tokens: [Vec::new(), Vec::new(), Vec::new()],
-        };
-        let (auth, found, pos) =
-            if let Some(pos) = auth_list.iter().position(|a| a.address == *address) {
-                if let Some(a) = auth_list.get_mut(pos) {
-                    (a, true, pos)
-                    // shouldn't ever find it but not successfully get it, so this should never happen
-                } else {
-                    (&mut new_auth, false, 0usize)
-                }
-                // didn't find the address in the permission list
-            } else {
-                (&mut new_auth, false, 0usize)
-            };
+        // get the tokens this address is authorized for
+        let mut auth_tokens: Vec<Vec<u32>> = Vec::new();
+        for i in 0..num_perm_types {
+            auth_tokens.push(get_auth_tokens(storage, owner, address, i)?);
+        }
         let mut load_list: HashSet<u32> = HashSet::new();
         // if we need to load other tokens create the load list
         if alt_load_tok_perm.has_update {
             // if we are loading all the owner's other tokens
             if load_all {
-                let owned_store = ReadonlyPrefixedStorage::new(PREFIX_OWNED, storage);
-                let owned: HashSet<u32> =
-                    may_load(&owned_store, owner_slice)?.unwrap_or_else(HashSet::new);
-                load_list.extend(owned);
+                let owned_cnt = get_inventory_len(storage, owner)?;
+                load_list.extend(get_inventory(storage, owner, 0, owned_cnt)?);
                 // above, we already processed the input token, so remove it from the load list
                 load_list.remove(&proc_info.idx);
                 // just loading the tokens in the appropriate AuthList
             } else {
                 for l in add_load_list {
-                    load_list.extend(auth.tokens[l].iter());
+                    load_list.extend(auth_tokens[l].iter());
                 }
                 // don't load the input token if given
                 if proc_info.token_given {
@@ -3082,7 +4918,7 @@ fn process_accesses<S: Storage>(
             let mut info_store = PrefixedStorage::new(PREFIX_INFOS, storage);
             for t_i in &load_list {
                 let tok_key = t_i.to_le_bytes();
-                let may_tok: Option<Token> = json_may_load(&info_store, &tok_key)?;
+                let may_tok = may_load_token(&info_store, &tok_key)?;
                 if let Some(mut load_tok) = may_tok {
                     // shouldn't ever fail this ownership check, but let's be safe
                     if load_tok.owner == *owner
@@ -3094,72 +4930,51 @@ fn process_accesses<S: Storage>(
                         num_perm_types,
                     )
                     {
-                        json_save(&mut info_store, &tok_key, &load_tok)?;
+                        save_token(&mut info_store, &tok_key, &load_tok)?;
                     }
                 }
             }
         }
-        let mut updated = false;
         // do for each PermissionType
-        for i in 0..num_perm_types {
-            // if revoked all individual token permissions
-            if alt_auth_list.clear[i] {
-                if !auth.tokens[i].is_empty() {
-                    auth.tokens[i].clear();
-                    updated = true;
-                }
+        for (i, tokens) in auth_tokens.iter_mut().enumerate() {
+            let updated = if alt_auth_list.clear[i] {
+                // if revoked all individual token permissions
+                let had_tokens = !tokens.is_empty();
+                tokens.clear();
+                had_tokens
                 // else if gave permission to all individual tokens (except the input token)
             } else if alt_auth_list.full[i] {
-                auth.tokens[i] = load_list.iter().copied().collect();
+                *tokens = load_list.iter().copied().collect();
                 // if this was an ApproveToken done to an address with ALL permission
                 // also add the specified token
                 if alt_auth_list.add[i] {
-                    auth.tokens[i].push(proc_info.idx);
+                    tokens.push(proc_info.idx);
                 }
-                updated = true;
+                tokens.sort_unstable();
+                true
                 // else if just adding the input token (shouldn't need the token_given check)
             } else if alt_auth_list.add[i] && proc_info.token_given {
-                if !auth.tokens[i].contains(&proc_info.idx) {
-                    auth.tokens[i].push(proc_info.idx);
-                    updated = true;
+                match tokens.binary_search(&proc_info.idx) {
+                    Ok(_) => false,
+                    Err(tok_pos) => {
+                        tokens.insert(tok_pos, proc_info.idx);
+                        true
+                    }
                 }
                 // else if just revoking perm on the input token (don't need the token_given check)
             } else if alt_auth_list.remove[i] && proc_info.token_given {
-                if let Some(tok_pos) = auth.tokens[i].iter().position(|&t| t == proc_info.idx) {
-                    auth.tokens[i].swap_remove(tok_pos);
-                    updated = true;
-                }
-            }
-        }
-        // if a change was made
-        if updated {
-            let mut auth_store = PrefixedStorage::new(PREFIX_AUTHLIST, storage);
-            let mut save_it = true;
-            // if the address has no authorized tokens
-            if auth.tokens.iter().all(|t| t.is_empty()) {
-                // and it was a pre-existing AuthList
-                if found {
-                    // if it was the only authorized address,
-                    // remove the storage entry
-                    if auth_list.len() == 1 {
-                        remove(&mut auth_store, owner_slice);
-                        save_it = false;
-                    } else {
-                        auth_list.swap_remove(pos);
-                    }
-                    // address had no previous authorization so no need to add it
+                if let Ok(tok_pos) = tokens.binary_search(&proc_info.idx) {
+                    tokens.remove(tok_pos);
+                    true
                 } else {
-                    save_it = false;
+                    false
                 }
-                // AuthList has data, so save it
             } else {
-                // if it is a new address, add it to the list
-                if !found {
-                    auth_list.push(new_auth);
-                }
-            }
-            if save_it {
-                save(&mut auth_store, owner_slice, &auth_list)?;
+                false
+            };
+            // if a change was made
+            if updated {
+                set_auth_tokens(storage, owner, address, i, tokens)?;
             }
         }
     }
@@ -3252,10 +5067,10 @@ pub struct ReceiverInfo {
 pub struct InventoryUpdate {
     // token owner
     pub owner: CanonicalAddr,
-    // the list of remaining tokens
-    pub retain: HashSet<u32>,
     // the list of lost tokens
     pub remove: HashSet<u32>,
+    // the addresses and permission types whose AuthLists may include lost tokens
+    pub auth_lists: Vec<(CanonicalAddr, usize)>,
 }
 
 /// Returns StdResult<()>
@@ -3266,46 +5081,52 @@ pub struct InventoryUpdate {
 ///
 /// * `storage` - a mutable reference to the contract's storage
 /// * `updates` - a slice of an InventoryUpdate list to modify and store new inventories/AuthLists
-/// * `num_perm_types` - the number of permission types
 fn update_owner_inventory<S: Storage>(
     storage: &mut S,
     updates: &[InventoryUpdate],
-    num_perm_types: usize,
 ) -> StdResult<()> {
     for update in updates {
-        let owner_slice = update.owner.as_slice();
         // update the inventories
-        let mut owned_store = PrefixedStorage::new(PREFIX_OWNED, storage);
-        if update.retain.is_empty() {
-            remove(&mut owned_store, owner_slice);
-        } else {
-            save(&mut owned_store, owner_slice, &update.retain)?;
-        }
-        // update the AuthLists if tokens were lost
-        if !update.remove.is_empty() {
-            let mut auth_store = PrefixedStorage::new(PREFIX_AUTHLIST, storage);
-            let may_list: Option<Vec<AuthList>> = may_load(&auth_store, owner_slice)?;
-            if let Some(mut list) = may_list {
-                let mut new_list = Vec::new();
-                for mut auth in list.drain(..) {
-                    for i in 0..num_perm_types {
-                        auth.tokens[i].retain(|t| !update.remove.contains(t));
-                    }
-                    if !auth.tokens.iter().all(|u| u.is_empty()) {
-                        new_list.push(auth)
-                    }
-                }
-                if new_list.is_empty() {
-                    remove(&mut auth_store, owner_slice);
-                } else {
-                    save(&mut auth_store, owner_slice, &new_list)?;
-                }
+        for idx in update.remove.iter() {
+            remove_from_inventory(storage, &update.owner, *idx)?;
+        }
+        // update the AuthLists that may have included the lost tokens
+        for (address, perm_type) in update.auth_lists.iter() {
+            let mut tokens = get_auth_tokens(storage, &update.owner, address, *perm_type)?;
+            let old_len = tokens.len();
+            tokens.retain(|t| !update.remove.contains(t));
+            if tokens.len() != old_len {
+                set_auth_tokens(storage, &update.owner, address, *perm_type, &tokens)?;
             }
         }
     }
     Ok(())
 }
 
+/// records that an owner lost a token, along with the AuthLists that may include it
+///
+/// # Arguments
+///
+/// * `inv` - a mutable reference to the InventoryUpdate of the token's owner
+/// * `token` - a reference to the token
+/// * `idx` - the token's index
+fn log_lost_token(inv: &mut InventoryUpdate, token: &Token, idx: u32) {
+    inv.remove.insert(idx);
+    // every address with a permission for this token has it in an AuthList
+    for perm in token.permissions.iter() {
+        for i in 0..PermissionType::ViewOwner.num_types() {
+            if perm.expirations[i].is_some()
+                && !inv
+                    .auth_lists
+                    .iter()
+                    .any(|(a, t)| *a == perm.address && *t == i)
+            {
+                inv.auth_lists.push((perm.address.clone(), i));
+            }
+        }
+    }
+}
+
 // list of tokens sent from one previous owner
 pub struct SendFrom {
     // the owner's address
@@ -3314,9 +5135,236 @@ pub struct SendFrom {
     pub token_ids: Vec<String>,
 }
 
-/// Returns StdResult<()>
+/// Returns StdResult<Vec<CosmosMsg>>
+///
+/// transfer or sends a list of tokens and returns a list of ReceiveNft callbacks if applicable
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - a reference to the Env of contract's environment
+/// * `config` - a mutable reference to the Config
+/// * `sender` - a reference to the message sender address
+/// * `transfers` - optional list of transfers to perform
+/// * `sends` - optional list of sends to perform
+fn send_list<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: &Env,
+    config: &mut Config,
+    sender: &CanonicalAddr,
+    transfers: Option<Vec<Transfer>>,
+    sends: Option<Vec<Send>>,
+) -> StdResult<Vec<CosmosMsg>> {
+    let mut messages: Vec<CosmosMsg> = Vec::new();
+    let mut oper_for: Vec<CanonicalAddr> = Vec::new();
+    if let Some(xfers) = transfers {
+        for xfer in xfers.into_iter() {
+            let recipient_raw = deps.api.canonical_address(&xfer.recipient)?;
+            for token_id in xfer.token_ids.into_iter() {
+                let _o = transfer_impl(
+                    deps,
+                    &env.block,
+                    config,
+                    sender,
+                    token_id,
+                    recipient_raw.clone(),
+                    xfer.recipient_only.unwrap_or(false),
+                    &mut oper_for,
+                    xfer.memo.clone(),
+                )?;
+            }
+        }
+    } else if let Some(snds) = sends {
+        let mut receivers = Vec::new();
+        for send in snds.into_iter() {
+            let contract_raw = deps.api.canonical_address(&send.contract)?;
+            let mut send_from_list: Vec<SendFrom> = Vec::new();
+            for token_id in send.token_ids.into_iter() {
+                let owner_raw = transfer_impl(
+                    deps,
+                    &env.block,
+                    config,
+                    sender,
+                    token_id.clone(),
+                    contract_raw.clone(),
+                    send.recipient_only.unwrap_or(false),
+                    &mut oper_for,
+                    send.memo.clone(),
+                )?;
+                // compile list of all tokens being sent from each owner in this Send
+                let owner = deps.api.human_address(&owner_raw)?;
+                if let Some(sd_fm) = send_from_list.iter_mut().find(|s| s.owner == owner) {
+                    sd_fm.token_ids.push(token_id);
+                } else {
+                    send_from_list.push(SendFrom {
+                        owner,
+                        token_ids: vec![token_id],
+                    });
+                }
+            }
+            // get BatchReceiveNft and ReceiveNft msgs for all the tokens sent in this Send
+            messages.extend(receiver_callback_msgs(
+                &deps.storage,
+                &send.contract,
+                &contract_raw,
+                send_from_list,
+                &send.msg,
+                &env.message.sender,
+                &mut receivers,
+            )?);
+        }
+    }
+    save(&mut deps.storage, CONFIG_KEY, &config)?;
+    Ok(messages)
+}
+
+/// Returns StdResult<CanonicalAddr>
+///
+/// transfers a token, clears its approvals, and returns the address of the previous owner.
+/// If transfers are not enabled, only sealed tokens may be transferred
+///
+/// # Arguments
+///
+/// * `deps` - a mutable reference to Extern containing all the contract's external dependencies
+/// * `block` - a reference to the current BlockInfo
+/// * `config` - a mutable reference to the Config
+/// * `sender` - a reference to the message sender address
+/// * `token_id` - token id String of token being transferred
+/// * `recipient` - the recipient's address
+/// * `recipient_only` - true if only the recipient may burn the token
+/// * `oper_for` - a mutable reference to a list of owners that gave the sender "all" permission
+/// * `memo` - optional memo for the transfer tx
+#[allow(clippy::too_many_arguments)]
+fn transfer_impl<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    block: &BlockInfo,
+    config: &mut Config,
+    sender: &CanonicalAddr,
+    token_id: String,
+    recipient: CanonicalAddr,
+    recipient_only: bool,
+    oper_for: &mut Vec<CanonicalAddr>,
+    memo: Option<String>,
+) -> StdResult<CanonicalAddr> {
+    let (mut token, idx) = get_token_if_approved(deps, block, &token_id, sender, oper_for, config)?;
+    // throw error if ownership would not change
+    if token.owner == recipient {
+        return Err(ContractError::TransferToOwner { token_id }.into());
+    }
+    // without transfers, a token can only be gifted before it is read
+    if !config.transfers_enabled && token.unwrapped {
+        return Err(ContractError::TransferUnwrapped { token_id }.into());
+    }
+    // move the token to the recipient's inventory and drop the old owner's AuthLists for it
+    let mut inv = InventoryUpdate {
+        owner: token.owner.clone(),
+        remove: HashSet::new(),
+        auth_lists: Vec::new(),
+    };
+    log_lost_token(&mut inv, &token, idx);
+    update_owner_inventory(&mut deps.storage, &[inv])?;
+    remove_from_inbox(&mut deps.storage, &token.owner, idx)?;
+    add_to_inventory(&mut deps.storage, &recipient, idx)?;
+    let old_owner = token.owner;
+    token.owner = recipient.clone();
+    token.permissions.clear();
+    token.recipient_only = recipient_only;
+    // save updated token info
+    let mut info_store = PrefixedStorage::new(PREFIX_INFOS, &mut deps.storage);
+    save_token(&mut info_store, &idx.to_le_bytes(), &token)?;
+
+    let sndr = if old_owner == *sender {
+        None
+    } else {
+        Some(sender.clone())
+    };
+    // store the tx
+    store_transfer(
+        &mut deps.storage,
+        config,
+        block.height,
+        token_id,
+        old_owner.clone(),
+        sndr,
+        recipient,
+        memo,
+    )?;
+    Ok(old_owner)
+}
+
+/// Returns StdResult<Vec<CosmosMsg>>
+///
+/// gets the ReceiveNft or BatchReceiveNft callbacks for the tokens sent to a contract, if it
+/// has registered its code hash
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `contract_human` - a reference to the human address of the contract receiving the tokens
+/// * `contract` - a reference to the canonical address of the contract receiving the tokens
+/// * `send_from_list` - list of SendFroms containing all the owners and their tokens that were sent
+/// * `msg` - a reference to the optional msg used to control ReceiveNft logic
+/// * `sender` - a reference to the address that is sending the tokens
+/// * `receivers` - a mutable reference the list of receiver contracts and their registration info
+fn receiver_callback_msgs<S: ReadonlyStorage>(
+    storage: &S,
+    contract_human: &HumanAddr,
+    contract: &CanonicalAddr,
+    send_from_list: Vec<SendFrom>,
+    msg: &Option<Binary>,
+    sender: &HumanAddr,
+    receivers: &mut Vec<ReceiverInfo>,
+) -> StdResult<Vec<CosmosMsg>> {
+    let registration = if let Some(receiver) = receivers.iter().find(|r| r.contract == *contract) {
+        receiver.registration.clone()
+    } else {
+        let store = ReadonlyPrefixedStorage::new(PREFIX_RECEIVERS, storage);
+        let registration: ReceiveRegistration =
+            may_load(&store, contract.as_slice())?.unwrap_or(ReceiveRegistration {
+                code_hash: String::new(),
+                impl_batch: false,
+            });
+        receivers.push(ReceiverInfo {
+            contract: contract.clone(),
+            registration: registration.clone(),
+        });
+        registration
+    };
+    if registration.code_hash.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut callbacks: Vec<CosmosMsg> = Vec::new();
+    for send_from in send_from_list.into_iter() {
+        // if BatchReceiveNft is implemented, use it
+        if registration.impl_batch {
+            callbacks.push(batch_receive_nft_msg(
+                sender.clone(),
+                send_from.owner,
+                send_from.token_ids,
+                msg.clone(),
+                registration.code_hash.clone(),
+                contract_human.clone(),
+            )?);
+        //otherwise do a bunch of ReceiveNft
+        } else {
+            for token_id in send_from.token_ids.into_iter() {
+                callbacks.push(receive_nft_msg(
+                    send_from.owner.clone(),
+                    token_id,
+                    msg.clone(),
+                    registration.code_hash.clone(),
+                    contract_human.clone(),
+                )?);
+            }
+        }
+    }
+    Ok(callbacks)
+}
+
+/// Returns StdResult<(Vec<TokenData>, Vec<BurnResult>)>
 ///
-/// burns a list of tokens
+/// burns a list of tokens and returns the revealed secrets along with the outcome for each
+/// token id.  Unless doing a best-effort burn, any token that can not be burned will error
 ///
 /// # Arguments
 ///
@@ -3325,68 +5373,92 @@ pub struct SendFrom {
 /// * `config` - a mutable reference to the Config
 /// * `sender` - a reference to the message sender address
 /// * `burns` - list of burns to perform
+/// * `best_effort` - true if tokens that can not be burned should be skipped and reported
 fn burn_list<S: Storage, A: Api, Q: Querier>(
     deps: &mut Extern<S, A, Q>,
     block: &BlockInfo,
     config: &mut Config,
     sender: &CanonicalAddr,
     burns: &mut Vec<Burn>,
-) -> StdResult<Vec<TokenData>> {
+    best_effort: bool,
+) -> StdResult<(Vec<TokenData>, Vec<BurnResult>)> {
     let mut inv_updates: Vec<InventoryUpdate> = Vec::new();
-    let num_perm_types = PermissionType::ViewOwner.num_types();
-    let mut tokens: HashSet<String> =
-        may_load(&deps.storage, TOKENS_KEY)?.unwrap_or_else(HashSet::new);
+    let mut oper_for: Vec<CanonicalAddr> = Vec::new();
+    let mut token_cnt = get_token_count(&deps.storage)?;
     let mut retrieved_data: Vec<TokenData> = vec![];
+    let mut results: Vec<BurnResult> = Vec::new();
     for burn in burns.drain(..) {
         for token_id in burn.token_ids.into_iter() {
-            let (token, idx) = get_token_if_permitted(
-                deps,
-                &token_id,
-                &config,
-            )?;
-
-            let public_metadata = get_public_metadata(
-                &deps.storage,
-                &token_id,
-            ).unwrap();
-
-            let mut token_data = TokenData::from_public_metadata(
-                &token_id,
-                public_metadata,
-            );
-
-            let private_metadata = get_private_metadata(
-                &deps.storage,
-                &token_id,
-            );
-
-            // if private_metadata.is_err() {
-            //     panic!("{}", private_metadata.err())
-            // }
+            let permitted = if config.burn_reveals_secret {
+                get_token_if_permitted(deps, &token_id, config)
+            } else {
+                get_token_if_approved(deps, block, &token_id, sender, &mut oper_for, config)
+            };
+            // recipient-restricted tokens can only be burned by their owner
+            let permitted = permitted.and_then(|(token, idx)| {
+                if token.recipient_only && token.owner != *sender {
+                    return Err(token_error(
+                        config.token_supply_is_public,
+                        ContractError::NotTokenOwner {
+                            token_id: token_id.clone(),
+                        },
+                    ));
+                }
+                Ok((token, idx))
+            });
+            let (token, idx) = match permitted {
+                Ok(found) => found,
+                Err(err) => {
+                    if !best_effort {
+                        return Err(err);
+                    }
+                    // report the cause of the failure.  If token supply is private, every
+                    // cause was already hidden as token_unavailable
+                    let outcome = match ContractError::code_of(&err).as_deref() {
+                        Some("token_sealed") => BurnOutcome::Sealed,
+                        Some("token_not_found") => BurnOutcome::NotFound,
+                        Some("token_unavailable")
+                        | Some("token_not_owner")
+                        | Some("token_unauthorized")
+                        | Some("token_access_expired") => BurnOutcome::NotPermitted,
+                        _ => return Err(err),
+                    };
+                    results.push(BurnResult { token_id, outcome });
+                    continue;
+                }
+            };
 
-            token_data.update_with_private_metadata(private_metadata.unwrap());
-            retrieved_data.push(token_data);
+            let outcome = if config.burn_reveals_secret {
+                let public_metadata = get_public_metadata(&deps.storage, &token_id)?;
+                let mut token_data = TokenData::from_public_metadata(&token_id, public_metadata)?;
+                let private_metadata = get_private_metadata(&deps.storage, &token_id)?;
+                token_data.update_with_private_metadata(private_metadata)?;
+                retrieved_data.push(token_data);
+                BurnOutcome::Revealed
+            } else {
+                BurnOutcome::Burned
+            };
+            results.push(BurnResult {
+                token_id: token_id.clone(),
+                outcome,
+            });
 
             // log the inventory change
-            if let Some(inv) = inv_updates.iter_mut().find(|i| i.owner == token.owner) {
-                inv.retain.remove(&idx);
-                inv.remove.insert(idx);
-            } else {
-                let owned_store = ReadonlyPrefixedStorage::new(PREFIX_OWNED, &deps.storage);
-                let retain: HashSet<u32> =
-                    may_load(&owned_store, token.owner.as_slice())?.unwrap_or_else(HashSet::new);
-                let mut new_inv = InventoryUpdate {
-                    owner: token.owner.clone(),
-                    retain,
-                    remove: HashSet::new(),
-                };
-                new_inv.retain.remove(&idx);
-                new_inv.remove.insert(idx);
-                inv_updates.push(new_inv);
-            }
+            let inv_pos = match inv_updates.iter().position(|i| i.owner == token.owner) {
+                Some(pos) => pos,
+                None => {
+                    inv_updates.push(InventoryUpdate {
+                        owner: token.owner.clone(),
+                        remove: HashSet::new(),
+                        auth_lists: Vec::new(),
+                    });
+                    inv_updates.len() - 1
+                }
+            };
+            log_lost_token(&mut inv_updates[inv_pos], &token, idx);
             let token_key = idx.to_le_bytes();
-            // remove from token list and maps
-            tokens.remove(&token_id);
+            // remove from token count and maps
+            token_cnt = token_cnt.saturating_sub(1);
             let mut map2idx = PrefixedStorage::new(PREFIX_MAP_TO_INDEX, &mut deps.storage);
             remove(&mut map2idx, token_id.as_bytes());
             let mut map2id = PrefixedStorage::new(PREFIX_MAP_TO_ID, &mut deps.storage);
@@ -3399,6 +5471,9 @@ fn burn_list<S: Storage, A: Api, Q: Querier>(
             remove(&mut pub_store, &token_key);
             let mut priv_store = PrefixedStorage::new(PREFIX_PRIV_META, &mut deps.storage);
             remove(&mut priv_store, &token_key);
+            let mut mint_store = PrefixedStorage::new(PREFIX_MINT_INFO, &mut deps.storage);
+            remove(&mut mint_store, &token_key);
+            remove_from_inbox(&mut deps.storage, &token.owner, idx)?;
             let brnr = if token.owner == *sender {
                 None
             } else {
@@ -3417,17 +5492,25 @@ fn burn_list<S: Storage, A: Api, Q: Querier>(
         }
     }
     save(&mut deps.storage, CONFIG_KEY, &config)?;
-    save(&mut deps.storage, TOKENS_KEY, &tokens)?;
-    update_owner_inventory(&mut deps.storage, &inv_updates, num_perm_types)?;
-    Ok(retrieved_data)
-}
-
-// an owner and their list of tokens
-pub struct Inventory {
-    // the owner's address
-    pub owner: CanonicalAddr,
-    // the owner's tokens
-    pub tokens: HashSet<u32>,
+    set_token_count(&mut deps.storage, token_cnt)?;
+    update_owner_inventory(&mut deps.storage, &inv_updates)?;
+    // keep a copy of the secrets in case the burner never receives the response
+    if let Some(window) = config.retrieval_window {
+        if !retrieved_data.is_empty() {
+            let mut retrieved_store = PrefixedStorage::new(PREFIX_RETRIEVED, &mut deps.storage);
+            let mut retained: Vec<RetrievedSecret> =
+                may_load(&retrieved_store, sender.as_slice())?.unwrap_or_else(Vec::new);
+            // purge any expired secrets while we are here
+            retained.retain(|s| s.expires_at > block.height);
+            let expires_at = block.height.saturating_add(window);
+            retained.extend(retrieved_data.iter().map(|secret| RetrievedSecret {
+                secret: secret.clone(),
+                expires_at,
+            }));
+            save(&mut retrieved_store, sender.as_slice(), &retained)?;
+        }
+    }
+    Ok((retrieved_data, results))
 }
 
 /// Returns <Vec<String>>
@@ -3441,30 +5524,42 @@ pub struct Inventory {
 /// * `config` - a mutable reference to the Config
 /// * `sender_raw` - a reference to the message sender address
 /// * `mints` - list of mints to perform
+/// * `authorized` - true if the sender was already authorized to mint these tokens, even if
+///   it is not a minter
 fn mint_list<S: Storage, A: Api, Q: Querier>(
     deps: &mut Extern<S, A, Q>,
     block: &BlockInfo,
     config: &mut Config,
     sender_raw: &CanonicalAddr,
     mints: &mut Vec<Mint>,
+    authorized: bool,
 ) -> StdResult<Vec<String>> {
-    let mut tokens: HashSet<String> =
-        may_load(&deps.storage, TOKENS_KEY)?.unwrap_or_else(HashSet::new);
-    let mut inventories: Vec<Inventory> = Vec::new();
+    if !config.open_minting && !authorized {
+        let minters: Vec<CanonicalAddr> =
+            may_load(&deps.storage, MINTERS_KEY)?.unwrap_or_else(Vec::new);
+        if !minters.contains(sender_raw) {
+            return Err(ContractError::MinterOnly.into());
+        }
+    }
+    let mut token_cnt = get_token_count(&deps.storage)?;
     let mut minted: Vec<String> = Vec::new();
-    let sender_human = deps.api.human_address(sender_raw).unwrap();
+    let sender_human = deps.api.human_address(sender_raw)?;
     for mint in mints.drain(..) {
         let id = mint.token_id.unwrap_or(format!("{}", config.mint_cnt));
-        if tokens.contains(&id) {
-            return Err(StdError::generic_err(format!(
-                "Token ID {} is already in use",
-                id
-            )));
-        }
-        let recipient = if mint.owner.is_none() || mint.owner.unwrap() == sender_human {
-            sender_raw.clone()
-        } else {
-            return Err(StdError::generic_err("You can only mint tokens for yourself"));
+        let map2idx = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_INDEX, &deps.storage);
+        let may_exist: Option<u32> = may_load(&map2idx, id.as_bytes())?;
+        if may_exist.is_some() {
+            return Err(ContractError::TokenIdInUse { token_id: id }.into());
+        }
+        // only designated minters may mint on behalf of others
+        let recipient = match mint.owner {
+            Some(owner) if owner != sender_human => {
+                if config.open_minting && !authorized {
+                    return Err(ContractError::MintForOthers.into());
+                }
+                deps.api.canonical_address(&owner)?
+            }
+            _ => sender_raw.clone(),
         };
 
         // if you are modifying the base contract to include other data fields on-chain in
@@ -3476,6 +5571,8 @@ fn mint_list<S: Storage, A: Api, Q: Querier>(
             owner: recipient.clone(),
             permissions: Vec::new(),
             unwrapped: !config.sealed_metadata_is_enabled,
+            public_teaser: mint.public_teaser.unwrap_or(false),
+            recipient_only: false,
         };
         //
         //
@@ -3483,31 +5580,28 @@ fn mint_list<S: Storage, A: Api, Q: Querier>(
         // save new token info
         let token_key = config.mint_cnt.to_le_bytes();
         let mut info_store = PrefixedStorage::new(PREFIX_INFOS, &mut deps.storage);
-        json_save(&mut info_store, &token_key, &token)?;
-        // add token to owner's list
-        let mut new_inv = Inventory {
-            owner: token.owner.clone(),
-            tokens: HashSet::new(),
-        };
-        let (inventory, found) =
-            if let Some(inv) = inventories.iter_mut().find(|i| i.owner == new_inv.owner) {
-                (inv, true)
-            } else {
-                let owned_store = ReadonlyPrefixedStorage::new(PREFIX_OWNED, &deps.storage);
-                new_inv.tokens =
-                    may_load(&owned_store, new_inv.owner.as_slice())?.unwrap_or_else(HashSet::new);
-                (&mut new_inv, false)
-            };
-        inventory.tokens.insert(config.mint_cnt);
-        if !found {
-            inventories.push(new_inv);
+        save_token(&mut info_store, &token_key, &token)?;
+        // add token to owner's inventory
+        add_to_inventory(&mut deps.storage, &token.owner, config.mint_cnt)?;
+        // let the recipient know a secret was minted for them
+        if token.owner != *sender_raw {
+            add_to_inbox(&mut deps.storage, &token.owner, config.mint_cnt)?;
         }
-        // add to token list, id and index maps
-        tokens.insert(id.clone());
+        // add to token count, id and index maps
+        token_cnt += 1;
         let mut map2idx = PrefixedStorage::new(PREFIX_MAP_TO_INDEX, &mut deps.storage);
         save(&mut map2idx, id.as_bytes(), &config.mint_cnt)?;
         let mut map2id = PrefixedStorage::new(PREFIX_MAP_TO_ID, &mut deps.storage);
         save(&mut map2id, &token_key, &id)?;
+        let mut mint_store = PrefixedStorage::new(PREFIX_MINT_INFO, &mut deps.storage);
+        save(
+            &mut mint_store,
+            &token_key,
+            &MintInfo {
+                creator: sender_raw.clone(),
+                height: block.height,
+            },
+        )?;
 
         //
         // If you wanted to store an additional data struct for each NFT, you would create
@@ -3519,6 +5613,7 @@ fn mint_list<S: Storage, A: Api, Q: Querier>(
             save(&mut pub_store, &token_key, &pub_meta)?;
         }
         if let Some(priv_meta) = mint.private_metadata {
+            check_secret_size(config, &priv_meta)?;
             let mut priv_store = PrefixedStorage::new(PREFIX_PRIV_META, &mut deps.storage);
             save(&mut priv_store, &token_key, &priv_meta)?;
         }
@@ -3539,16 +5634,7 @@ fn mint_list<S: Storage, A: Api, Q: Querier>(
         // increment index for next mint
         config.mint_cnt += 1;
     }
-    // save all the updated owners' token lists
-    let mut owned_store = PrefixedStorage::new(PREFIX_OWNED, &mut deps.storage);
-    for inventory in inventories {
-        save(
-            &mut owned_store,
-            inventory.owner.as_slice(),
-            &inventory.tokens,
-        )?;
-    }
-    save(&mut deps.storage, TOKENS_KEY, &tokens)?;
+    set_token_count(&mut deps.storage, token_cnt)?;
     save(&mut deps.storage, CONFIG_KEY, &config)?;
     Ok(minted)
 }
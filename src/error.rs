@@ -0,0 +1,256 @@
+use cosmwasm_std::{from_slice, to_vec, StdError};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+
+/// every failure the contract reports.  A ContractError is returned as a generic StdError
+/// whose message is the JSON of an ErrorPayload, so clients can switch on its stable code
+#[derive(Snafu, Clone, Debug, PartialEq)]
+pub enum ContractError {
+    #[snafu(display("This is an admin command and can only be run from the admin address"))]
+    AdminOnly,
+    #[snafu(display("The contract admin has temporarily disabled this action"))]
+    ContractDisabled,
+    #[snafu(display("Wrong viewing key for this address or viewing key not set"))]
+    WrongViewingKey,
+    #[snafu(display("Viewing keys cannot be set, they must be generated"))]
+    SetViewingKeyUnsupported,
+    #[snafu(display("Minting is not restricted, minter list not supported"))]
+    MintersUnsupported,
+    #[snafu(display("Token transfer not permitted"))]
+    TransferUnsupported,
+    #[snafu(display("Token transfer not permitted. ReceiveNft registration not supported."))]
+    ReceiveUnsupported,
+    #[snafu(display("Tokens cannot be queried"))]
+    TokensQueryUnsupported,
+    #[snafu(display("You can only mint tokens for yourself"))]
+    MintForOthers,
+    #[snafu(display("Only designated minters are allowed to mint"))]
+    MinterOnly,
+    #[snafu(display("Token ID {} is already in use", token_id))]
+    TokenIdInUse { token_id: String },
+    #[snafu(display("Unable to generate an unused token ID, please try again"))]
+    TokenIdCollision,
+    #[snafu(display("Sealed metadata functionality is not enabled for this contract"))]
+    SealedMetadataDisabled,
+    #[snafu(display("This token has already been unwrapped"))]
+    AlreadyUnwrapped,
+    #[snafu(display("Burn functionality is not enabled for this token"))]
+    BurnDisabled,
+    #[snafu(display("The token supply of this contract is private"))]
+    PrivateSupply,
+    #[snafu(display("Private metadata can not be larger than {} bytes", max_size))]
+    SecretTooLarge { max_size: u32 },
+    #[snafu(display("Secret padding bucket and max secret size must be greater than zero"))]
+    InvalidSecretPadding,
+    #[snafu(display(
+        "Attempted to grant/revoke permission for a token, but did not specify a token ID"
+    ))]
+    MissingTokenId,
+    #[snafu(display("Can not revoke transfer permission from an existing operator"))]
+    RevokeOperator,
+    #[snafu(display(
+        "Token ID: {} is not in the list of tokens you are permitted to view",
+        token_id
+    ))]
+    InvalidCursor { token_id: String },
+    #[snafu(display("Token must be burned to retrieve metadata."))]
+    NotBurned,
+    #[snafu(display("Permit does not apply to this contract"))]
+    PermitWrongContract,
+    #[snafu(display("Permit {} was revoked", permit_name))]
+    PermitRevoked { permit_name: String },
+    #[snafu(display("Failed to verify the signature of the permit"))]
+    PermitInvalidSignature,
+    #[snafu(display("Permit does not grant permission for this query"))]
+    PermitUnauthorized,
+    #[snafu(display("A viewing key named {} already exists", name))]
+    ScopedKeyExists { name: String },
+    #[snafu(display("There is no viewing key named {}", name))]
+    ScopedKeyNotFound { name: String },
+    #[snafu(display("A scoped viewing key must allow at least one query"))]
+    ScopedKeyEmpty,
+    #[snafu(display("Viewing key {} has expired", name))]
+    ScopedKeyExpired { name: String },
+    #[snafu(display("Viewing key {} does not grant access to this query", name))]
+    ScopedKeyUnauthorized { name: String },
+    #[snafu(display(
+        "Secret request {} does not exist or the code is incorrect",
+        request_id
+    ))]
+    SecretRequestInvalid { request_id: u32 },
+    #[snafu(display("Secret request {} may only be fulfilled by its responder", request_id))]
+    SecretRequestResponderOnly { request_id: u32 },
+    #[snafu(display("Secret request {} was already fulfilled", request_id))]
+    SecretRequestFulfilled { request_id: u32 },
+    #[snafu(display("Secret request {} has expired", request_id))]
+    SecretRequestExpired { request_id: u32 },
+    #[snafu(display(
+        "Attempting to transfer token ID: {} to the address that already owns it",
+        token_id
+    ))]
+    TransferToOwner { token_id: String },
+    #[snafu(display(
+        "Token {} has been unwrapped and can no longer be transferred",
+        token_id
+    ))]
+    TransferUnwrapped { token_id: String },
+    #[snafu(display("Unable to find token info for {}", token_id))]
+    MissingTokenInfo { token_id: String },
+    #[snafu(display("Unable to read the {} of token {}", kind, token_id))]
+    UnreadableMetadata { token_id: String, kind: String },
+    #[snafu(display("Token ID: {} not found", token_id))]
+    TokenNotFound { token_id: String },
+    #[snafu(display("You do not own token {}", token_id))]
+    NotTokenOwner { token_id: String },
+    #[snafu(display("{}", detail))]
+    TokenUnauthorized { token_id: String, detail: String },
+    #[snafu(display("{}", detail))]
+    TokenSealed { token_id: String, detail: String },
+    #[snafu(display("{}", detail))]
+    TokenAccessExpired { token_id: String, detail: String },
+    #[snafu(display(
+        "Token {} does not exist or you are not authorized to access it",
+        token_id
+    ))]
+    TokenUnavailable { token_id: String },
+}
+
+/// the message of every generic StdError created from a ContractError
+#[derive(Serialize, Deserialize)]
+pub struct ErrorPayload {
+    /// stable code identifying the failure
+    pub code: String,
+    /// human readable description
+    pub msg: String,
+}
+
+impl ContractError {
+    /// Returns &'static str of the stable code clients can switch on
+    pub fn code(&self) -> &'static str {
+        match self {
+            ContractError::AdminOnly => "admin_only",
+            ContractError::ContractDisabled => "contract_disabled",
+            ContractError::WrongViewingKey => "wrong_viewing_key",
+            ContractError::SetViewingKeyUnsupported => "set_viewing_key_unsupported",
+            ContractError::MintersUnsupported => "minters_unsupported",
+            ContractError::TransferUnsupported => "transfer_unsupported",
+            ContractError::ReceiveUnsupported => "receive_unsupported",
+            ContractError::TokensQueryUnsupported => "tokens_query_unsupported",
+            ContractError::MintForOthers => "mint_for_others",
+            ContractError::MinterOnly => "minter_only",
+            ContractError::TokenIdInUse { .. } => "token_id_in_use",
+            ContractError::TokenIdCollision => "token_id_collision",
+            ContractError::SealedMetadataDisabled => "sealed_metadata_disabled",
+            ContractError::AlreadyUnwrapped => "already_unwrapped",
+            ContractError::BurnDisabled => "burn_disabled",
+            ContractError::PrivateSupply => "private_supply",
+            ContractError::SecretTooLarge { .. } => "secret_too_large",
+            ContractError::InvalidSecretPadding => "invalid_secret_padding",
+            ContractError::MissingTokenId => "missing_token_id",
+            ContractError::RevokeOperator => "revoke_operator",
+            ContractError::InvalidCursor { .. } => "invalid_cursor",
+            ContractError::NotBurned => "not_burned",
+            ContractError::PermitWrongContract => "permit_wrong_contract",
+            ContractError::PermitRevoked { .. } => "permit_revoked",
+            ContractError::PermitInvalidSignature => "permit_invalid_signature",
+            ContractError::PermitUnauthorized => "permit_unauthorized",
+            ContractError::ScopedKeyExists { .. } => "scoped_key_exists",
+            ContractError::ScopedKeyNotFound { .. } => "scoped_key_not_found",
+            ContractError::ScopedKeyEmpty => "scoped_key_empty",
+            ContractError::ScopedKeyExpired { .. } => "scoped_key_expired",
+            ContractError::ScopedKeyUnauthorized { .. } => "scoped_key_unauthorized",
+            ContractError::SecretRequestInvalid { .. } => "secret_request_invalid",
+            ContractError::SecretRequestResponderOnly { .. } => "secret_request_responder_only",
+            ContractError::SecretRequestFulfilled { .. } => "secret_request_fulfilled",
+            ContractError::SecretRequestExpired { .. } => "secret_request_expired",
+            ContractError::TransferToOwner { .. } => "transfer_to_owner",
+            ContractError::TransferUnwrapped { .. } => "transfer_unwrapped",
+            ContractError::MissingTokenInfo { .. } => "missing_token_info",
+            ContractError::UnreadableMetadata { .. } => "unreadable_metadata",
+            ContractError::TokenNotFound { .. } => "token_not_found",
+            ContractError::NotTokenOwner { .. } => "token_not_owner",
+            ContractError::TokenUnauthorized { .. } => "token_unauthorized",
+            ContractError::TokenSealed { .. } => "token_sealed",
+            ContractError::TokenAccessExpired { .. } => "token_access_expired",
+            ContractError::TokenUnavailable { .. } => "token_unavailable",
+        }
+    }
+
+    /// Returns ContractError that does not reveal why an operation on a token failed if the
+    /// token supply is private, so the caller can not learn whether the token exists, is
+    /// sealed, belongs to someone else, or their access expired
+    ///
+    /// # Arguments
+    ///
+    /// * `supply_is_public` - true if the token supply of the contract is public
+    pub fn hide_token(self, supply_is_public: bool) -> Self {
+        if supply_is_public {
+            return self;
+        }
+        match self {
+            ContractError::TokenNotFound { token_id }
+            | ContractError::NotTokenOwner { token_id }
+            | ContractError::TokenUnauthorized { token_id, .. }
+            | ContractError::TokenSealed { token_id, .. }
+            | ContractError::TokenAccessExpired { token_id, .. } => {
+                ContractError::TokenUnavailable { token_id }
+            }
+            other => other,
+        }
+    }
+
+    /// Returns String of the JSON ErrorPayload
+    pub fn payload(&self) -> String {
+        let payload = ErrorPayload {
+            code: self.code().to_string(),
+            msg: self.to_string(),
+        };
+        to_vec(&payload)
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .unwrap_or(payload.msg)
+    }
+
+    /// Returns Option<String> of the code of an error created from a ContractError
+    ///
+    /// # Arguments
+    ///
+    /// * `err` - a reference to the error
+    pub fn code_of(err: &StdError) -> Option<String> {
+        if let StdError::GenericErr { msg, .. } = err {
+            return from_slice::<ErrorPayload>(msg.as_bytes())
+                .ok()
+                .map(|payload| payload.code);
+        }
+        None
+    }
+
+    /// Returns String of the human readable description in an error's message, without the
+    /// ErrorPayload wrapping if the error was created from a ContractError
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - the message of a generic StdError
+    pub fn msg_of(msg: String) -> String {
+        from_slice::<ErrorPayload>(msg.as_bytes())
+            .map(|payload| payload.msg)
+            .unwrap_or(msg)
+    }
+}
+
+impl From<ContractError> for StdError {
+    fn from(err: ContractError) -> Self {
+        StdError::generic_err(err.payload())
+    }
+}
+
+/// Returns StdError for a failed operation on a specific token, hiding the reason if the
+/// token supply is private
+///
+/// # Arguments
+///
+/// * `supply_is_public` - true if the token supply of the contract is public
+/// * `err` - the reason the operation failed
+pub fn token_error(supply_is_public: bool, err: ContractError) -> StdError {
+    err.hide_token(supply_is_public).into()
+}
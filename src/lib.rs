@@ -1,7 +1,9 @@
 //#![allow(clippy::field_reassign_with_default)]
 pub mod contract;
+pub mod error;
 pub mod expiration;
 pub mod msg;
+pub mod permit;
 mod rand;
 pub mod receiver;
 pub mod state;
@@ -1,9 +1,11 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Binary, Coin, HumanAddr};
+use cosmwasm_std::{Binary, Coin, HumanAddr, StdResult};
 
+use crate::error::ContractError;
 use crate::expiration::Expiration;
+use crate::permit::Permit;
 use crate::token::Metadata;
 
 /// Instantiation message
@@ -63,6 +65,71 @@ pub struct InitConfig {
     /// Indicates whether burn functionality should be enabled
     /// default: False
     pub enable_burn: Option<bool>,
+    /// optional number of blocks a burner can still retrieve the secrets revealed by their
+    /// burns with the RetrievedSecrets query.  If not specified, revealed secrets are only
+    /// returned in the burn's response
+    /// default: None
+    pub retrieval_window: Option<u64>,
+    /// optional limits on how much transaction history is kept for each address
+    /// default: keep all transaction history
+    pub tx_retention: Option<TxRetention>,
+    /// indicates whether stored transactions should hold a keyed hash of the token ID instead
+    /// of the ID, and encrypt memos so only the token owner can view them.  Memo authors who
+    /// are not the token owner can not read their own memos back, and burns by an approved
+    /// address only appear in the previous owner's history, without the burner's address
+    /// default: False
+    pub private_tx_records: Option<bool>,
+    /// optional padding buckets that hide the size of the secrets returned by burns and private
+    /// metadata queries, along with the max size of a token's private metadata
+    /// default: None
+    pub secret_padding: Option<SecretPadding>,
+    /// indicates whether tokens can be transferred and sent, and whether contracts can
+    /// register to receive them
+    /// default: False
+    pub transfers_enabled: Option<bool>,
+    /// indicates whether sealed tokens can be transferred and sent as gifts, even if transfers
+    /// are not enabled.  Once a token is unwrapped, it can no longer be gifted.  This config
+    /// value is ignored if sealed metadata is not enabled
+    /// default: False
+    pub gifting_enabled: Option<bool>,
+    /// indicates whether anyone may mint tokens for themselves.  If false, only the
+    /// addresses in the admin-controlled minter list may mint, and they may mint tokens on
+    /// behalf of other addresses
+    /// default: True
+    pub open_minting: Option<bool>,
+    /// indicates whether the AllTokens query is supported, NftInfo displays a token's public
+    /// metadata even if its creator did not make it a teaser, and PrivateMetadata displays
+    /// the private metadata to permitted viewers
+    /// default: False
+    pub metadata_queries_enabled: Option<bool>,
+    /// indicates whether burning a token reveals its secret to the burner.  If true, anyone
+    /// may burn a token whose metadata is not sealed.  If false, only the owner or an address
+    /// with transfer approval may burn a token, and its metadata is destroyed unread
+    /// default: True
+    pub burn_reveals_secret: Option<bool>,
+}
+
+/// limits on how much transaction history is kept for each address.  Txs outside the limits
+/// are no longer displayed, and are deleted from storage once every address involved has
+/// pruned them
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Default, PartialEq, Debug)]
+pub struct TxRetention {
+    /// optional max number of txs kept for each address.  0 keeps no transaction history
+    pub max_txs: Option<u32>,
+    /// optional number of blocks a tx is kept
+    pub max_blocks: Option<u64>,
+}
+
+/// size buckets used to pad responses that contain secrets.  A response is padded to the
+/// smallest bucket of min_bucket times a power of two that can hold it, so observers only learn
+/// which bucket a secret falls in.  Clients should also use the padding field of mint and
+/// SetPrivateMetadata messages, because the contract can not pad the messages it receives
+#[derive(Serialize, Deserialize, JsonSchema, Clone, PartialEq, Debug)]
+pub struct SecretPadding {
+    /// size in bytes of the smallest bucket
+    pub min_bucket: u32,
+    /// max size in bytes of a token's serialized private metadata
+    pub max_secret_size: u32,
 }
 
 impl Default for InitConfig {
@@ -75,6 +142,15 @@ impl Default for InitConfig {
             minter_may_update_metadata: Some(true),
             owner_may_update_metadata: Some(false),
             enable_burn: Some(false),
+            retrieval_window: None,
+            tx_retention: None,
+            private_tx_records: Some(false),
+            secret_padding: None,
+            transfers_enabled: Some(false),
+            gifting_enabled: Some(false),
+            open_minting: Some(true),
+            metadata_queries_enabled: Some(false),
+            burn_reveals_secret: Some(true),
         }
     }
 }
@@ -99,12 +175,16 @@ pub enum HandleMsg {
     MintNft {
         /// optional token id. if omitted, use current token index
         token_id: Option<String>,
-        /// optional owner address. if omitted, owned by the message sender
+        /// optional owner address. if omitted, owned by the message sender.  Only designated
+        /// minters may mint tokens owned by another address
         owner: Option<HumanAddr>,
         /// optional public metadata that can be seen by everyone
         public_metadata: Option<Metadata>,
         /// optional private metadata that can only be seen by the owner and whitelist
         private_metadata: Option<Metadata>,
+        /// optionally true if the public metadata can be viewed with NftInfo before the
+        /// token is burned.  Defaults to false if not specified
+        public_teaser: Option<bool>,
         /// optional memo for the tx
         memo: Option<String>,
         /// optional message length padding
@@ -117,6 +197,31 @@ pub enum HandleMsg {
         /// optional message length padding
         padding: Option<String>,
     },
+    /// ask for a secret to be minted for the sender.  Returns a one-time code the sender
+    /// gives to whoever should fulfill the request
+    CreateSecretRequest {
+        /// description of the requested secret
+        description: String,
+        /// optional address that must fulfill the request.  If omitted, anyone with the code
+        /// may fulfill it
+        responder: Option<HumanAddr>,
+        /// optional expiration of the request.  Defaults to never
+        expires: Option<Expiration>,
+        /// optional message length padding
+        padding: Option<String>,
+    },
+    /// mint the requested secret for the requester.  The responder does not need to be a
+    /// minter
+    FulfillSecretRequest {
+        /// id of the request to fulfill
+        request_id: u32,
+        /// the request's one-time code
+        code: String,
+        /// the secret
+        private_metadata: Metadata,
+        /// optional message length padding
+        padding: Option<String>,
+    },
     /// set the public metadata.  This can be called by either the token owner or a valid minter
     /// if they have been given this power by the appropriate config values
     SetPublicMetadata {
@@ -144,6 +249,14 @@ pub enum HandleMsg {
         /// optional message length padding
         padding: Option<String>,
     },
+    /// reissue a token under a fresh, randomly generated ID.  Only the owner may rotate a
+    /// token.  The token's info and metadata are kept, but the old ID will no longer resolve
+    RotateTokenLink {
+        /// id of the token to rotate
+        token_id: String,
+        /// optional message length padding
+        padding: Option<String>,
+    },
     /// if a contract was instantiated to make ownership public by default, this will allow
     /// an address to make the ownership of their tokens private.  The address can still use
     /// SetGlobalApproval to make ownership public either inventory-wide or for a specific token
@@ -225,12 +338,15 @@ pub enum HandleMsg {
         /// optional message length padding
         padding: Option<String>,
     },
-    /// transfer a token
+    /// transfer a token.  If transfers are not enabled, only sealed tokens can be
+    /// transferred
     TransferNft {
         /// recipient of the transfer
         recipient: HumanAddr,
         /// id of the token to transfer
         token_id: String,
+        /// optionally true if only the recipient may burn the token.  Defaults to false
+        recipient_only: Option<bool>,
         /// optional memo for the tx
         memo: Option<String>,
         /// optional message length padding
@@ -243,7 +359,8 @@ pub enum HandleMsg {
         /// optional message length padding
         padding: Option<String>,
     },
-    /// send a token and call receiving contract's (Batch)ReceiveNft
+    /// send a token and call receiving contract's (Batch)ReceiveNft.  If transfers are not
+    /// enabled, only sealed tokens can be sent
     SendNft {
         /// address to send the token to
         contract: HumanAddr,
@@ -251,6 +368,9 @@ pub enum HandleMsg {
         token_id: String,
         /// optional message to send with the (Batch)RecieveNft callback
         msg: Option<Binary>,
+        /// optionally true if only the receiving contract may burn the token.  Defaults to
+        /// false
+        recipient_only: Option<bool>,
         /// optional memo for the tx
         memo: Option<String>,
         /// optional message length padding
@@ -276,6 +396,24 @@ pub enum HandleMsg {
     BatchBurnNft {
         /// list of burns to perform
         burns: Vec<Burn>,
+        /// optionally true if tokens that can not be burned should be skipped and reported
+        /// instead of failing the whole batch.  Defaults to false if not specified
+        best_effort: Option<bool>,
+        /// optional message length padding
+        padding: Option<String>,
+    },
+    /// remove any secrets retained from the message sender's burns before the retrieval
+    /// window expires
+    ForgetRetrieved {
+        /// optional message length padding
+        padding: Option<String>,
+    },
+    /// delete the message sender's transaction history that is outside the contract's
+    /// retention limits.  The history is deleted from storage once every address involved
+    /// has pruned it
+    PruneHistory {
+        /// optionally only keep this many of the sender's most recent txs
+        keep: Option<u32>,
         /// optional message length padding
         padding: Option<String>,
     },
@@ -306,21 +444,55 @@ pub enum HandleMsg {
         /// optional message length padding
         padding: Option<String>,
     },
-    /// add addresses with minting authority
+    /// revoke a query permit the sender signed, so it can no longer authenticate queries
+    RevokePermit {
+        /// name of the permit to revoke
+        permit_name: String,
+        /// optional message length padding
+        padding: Option<String>,
+    },
+    /// create an additional named viewing key that only authenticates the listed queries,
+    /// optionally only for the listed tokens, until it expires
+    CreateScopedKey {
+        /// name used to list and revoke the key
+        name: String,
+        /// entropy String used in random key generation
+        entropy: String,
+        /// queries the key may be used for
+        queries: Vec<ScopedQuery>,
+        /// optional list of the only tokens the key may be used for.  If given, the key
+        /// can not be used for queries that are not about specific tokens
+        token_ids: Option<Vec<String>>,
+        /// optional expiration of the key.  Defaults to never
+        expires: Option<Expiration>,
+        /// optional message length padding
+        padding: Option<String>,
+    },
+    /// revoke a scoped viewing key the sender created
+    RevokeScopedKey {
+        /// name of the key to revoke
+        name: String,
+        /// optional message length padding
+        padding: Option<String>,
+    },
+    /// add addresses with minting authority.  Only supported if minting is restricted to
+    /// the minter list
     AddMinters {
         /// list of addresses that can now mint
         minters: Vec<HumanAddr>,
         /// optional message length padding
         padding: Option<String>,
     },
-    /// revoke minting authority from addresses
+    /// revoke minting authority from addresses.  Only supported if minting is restricted to
+    /// the minter list
     RemoveMinters {
         /// list of addresses no longer allowed to mint
         minters: Vec<HumanAddr>,
         /// optional message length padding
         padding: Option<String>,
     },
-    /// define list of addresses with minting authority
+    /// define list of addresses with minting authority.  Only supported if minting is
+    /// restricted to the minter list
     SetMinters {
         /// list of addresses with minting authority
         minters: Vec<HumanAddr>,
@@ -334,6 +506,13 @@ pub enum HandleMsg {
         /// optional message length padding
         padding: Option<String>,
     },
+    /// set the limits on how much transaction history is kept for each address
+    SetTxRetention {
+        /// new retention limits
+        retention: TxRetention,
+        /// optional message length padding
+        padding: Option<String>,
+    },
     /// set contract status level to determine which functions are allowed.  StopTransactions
     /// status prevent mints, burns, sends, and transfers, but allows all other functions
     SetContractStatus {
@@ -342,6 +521,15 @@ pub enum HandleMsg {
         /// optional message length padding
         padding: Option<String>,
     },
+    /// upgrade stored records to the current storage schema.  Normal operations are refused
+    /// until the migration completes, so the admin should repeat this until it reports that
+    /// the migration is complete
+    Migrate {
+        /// maximum number of records to migrate in this call
+        batch_size: u32,
+        /// optional message length padding
+        padding: Option<String>,
+    },
 }
 
 /// permission access level
@@ -363,12 +551,16 @@ pub enum AccessLevel {
 pub struct Mint {
     /// optional token id, if omitted, use current token index
     pub token_id: Option<String>,
-    /// optional owner address, owned by the minter otherwise
+    /// optional owner address, owned by the minter otherwise.  Only designated minters may
+    /// mint tokens owned by another address
     pub owner: Option<HumanAddr>,
     /// optional public metadata that can be seen by everyone
     pub public_metadata: Option<Metadata>,
     /// optional private metadata that can only be seen by owner and whitelist
     pub private_metadata: Option<Metadata>,
+    /// optionally true if the public metadata can be viewed with NftInfo before the token
+    /// is burned.  Defaults to false if not specified
+    pub public_teaser: Option<bool>,
     /// optional memo for the tx
     pub memo: Option<String>,
 }
@@ -389,6 +581,8 @@ pub struct Transfer {
     pub recipient: HumanAddr,
     /// tokens being transferred
     pub token_ids: Vec<String>,
+    /// optionally true if only the recipient may burn the tokens.  Defaults to false
+    pub recipient_only: Option<bool>,
     /// optional memo for the tx
     pub memo: Option<String>,
 }
@@ -402,6 +596,9 @@ pub struct Send {
     pub token_ids: Vec<String>,
     /// optional message to send with the (Batch)RecieveNft callback
     pub msg: Option<Binary>,
+    /// optionally true if only the receiving contract may burn the tokens.  Defaults to
+    /// false
+    pub recipient_only: Option<bool>,
     /// optional memo for the tx
     pub memo: Option<String>,
 }
@@ -419,6 +616,15 @@ pub enum HandleAnswer {
     BatchMintNft {
         token_ids: Vec<String>,
     },
+    CreateSecretRequest {
+        request_id: u32,
+        code: String,
+    },
+    /// FulfillSecretRequest will also display the minted token's ID in the log attributes
+    /// under the key `minted`
+    FulfillSecretRequest {
+        token_id: String,
+    },
     SetPublicMetadata {
         status: ResponseStatus,
     },
@@ -431,6 +637,10 @@ pub enum HandleAnswer {
     Reveal {
         status: ResponseStatus,
     },
+    /// RotateTokenLink only returns the new token ID in the response data, never in the log
+    RotateTokenLink {
+        token_id: String,
+    },
     Approve {
         status: ResponseStatus,
     },
@@ -461,12 +671,25 @@ pub enum HandleAnswer {
     BatchSendNft {
         status: ResponseStatus,
     },
+    /// BurnNft returns the burned token's secret, unless the contract's burns do not reveal
+    /// secrets
     BurnNft {
-        secret: TokenData,
+        secret: Option<TokenData>,
     },
+    /// BatchBurnNft returns the secrets of every burned token.  If best_effort was
+    /// requested, it will also display the outcome for each token id
     BatchBurnNft {
+        secrets: Vec<TokenData>,
+        results: Option<Vec<BurnResult>>,
+    },
+    ForgetRetrieved {
         status: ResponseStatus,
     },
+    PruneHistory {
+        status: ResponseStatus,
+        /// number of txs removed from the sender's history
+        pruned: u32,
+    },
     RegisterReceiveNft {
         status: ResponseStatus,
     },
@@ -474,6 +697,12 @@ pub enum HandleAnswer {
     ViewingKey {
         key: String,
     },
+    RevokePermit {
+        status: ResponseStatus,
+    },
+    RevokeScopedKey {
+        status: ResponseStatus,
+    },
     AddMinters {
         status: ResponseStatus,
     },
@@ -486,9 +715,51 @@ pub enum HandleAnswer {
     ChangeAdmin {
         status: ResponseStatus,
     },
+    SetTxRetention {
+        status: ResponseStatus,
+    },
     SetContractStatus {
         status: ResponseStatus,
     },
+    Migrate {
+        status: ResponseStatus,
+        complete: bool,
+    },
+}
+
+/// the queries a scoped viewing key can authenticate
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ScopedQuery {
+    OwnerOf,
+    AllNftInfo,
+    PrivateMetadata,
+    NftDossier,
+    TokenApprovals,
+    InventoryApprovals,
+    ApprovedForAll,
+    Tokens,
+    VerifyTransferApproval,
+    /// whether a token's secret can be retrieved
+    CanRetrieve,
+    RetrievedSecrets,
+    TransactionHistory,
+    Outbox,
+    Inbox,
+    SecretRequests,
+}
+
+/// a scoped viewing key, without the key itself
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ScopedKeyInfo {
+    /// name of the key
+    pub name: String,
+    /// queries the key may be used for
+    pub queries: Vec<ScopedQuery>,
+    /// if given, the only tokens the key may be used for
+    pub token_ids: Option<Vec<String>>,
+    /// when the key stops working
+    pub expires: Expiration,
 }
 
 /// the address and viewing key making an authenticated query request
@@ -500,6 +771,30 @@ pub struct ViewerInfo {
     pub viewing_key: String,
 }
 
+/// type of tx action used to filter the transaction history
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum TxActionType {
+    Mint,
+    Burn,
+    Transfer,
+    /// any action that is not a mint, burn, or transfer
+    Other,
+}
+
+/// criteria a tx must meet to be displayed in the transaction history
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Default, PartialEq, Debug)]
+pub struct TxFilter {
+    /// optionally only display txs of this action type
+    pub action: Option<TxActionType>,
+    /// optionally only display txs of this token
+    pub token_id: Option<String>,
+    /// optionally only display txs at or above this block height
+    pub min_height: Option<u64>,
+    /// optionally only display txs at or below this block height
+    pub max_height: Option<u64>,
+}
+
 /// tx type and specifics
 #[derive(Serialize, Deserialize, JsonSchema, Clone, PartialEq, Debug)]
 #[serde(rename_all = "snake_case")]
@@ -537,7 +832,7 @@ pub struct Tx {
     pub tx_id: u64,
     /// the block containing this tx
     pub blockheight: u64,
-    /// token id
+    /// token id, or its keyed hash if tx records are private
     pub token_id: String,
     /// tx type and specifics
     pub action: TxAction,
@@ -552,7 +847,8 @@ pub enum QueryMsg {
     ContractInfo {},
     /// display the contract's configuration
     ContractConfig {},
-    /// display the list of authorized minters
+    /// display the list of authorized minters.  Only supported if minting is restricted to
+    /// the minter list
     Minters {},
     /// display the number of tokens controlled by the contract.  The token supply must
     /// either be public, or the querier must be an authenticated minter
@@ -562,12 +858,11 @@ pub enum QueryMsg {
     },
     /// display an optionally paginated list of all the tokens controlled by the contract.
     /// The token supply must either be public, or the querier must be an authenticated
-    /// minter
+    /// minter.  Only supported if the contract enables metadata queries
     AllTokens {
         /// optional address and key requesting to view the list of tokens
         viewer: Option<ViewerInfo>,
-        /// optionally display only token ids that come after the input String in
-        /// lexicographical order
+        /// optionally display only token ids that were minted after the input String
         start_after: Option<String>,
         /// optional number of token ids to display
         limit: Option<u32>,
@@ -584,7 +879,8 @@ pub enum QueryMsg {
         /// false, expired Approvals will be filtered out of the response
         include_expired: Option<bool>,
     },
-    /// displays the public metadata of a token
+    /// displays the public metadata of a token if its creator allowed it to be viewed
+    /// before the token is burned, or if the contract enables metadata queries
     NftInfo { token_id: String },
     /// displays all the information contained in the OwnerOf and NftInfo queries
     AllNftInfo {
@@ -595,7 +891,8 @@ pub enum QueryMsg {
         /// false, expired Approvals will be filtered out of the response
         include_expired: Option<bool>,
     },
-    /// displays the private metadata if permitted to view it
+    /// displays the private metadata if permitted to view it.  Only supported if the
+    /// contract enables metadata queries
     PrivateMetadata {
         token_id: String,
         /// optional address and key requesting to view the private metadata
@@ -647,22 +944,40 @@ pub enum QueryMsg {
         include_expired: Option<bool>,
     },
     /// displays a list of all the tokens belonging to the input owner in which the viewer
-    /// has view_owner permission
+    /// has view_owner permission.  Tokens are listed in inventory order, which is not sorted
+    /// and changes when a token leaves the inventory (the last token takes its place), so a
+    /// burn or transfer between pages can cause tokens to be skipped or repeated
     Tokens {
         owner: HumanAddr,
         /// optional address of the querier if different from the owner
         viewer: Option<HumanAddr>,
         /// optional viewing key
         viewing_key: Option<String>,
-        /// optionally display only token ids that come after the input String in
-        /// lexicographical order
+        /// optionally display only token ids that come after the input token id in the
+        /// owner's inventory.  Use the last token id of the previous page
         start_after: Option<String>,
         /// optional number of token ids to display
         limit: Option<u32>,
     },
     /// display if a token is unwrapped
     IsUnwrapped { token_id: String },
-    /// verify that the specified address has approval to transfer every listed token
+    /// display whether burning a token would currently reveal its secret, and if not, the
+    /// reason the burn would fail.  If burns do not reveal secrets, only checks whether the
+    /// viewer may burn the token.  Never displays any metadata
+    CanRetrieve {
+        token_id: String,
+        /// optional address and key of the querier
+        viewer: Option<ViewerInfo>,
+    },
+    /// display the secrets revealed by the address' burns that are still within the
+    /// retrieval window
+    RetrievedSecrets {
+        address: HumanAddr,
+        /// viewing key
+        viewing_key: String,
+    },
+    /// verify that the specified address has approval to transfer every listed token.  Only
+    /// supported if the contract enables transfers
     VerifyTransferApproval {
         /// list of tokens to verify approval for
         token_ids: Vec<String>,
@@ -677,17 +992,192 @@ pub enum QueryMsg {
         address: HumanAddr,
         /// viewing key
         viewing_key: String,
-        /// optional page to display
+        /// optional page to display.  Ignored if a cursor is given
         page: Option<u32>,
         /// optional number of transactions per page
         page_size: Option<u32>,
+        /// optional tx_id of the last transaction already displayed.  If given, only
+        /// transactions older than it are displayed, so pages do not shift as new
+        /// transactions arrive
+        cursor: Option<u64>,
+        /// optional criteria the displayed transactions must meet.  The total in the
+        /// response still counts every transaction
+        filter: Option<TxFilter>,
     },
     /// display the code hash a contract has registered with the token contract and whether
-    /// the contract implements BatchReceivenft
+    /// the contract implements BatchReceivenft.  Only supported if the contract enables
+    /// transfers
     RegisteredCodeHash {
         /// the contract whose receive registration info you want to view
         contract: HumanAddr,
     },
+    /// display the progress of migrating stored records to the current storage schema.  The
+    /// record counts are only displayed if the token supply is public or the viewer is the
+    /// admin
+    MigrationStatus {
+        /// optional address and key requesting to view the record counts
+        viewer: Option<ViewerInfo>,
+    },
+    /// list the scoped viewing keys the address has created.  Scoped keys can not be used
+    /// for this query
+    ScopedKeys {
+        address: HumanAddr,
+        /// viewing key
+        viewing_key: String,
+    },
+    /// display a summary of each secret in the address' inventory.  Never displays private
+    /// metadata
+    Outbox {
+        address: HumanAddr,
+        /// viewing key
+        viewing_key: String,
+        /// optionally display only secrets that come after the input token id in the
+        /// address' inventory.  Use the last token id of the previous page
+        start_after: Option<String>,
+        /// optional number of secrets to display
+        limit: Option<u32>,
+    },
+    /// display the secrets other addresses minted for the address that it has not burned
+    /// or transferred yet.  With open minting, only designated minters may mint for others,
+    /// so the inbox mostly fills through fulfilled secret requests
+    Inbox {
+        address: HumanAddr,
+        /// viewing key
+        viewing_key: String,
+        /// optional token id of the last secret already displayed
+        cursor: Option<String>,
+        /// optional number of secrets to display
+        limit: Option<u32>,
+    },
+    /// display the secret requests the address created, most recent first
+    SecretRequests {
+        address: HumanAddr,
+        /// viewing key
+        viewing_key: String,
+        /// optional page to display
+        page: Option<u32>,
+        /// optional number of requests per page
+        page_size: Option<u32>,
+    },
+    /// perform an authenticated query using a permit signed by the querier instead of a
+    /// viewing key.  The permit must name this contract and grant the Owner permission, or
+    /// the History permission for TransactionHistory
+    WithPermit {
+        /// the signed permit
+        permit: Permit,
+        /// the query to perform as the permit's signer
+        query: QueryWithPermit,
+    },
+}
+
+/// queries that can be authenticated with a permit.  They mirror the QueryMsg variants of
+/// the same name, with the permit's signer acting as the viewer or address
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryWithPermit {
+    /// display the number of tokens controlled by the contract
+    NumTokens {},
+    /// display an optionally paginated list of all the tokens controlled by the contract
+    AllTokens {
+        /// optionally display only token ids that were minted after the input String
+        start_after: Option<String>,
+        /// optional number of token ids to display
+        limit: Option<u32>,
+    },
+    /// display the owner of the specified token if authorized to view it
+    OwnerOf {
+        token_id: String,
+        /// optionally include expired Approvals in the response list
+        include_expired: Option<bool>,
+    },
+    /// displays the private metadata if permitted to view it
+    PrivateMetadata { token_id: String },
+    /// displays all the information contained in the OwnerOf and NftInfo queries
+    AllNftInfo {
+        token_id: String,
+        /// optionally include expired Approvals in the response list
+        include_expired: Option<bool>,
+    },
+    /// displays all the information about a token that the signer has permission to see
+    NftDossier {
+        token_id: String,
+        /// optionally include expired Approvals in the response list
+        include_expired: Option<bool>,
+    },
+    /// list all the approvals in place for a token the signer owns
+    TokenApprovals {
+        token_id: String,
+        /// optionally include expired Approvals in the response list
+        include_expired: Option<bool>,
+    },
+    /// list all the inventory-wide approvals in place for the signer
+    InventoryApprovals {
+        /// optionally include expired Approvals in the response list
+        include_expired: Option<bool>,
+    },
+    /// displays a list of all the CW721-style operators of the signer's tokens
+    ApprovedForAll {
+        /// optionally include expired Approvals in the response list
+        include_expired: Option<bool>,
+    },
+    /// displays a list of all the tokens belonging to the input owner in which the signer
+    /// has view_owner permission.  Tokens are listed in unsorted inventory order, so a burn or
+    /// transfer between pages can cause tokens to be skipped or repeated
+    Tokens {
+        owner: HumanAddr,
+        /// optionally display only token ids that come after the input token id in the
+        /// owner's inventory
+        start_after: Option<String>,
+        /// optional number of token ids to display
+        limit: Option<u32>,
+    },
+    /// verify that the signer has approval to transfer every listed token
+    VerifyTransferApproval {
+        /// list of tokens to verify approval for
+        token_ids: Vec<String>,
+    },
+    /// display whether burning a token would currently reveal its secret
+    CanRetrieve { token_id: String },
+    /// display the secrets revealed by the signer's burns that are still within the
+    /// retrieval window
+    RetrievedSecrets {},
+    /// display the signer's transaction history in reverse chronological order
+    TransactionHistory {
+        /// optional page to display.  Ignored if a cursor is given
+        page: Option<u32>,
+        /// optional number of transactions per page
+        page_size: Option<u32>,
+        /// optional tx_id of the last transaction already displayed
+        cursor: Option<u64>,
+        /// optional criteria the displayed transactions must meet.  The total in the
+        /// response still counts every transaction
+        filter: Option<TxFilter>,
+    },
+    /// list the scoped viewing keys the signer has created
+    ScopedKeys {},
+    /// display a summary of each secret in the signer's inventory
+    Outbox {
+        /// optionally display only secrets that come after the input token id in the
+        /// signer's inventory
+        start_after: Option<String>,
+        /// optional number of secrets to display
+        limit: Option<u32>,
+    },
+    /// display the secrets other addresses minted for the signer.  With open minting, these
+    /// mostly come from fulfilled secret requests
+    Inbox {
+        /// optional token id of the last secret already displayed
+        cursor: Option<String>,
+        /// optional number of secrets to display
+        limit: Option<u32>,
+    },
+    /// display the secret requests the signer created, most recent first
+    SecretRequests {
+        /// optional page to display
+        page: Option<u32>,
+        /// optional number of requests per page
+        page_size: Option<u32>,
+    },
 }
 
 /// SNIP721 Approval
@@ -730,41 +1220,143 @@ pub struct TokenData {
 }
 
 impl TokenData {
-    pub fn from_public_metadata(token_id: &String, m: QueryAnswer) -> TokenData {
+    /// Returns StdResult<TokenData> holding the public metadata from an NftInfo answer
+    ///
+    /// # Arguments
+    ///
+    /// * `token_id` - token id string slice
+    /// * `m` - the NftInfo QueryAnswer of the token
+    pub fn from_public_metadata(token_id: &str, m: QueryAnswer) -> StdResult<TokenData> {
         match m {
-            QueryAnswer::NftInfo { name, description, image } => {
-                TokenData {
-                    token_id: token_id.clone(),
-                    name: name.clone(),
-                    description: description.clone(),
-                    image: image.clone(),
-                }
-            }
-            _ => {
-                panic!()
+            QueryAnswer::NftInfo {
+                name,
+                description,
+                image,
+            } => Ok(TokenData {
+                token_id: token_id.to_string(),
+                name,
+                description,
+                image,
+            }),
+            _ => Err(ContractError::UnreadableMetadata {
+                token_id: token_id.to_string(),
+                kind: "public metadata".to_string(),
             }
+            .into()),
         }
     }
-    pub fn update_with_private_metadata(&mut self, d: QueryAnswer) {
+
+    /// Returns StdResult<()> after overwriting any fields that are set in a PrivateMetadata
+    /// answer
+    ///
+    /// # Arguments
+    ///
+    /// * `d` - the PrivateMetadata QueryAnswer of the token
+    pub fn update_with_private_metadata(&mut self, d: QueryAnswer) -> StdResult<()> {
         match d {
-            QueryAnswer::PrivateMetadata { name, description, image } => {
-                if !name.is_none() {
-                    self.name = name.clone();
+            QueryAnswer::PrivateMetadata {
+                name,
+                description,
+                image,
+            } => {
+                if name.is_some() {
+                    self.name = name;
                 }
-                if !description.is_none() {
-                    self.description = description.clone();
+                if description.is_some() {
+                    self.description = description;
                 }
-                if !image.is_none() {
-                    self.image = image.clone();
+                if image.is_some() {
+                    self.image = image;
                 }
+                Ok(())
             }
-            _ => {
-                panic!()
+            _ => Err(ContractError::UnreadableMetadata {
+                token_id: self.token_id.clone(),
+                kind: "private metadata".to_string(),
             }
+            .into()),
         }
     }
 }
 
+/// outcome of an attempt to burn a token in a best-effort BatchBurnNft
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BurnOutcome {
+    /// the token was burned and its secret revealed
+    Revealed,
+    /// the token was burned without revealing its secret
+    Burned,
+    /// the token does not exist
+    NotFound,
+    /// the token could not be burned by the message sender.  This is also used when
+    /// the token does not exist or is sealed, but the token supply is private
+    NotPermitted,
+    /// the token's metadata is sealed
+    Sealed,
+}
+
+/// the outcome for a single token id in a best-effort BatchBurnNft
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BurnResult {
+    /// the token id
+    pub token_id: String,
+    /// what happened when trying to burn it
+    pub outcome: BurnOutcome,
+}
+
+/// summary of a secret in its owner's inventory
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OutboxSecret {
+    pub token_id: String,
+    /// the public metadata shown as the secret's teaser
+    pub public_metadata: Option<Metadata>,
+    /// block height the secret was minted at, if it was recorded
+    pub minted_at: Option<u64>,
+    /// true if the private metadata is sealed
+    pub sealed: bool,
+    /// true if only the owner may burn the secret
+    pub recipient_only: bool,
+    /// addresses that currently have permission to view the private metadata
+    pub recipients: Vec<HumanAddr>,
+}
+
+/// a secret another address minted for the recipient
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InboxSecret {
+    pub token_id: String,
+    /// the address that minted the secret, if it allows being displayed
+    pub creator: Option<HumanAddr>,
+    /// the public metadata shown as the secret's teaser, if the creator allows viewing it
+    /// before the token is burned
+    pub public_metadata: Option<Metadata>,
+    /// block height the secret was minted at
+    pub minted_at: Option<u64>,
+}
+
+/// a request for a secret to be minted for the requester
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SecretRequestInfo {
+    pub request_id: u32,
+    /// description of the requested secret
+    pub description: String,
+    /// the only address that may fulfill the request, if any
+    pub responder: Option<HumanAddr>,
+    /// when the request can no longer be fulfilled
+    pub expires: Expiration,
+    /// id of the token minted for the requester, if the request was fulfilled
+    pub token_id: Option<String>,
+}
+
+/// a secret revealed by a burn that can still be retrieved by the burner
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RetrievedSecret {
+    /// the revealed token data
+    pub secret: TokenData,
+    /// block height at which the secret will no longer be retrievable
+    pub expires_at: u64,
+}
+
 #[derive(Serialize, Deserialize, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryAnswer {
@@ -780,6 +1372,15 @@ pub enum QueryAnswer {
         minter_may_update_metadata: bool,
         owner_may_update_metadata: bool,
         burn_is_enabled: bool,
+        retrieval_window: Option<u64>,
+        tx_retention: TxRetention,
+        private_tx_records: bool,
+        secret_padding: Option<SecretPadding>,
+        transfers_enabled: bool,
+        gifting_enabled: bool,
+        open_minting: bool,
+        metadata_queries_enabled: bool,
+        burn_reveals_secret: bool,
     },
     Minters {
         minters: Vec<HumanAddr>,
@@ -840,17 +1441,55 @@ pub enum QueryAnswer {
     IsUnwrapped {
         token_is_unwrapped: bool,
     },
+    CanRetrieve {
+        /// true if burning the token would reveal its secret
+        retrievable: bool,
+        /// true if the token's private metadata is sealed
+        sealed: bool,
+        /// description of the error a burn would return if the token is not retrievable.  None
+        /// if the burn would succeed without revealing the secret, because the contract does
+        /// not reveal secrets when burning
+        reason: Option<String>,
+    },
+    RetrievedSecrets {
+        secrets: Vec<RetrievedSecret>,
+    },
     VerifyTransferApproval {
         approved_for_all: bool,
         first_unapproved_token: Option<String>,
     },
     TransactionHistory {
+        /// total number of transactions involving the address, ignoring any filter
+        total: u64,
         txs: Vec<Tx>,
     },
     RegisteredCodeHash {
         code_hash: Option<String>,
         also_implements_batch_receive_nft: bool,
     },
+    MigrationStatus {
+        schema_version: u8,
+        target_version: u8,
+        complete: bool,
+        tokens_migrated: Option<u32>,
+        total_tokens: Option<u32>,
+        txs_migrated: Option<u64>,
+        total_txs: Option<u64>,
+    },
+    ScopedKeys {
+        keys: Vec<ScopedKeyInfo>,
+    },
+    Outbox {
+        secrets: Vec<OutboxSecret>,
+    },
+    Inbox {
+        secrets: Vec<InboxSecret>,
+    },
+    SecretRequests {
+        /// total number of requests the address created
+        total: u32,
+        requests: Vec<SecretRequestInfo>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -0,0 +1,230 @@
+use bech32::ToBase32;
+use ripemd160::{Digest, Ripemd160};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{
+    to_binary, Api, Binary, CanonicalAddr, Extern, HumanAddr, Querier, ReadonlyStorage, StdResult,
+    Storage, Uint128,
+};
+use cosmwasm_storage::{PrefixedStorage, ReadonlyPrefixedStorage};
+
+use crate::error::ContractError;
+use crate::rand::sha_256;
+use crate::state::{may_load, save, PREFIX_REVOKED_PERMITS};
+
+/// bech32 prefix of Secret Network account addresses
+pub const ACCOUNT_HRP: &str = "secret";
+
+/// a SNIP-24 query permit signed offline by the querier
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct Permit {
+    /// the signed parameters
+    pub params: PermitParams,
+    /// the querier's signature of the parameters
+    pub signature: PermitSignature,
+}
+
+/// the parameters of a permit that are signed
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct PermitParams {
+    /// contracts the permit may be used with
+    pub allowed_tokens: Vec<HumanAddr>,
+    /// name the signer can use to revoke the permit
+    pub permit_name: String,
+    /// chain the permit was signed for
+    pub chain_id: String,
+    /// queries the permit authorizes
+    pub permissions: Vec<TokenPermissions>,
+}
+
+/// signature of a permit along with the signer's public key
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct PermitSignature {
+    /// the signer's public key
+    pub pub_key: PubKey,
+    /// compact secp256k1 signature
+    pub signature: Binary,
+}
+
+/// public key of a permit signer
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct PubKey {
+    /// ignored, but must be "tendermint/PubKeySecp256k1" to be signed by wallets
+    pub r#type: String,
+    /// compressed secp256k1 public key
+    pub value: Binary,
+}
+
+/// SNIP-24 permissions a permit can grant
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenPermissions {
+    /// not used by this contract
+    Allowance,
+    /// not used by this contract
+    Balance,
+    /// allows viewing the signer's transaction history
+    History,
+    /// allows every query the signer could make with their viewing key
+    Owner,
+}
+
+// the amino sign doc wallets sign for a permit.  Fields are in alphabetical order because
+// the signed Json must be sorted
+#[derive(Serialize)]
+struct SignedPermit {
+    account_number: Uint128,
+    chain_id: String,
+    fee: Fee,
+    memo: String,
+    msgs: Vec<PermitMsg>,
+    sequence: Uint128,
+}
+
+#[derive(Serialize)]
+struct Fee {
+    amount: Vec<FeeCoin>,
+    gas: Uint128,
+}
+
+#[derive(Serialize)]
+struct FeeCoin {
+    amount: Uint128,
+    denom: String,
+}
+
+#[derive(Serialize)]
+struct PermitMsg {
+    r#type: String,
+    value: PermitContent,
+}
+
+#[derive(Serialize)]
+struct PermitContent {
+    allowed_tokens: Vec<HumanAddr>,
+    permissions: Vec<TokenPermissions>,
+    permit_name: String,
+}
+
+impl Permit {
+    /// Returns bool of whether the permit grants the specified permission
+    ///
+    /// # Arguments
+    ///
+    /// * `permission` - a reference to the permission being checked
+    pub fn has_permission(&self, permission: &TokenPermissions) -> bool {
+        self.params.permissions.contains(permission)
+    }
+
+    /// Returns StdResult<Binary> of the bytes the signer signed
+    pub fn signed_bytes(&self) -> StdResult<Binary> {
+        let params = &self.params;
+        to_binary(&SignedPermit {
+            account_number: Uint128(0),
+            chain_id: params.chain_id.clone(),
+            fee: Fee {
+                amount: vec![FeeCoin {
+                    amount: Uint128(0),
+                    denom: "uscrt".to_string(),
+                }],
+                gas: Uint128(1),
+            },
+            memo: String::new(),
+            msgs: vec![PermitMsg {
+                r#type: "query_permit".to_string(),
+                value: PermitContent {
+                    allowed_tokens: params.allowed_tokens.clone(),
+                    permissions: params.permissions.clone(),
+                    permit_name: params.permit_name.clone(),
+                },
+            }],
+            sequence: Uint128(0),
+        })
+    }
+}
+
+/// Returns StdResult<CanonicalAddr> of the address that signed the permit after verifying
+/// it applies to this contract, has not been revoked, and has a valid signature
+///
+/// # Arguments
+///
+/// * `deps` - a reference to Extern containing all the contract's external dependencies
+/// * `permit` - a reference to the permit
+/// * `contract` - a reference to the address of this contract
+pub fn validate<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    permit: &Permit,
+    contract: &HumanAddr,
+) -> StdResult<CanonicalAddr> {
+    if !permit.params.allowed_tokens.contains(contract) {
+        return Err(ContractError::PermitWrongContract.into());
+    }
+    let pubkey = permit.signature.pub_key.value.as_slice();
+    let signer = deps.api.canonical_address(&pubkey_to_account(pubkey)?)?;
+    if is_permit_revoked(&deps.storage, &signer, &permit.params.permit_name)? {
+        return Err(ContractError::PermitRevoked {
+            permit_name: permit.params.permit_name.clone(),
+        }
+        .into());
+    }
+    let hash = sha_256(permit.signed_bytes()?.as_slice());
+    let message = libsecp256k1::Message::parse_slice(&hash)
+        .map_err(|_| ContractError::PermitInvalidSignature)?;
+    let mut signature =
+        libsecp256k1::Signature::parse_standard_slice(permit.signature.signature.as_slice())
+            .map_err(|_| ContractError::PermitInvalidSignature)?;
+    signature.normalize_s();
+    let public_key = libsecp256k1::PublicKey::parse_slice(pubkey, None)
+        .map_err(|_| ContractError::PermitInvalidSignature)?;
+    if !libsecp256k1::verify(&message, &signature, &public_key) {
+        return Err(ContractError::PermitInvalidSignature.into());
+    }
+    Ok(signer)
+}
+
+/// Returns StdResult<HumanAddr> of the account address of a secp256k1 public key
+///
+/// # Arguments
+///
+/// * `pubkey` - the compressed public key
+pub fn pubkey_to_account(pubkey: &[u8]) -> StdResult<HumanAddr> {
+    let hash = Ripemd160::digest(&sha_256(pubkey));
+    bech32::encode(ACCOUNT_HRP, hash.as_slice().to_base32())
+        .map(HumanAddr)
+        .map_err(|_| ContractError::PermitInvalidSignature.into())
+}
+
+/// Returns StdResult<bool> of whether the address revoked the named permit
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `address` - a reference to the address that signed the permit
+/// * `permit_name` - name of the permit
+pub fn is_permit_revoked<S: ReadonlyStorage>(
+    storage: &S,
+    address: &CanonicalAddr,
+    permit_name: &str,
+) -> StdResult<bool> {
+    let store =
+        ReadonlyPrefixedStorage::multilevel(&[PREFIX_REVOKED_PERMITS, address.as_slice()], storage);
+    Ok(may_load::<bool, _>(&store, permit_name.as_bytes())?.unwrap_or(false))
+}
+
+/// Returns StdResult<()> after recording that the address revoked the named permit
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `address` - a reference to the address revoking the permit
+/// * `permit_name` - name of the permit
+pub fn revoke_permit<S: Storage>(
+    storage: &mut S,
+    address: &CanonicalAddr,
+    permit_name: &str,
+) -> StdResult<()> {
+    let mut store =
+        PrefixedStorage::multilevel(&[PREFIX_REVOKED_PERMITS, address.as_slice()], storage);
+    save(&mut store, permit_name.as_bytes(), &true)
+}
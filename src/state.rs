@@ -1,6 +1,9 @@
 use std::any::type_name;
+use std::collections::HashSet;
 
-use cosmwasm_std::{Api, CanonicalAddr, ReadonlyStorage, StdError, StdResult, Storage};
+use cosmwasm_std::{
+    Api, Binary, BlockInfo, CanonicalAddr, ReadonlyStorage, StdError, StdResult, Storage,
+};
 use cosmwasm_storage::{PrefixedStorage, ReadonlyPrefixedStorage};
 
 use secret_toolkit::{
@@ -11,7 +14,10 @@ use secret_toolkit::{
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use crate::expiration::Expiration;
-use crate::msg::{Tx, TxAction};
+use crate::msg::{ScopedQuery, SecretPadding, Tx, TxAction, TxActionType, TxFilter, TxRetention};
+use crate::rand::{sha_256, Prng};
+use crate::token::Token;
+use crate::viewing_key::VIEWING_KEY_SIZE;
 
 /// storage key for config
 pub const CONFIG_KEY: &[u8] = b"config";
@@ -21,8 +27,15 @@ pub const BLOCK_KEY: &[u8] = b"blockinfo";
 pub const MINTERS_KEY: &[u8] = b"minters";
 /// storage key for prng seed
 pub const PRNG_SEED_KEY: &[u8] = b"prngseed";
-/// storage key for list of tokens
+/// storage key for the progress of a storage schema migration
+pub const MIGRATION_KEY: &[u8] = b"migration";
+/// storage key for this contract's address
+pub const MY_ADDRESS_KEY: &[u8] = b"myaddress";
+/// storage key for the legacy list of tokens.  Only read to migrate contracts that were
+/// deployed before the token count was stored
 pub const TOKENS_KEY: &[u8] = b"tokens";
+/// storage key for the number of tokens the contract controls
+pub const TOKEN_COUNT_KEY: &[u8] = b"tokencnt";
 /// prefix for storage that maps ids to indices
 pub const PREFIX_MAP_TO_INDEX: &[u8] = b"map2idx";
 /// prefix for storage that maps indices to ids
@@ -37,18 +50,72 @@ pub const PREFIX_PRIV_META: &[u8] = b"privatemeta";
 pub const PREFIX_TXS: &[u8] = b"rawtxs";
 /// prefix for storage of tx ids
 pub const PREFIX_TX_IDS: &[u8] = b"txids";
-/// prefix for storage of an owner's list of tokens
+/// prefix for storage of the number of tx histories that still include a tx
+pub const PREFIX_TX_REFS: &[u8] = b"txrefs";
+/// prefix for the legacy storage of an owner's list of tokens.  Only read to migrate
+/// inventories that were saved before they were indexed
 pub const PREFIX_OWNED: &[u8] = b"owned";
-/// prefix for storage of owner's list of "all" permissions
+/// prefix for storage of an owner's inventory of token indices
+pub const PREFIX_INVENTORY: &[u8] = b"inventory";
+/// prefix for storage of the position of a token index in its owner's inventory
+pub const PREFIX_INVENTORY_POS: &[u8] = b"invpos";
+/// prefix for the legacy storage of owner's list of "all" permissions.  Only read to migrate
+/// approvals that were saved before they were keyed by address
 pub const PREFIX_ALL_PERMISSIONS: &[u8] = b"allpermissions";
-/// prefix for storage of owner's list of tokens permitted to addresses
+/// prefix for the legacy storage of owner's list of tokens permitted to addresses.  Only read
+/// to migrate approvals that were saved before they were keyed by address
 pub const PREFIX_AUTHLIST: &[u8] = b"authlist";
+/// prefix for storage of the expiration of an owner's "all" permission for an address and
+/// permission type
+pub const PREFIX_ALL_PERM: &[u8] = b"allperm";
+/// prefix for storage of the addresses an owner has given any "all" permission
+pub const PREFIX_ALL_PERM_ADDRS: &[u8] = b"allpermaddrs";
+/// prefix for storage of the position of an address in its owner's list of "all" permissions
+pub const PREFIX_ALL_PERM_POS: &[u8] = b"allpermpos";
+/// prefix for storage of the owner's tokens an address is permitted for a permission type
+pub const PREFIX_AUTH_TOKENS: &[u8] = b"authtokens";
+/// prefix for storage of the addresses an owner has given permissions for individual tokens
+pub const PREFIX_AUTH_ADDRS: &[u8] = b"authaddrs";
+/// prefix for storage of the position of an address in its owner's list of token permissions
+pub const PREFIX_AUTH_POS: &[u8] = b"authpos";
 /// prefix for storage of an address' ownership prvicacy
 pub const PREFIX_OWNER_PRIV: &[u8] = b"ownerpriv";
 /// prefix for storage of viewing keys
 pub const PREFIX_VIEW_KEY: &[u8] = b"viewkeys";
 /// prefix for the storage of the code hashes of contract's that have implemented ReceiveNft
 pub const PREFIX_RECEIVERS: &[u8] = b"receivers";
+/// prefix for storage of the secrets retained from an address' burns
+pub const PREFIX_RETRIEVED: &[u8] = b"retrieved";
+/// prefix for storage of the names of the permits an address has revoked
+pub const PREFIX_REVOKED_PERMITS: &[u8] = b"revokedpermits";
+/// prefix for storage of the scoped viewing keys an address has issued
+pub const PREFIX_SCOPED_KEYS: &[u8] = b"scopedkeys";
+/// prefix for storage of who minted a token and when
+pub const PREFIX_MINT_INFO: &[u8] = b"mintinfo";
+/// prefix for storage of an address' index of the tokens minted for it by others
+pub const PREFIX_INBOX: &[u8] = b"inbox";
+/// prefix for storage of the position of a token index in its recipient's inbox
+pub const PREFIX_INBOX_POS: &[u8] = b"inboxpos";
+/// storage key for the number of secret requests ever created
+pub const REQUEST_CNT_KEY: &[u8] = b"requestcnt";
+/// prefix for storage of secret requests
+pub const PREFIX_SECRET_REQUESTS: &[u8] = b"secretrequests";
+/// prefix for storage of the ids of the secret requests an address created
+pub const PREFIX_REQUEST_IDS: &[u8] = b"requestids";
+
+/// version of the storage schema written by this code.  Contracts whose stored data uses an
+/// older schema must be migrated before they resume normal operations.  Version 2 stores
+/// tokens with their compact binary encoding
+pub const SCHEMA_VERSION: u8 = 2;
+
+/// compact encoding tag for an expiration that is not set
+const EXP_NONE: u8 = 0;
+/// compact encoding tag for an expiration at a block height
+const EXP_AT_HEIGHT: u8 = 1;
+/// compact encoding tag for an expiration at a time
+const EXP_AT_TIME: u8 = 2;
+/// compact encoding tag for an expiration that never expires
+const EXP_NEVER: u8 = 3;
 
 /// Token contract config
 #[derive(Serialize, Debug, Deserialize, Clone, PartialEq)]
@@ -80,6 +147,98 @@ pub struct Config {
     /// is burn enabled
     //TODO Remove
     pub burn_is_enabled: bool,
+    /// number of blocks burned secrets remain retrievable, if retention is enabled
+    pub retrieval_window: Option<u64>,
+    /// limits on how much transaction history is kept for each address
+    pub tx_retention: TxRetention,
+    /// do tx records hold hashed token ids and encrypted memos
+    pub private_tx_records: bool,
+    /// padding buckets for responses holding secrets, and the max private metadata size
+    pub secret_padding: Option<SecretPadding>,
+    /// can tokens be transferred and sent
+    pub transfers_enabled: bool,
+    /// can sealed tokens be transferred and sent as gifts when transfers are not enabled
+    pub gifting_enabled: bool,
+    /// may anyone mint, or only the minters
+    pub open_minting: bool,
+    /// are the AllTokens query, full NftInfo and PrivateMetadata queries supported
+    pub metadata_queries_enabled: bool,
+    /// does burning a token reveal its secret to the burner
+    pub burn_reveals_secret: bool,
+    /// version of the storage schema the contract's stored data uses
+    pub schema_version: u8,
+}
+
+/// config saved before the storage schema was versioned
+#[derive(Serialize, Deserialize)]
+struct ConfigV0 {
+    name: String,
+    symbol: String,
+    admin: CanonicalAddr,
+    mint_cnt: u32,
+    tx_cnt: u64,
+    status: u8,
+    token_supply_is_public: bool,
+    owner_is_public: bool,
+    sealed_metadata_is_enabled: bool,
+    unwrap_to_private: bool,
+    minter_may_update_metadata: bool,
+    owner_may_update_metadata: bool,
+    burn_is_enabled: bool,
+}
+
+impl From<ConfigV0> for Config {
+    fn from(old: ConfigV0) -> Self {
+        Config {
+            name: old.name,
+            symbol: old.symbol,
+            admin: old.admin,
+            mint_cnt: old.mint_cnt,
+            tx_cnt: old.tx_cnt,
+            status: old.status,
+            token_supply_is_public: old.token_supply_is_public,
+            owner_is_public: old.owner_is_public,
+            sealed_metadata_is_enabled: old.sealed_metadata_is_enabled,
+            unwrap_to_private: old.unwrap_to_private,
+            minter_may_update_metadata: old.minter_may_update_metadata,
+            owner_may_update_metadata: old.owner_may_update_metadata,
+            burn_is_enabled: old.burn_is_enabled,
+            retrieval_window: None,
+            tx_retention: TxRetention::default(),
+            private_tx_records: false,
+            secret_padding: None,
+            transfers_enabled: false,
+            gifting_enabled: false,
+            open_minting: true,
+            metadata_queries_enabled: false,
+            burn_reveals_secret: true,
+            schema_version: 0,
+        }
+    }
+}
+
+/// progress of migrating stored records to the current storage schema
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct MigrationProgress {
+    /// number of token indices that have been migrated
+    pub tokens: u32,
+    /// number of txs that have been migrated
+    pub txs: u64,
+}
+
+/// Returns StdResult<Config> of the contract's config.  A config saved before the storage
+/// schema was versioned is read as schema version 0
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+pub fn load_config<S: ReadonlyStorage>(storage: &S) -> StdResult<Config> {
+    let config: StdResult<Config> = load(storage, CONFIG_KEY);
+    config.or_else(|err| {
+        load::<ConfigV0, _>(storage, CONFIG_KEY)
+            .map(Config::from)
+            .map_err(|_| err)
+    })
 }
 
 /// tx type and specifics
@@ -111,6 +270,50 @@ pub enum StoredTxAction {
     },
 }
 
+impl StoredTxAction {
+    /// Returns &CanonicalAddr of the token owner a private memo is encrypted for
+    fn memo_owner(&self) -> &CanonicalAddr {
+        match self {
+            StoredTxAction::Transfer { from, .. } => from,
+            StoredTxAction::Mint { recipient, .. } => recipient,
+            StoredTxAction::Burn { owner, .. } => owner,
+        }
+    }
+
+    /// Returns Vec<CanonicalAddr> of the addresses whose tx histories include this action
+    fn participants(&self) -> Vec<CanonicalAddr> {
+        match self {
+            StoredTxAction::Transfer {
+                from,
+                sender,
+                recipient,
+            } => {
+                let mut addrs = vec![from.clone(), recipient.clone()];
+                if let Some(sndr) = sender.as_ref() {
+                    if sndr != recipient {
+                        addrs.push(sndr.clone());
+                    }
+                }
+                addrs
+            }
+            StoredTxAction::Mint { minter, recipient } => {
+                let mut addrs = vec![recipient.clone()];
+                if recipient != minter {
+                    addrs.push(minter.clone());
+                }
+                addrs
+            }
+            StoredTxAction::Burn { owner, burner } => {
+                let mut addrs = vec![owner.clone()];
+                if let Some(bnr) = burner.as_ref() {
+                    addrs.push(bnr.clone());
+                }
+                addrs
+            }
+        }
+    }
+}
+
 /// tx in storage
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "snake_case")]
@@ -119,21 +322,64 @@ pub struct StoredTx {
     pub tx_id: u64,
     /// the block containing this tx
     pub blockheight: u64,
-    /// token id
+    /// token id, or its keyed hash if tx records are private
     pub token_id: String,
     /// tx type and specifics
     pub action: StoredTxAction,
     /// optional memo
     pub memo: Option<String>,
+    /// optional memo encrypted for the token owner if tx records are private
+    #[serde(default)]
+    pub sealed_memo: Option<Binary>,
 }
 
 impl StoredTx {
-    /// Returns StdResult<Tx> from converting a stored tx to a displayable tx
+    /// Returns bool true if the tx meets all the filter's criteria
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - a reference to the criteria the tx must meet
+    fn matches(&self, filter: &TxFilter) -> bool {
+        if let Some(action) = filter.action {
+            let tx_type = match self.action {
+                StoredTxAction::Mint { .. } => TxActionType::Mint,
+                StoredTxAction::Burn { .. } => TxActionType::Burn,
+                StoredTxAction::Transfer { .. } => TxActionType::Transfer,
+            };
+            if tx_type != action {
+                return false;
+            }
+        }
+        if matches!(filter.token_id.as_ref(), Some(id) if *id != self.token_id) {
+            return false;
+        }
+        if matches!(filter.max_height, Some(max) if self.blockheight > max) {
+            return false;
+        }
+        !matches!(filter.min_height, Some(min) if self.blockheight < min)
+    }
+
+    /// Returns StdResult<Tx> from converting a stored tx to a displayable tx.  An encrypted
+    /// memo is only decrypted if the viewer is the token owner it was encrypted for
     ///
     /// # Arguments
     ///
     /// * `api` - a reference to the Api used to convert human and canonical addresses
-    pub fn into_humanized<A: Api>(self, api: &A) -> StdResult<Tx> {
+    /// * `viewer` - a reference to the address viewing the tx
+    /// * `seed` - the contract's prng seed
+    pub fn into_humanized<A: Api>(
+        self,
+        api: &A,
+        viewer: &CanonicalAddr,
+        seed: &[u8],
+    ) -> StdResult<Tx> {
+        let memo = match self.sealed_memo {
+            Some(sealed) if self.action.memo_owner() == viewer => {
+                let key = memo_key(seed, viewer, self.tx_id);
+                String::from_utf8(apply_memo_key(&key, sealed.as_slice())).ok()
+            }
+            _ => self.memo,
+        };
         let action = match self.action {
             StoredTxAction::Transfer {
                 from,
@@ -172,13 +418,54 @@ impl StoredTx {
             blockheight: self.blockheight,
             token_id: self.token_id,
             action,
-            memo: self.memo,
+            memo,
         };
 
         Ok(tx)
     }
 }
 
+/// Returns String of the hex encoded keyed hash of a token id that private tx records hold
+///
+/// # Arguments
+///
+/// * `seed` - the contract's prng seed
+/// * `token_id` - the token id to hash
+pub fn hash_token_id(seed: &[u8], token_id: &str) -> String {
+    hex::encode(sha_256(&[seed, b"tokenid", token_id.as_bytes()].concat()))
+}
+
+/// Returns [u8; 32] of the key used to encrypt a private tx's memo for the token owner
+///
+/// # Arguments
+///
+/// * `seed` - the contract's prng seed
+/// * `owner` - a reference to the address of the token owner the memo is for
+/// * `tx_id` - the id of the tx the memo belongs to
+fn memo_key(seed: &[u8], owner: &CanonicalAddr, tx_id: u64) -> [u8; 32] {
+    sha_256(&[seed, b"memo", owner.as_slice(), &tx_id.to_le_bytes()].concat())
+}
+
+/// Returns Vec<u8> of the data XORed with a ChaCha keystream seeded by the key.  Applying the
+/// same key again recovers the original data.  This only hides the memo from anyone reading
+/// the raw contract storage; it is not authenticated, so it does not detect tampering.  The
+/// key is derived from the token owner, so a memo written by anyone else (a minter minting for
+/// another address, or an approved burner) can not be read back by its author
+///
+/// # Arguments
+///
+/// * `key` - a reference to the memo key
+/// * `data` - the data to encrypt or decrypt
+fn apply_memo_key(key: &[u8; 32], data: &[u8]) -> Vec<u8> {
+    let mut rng = Prng::new(key, &[]);
+    let mut out = Vec::with_capacity(data.len());
+    for chunk in data.chunks(32) {
+        let stream = rng.rand_bytes();
+        out.extend(chunk.iter().zip(stream.iter()).map(|(b, k)| b ^ k));
+    }
+    out
+}
+
 /// Returns StdResult<()> after storing tx
 ///
 /// # Arguments
@@ -213,25 +500,9 @@ pub fn store_transfer<S: Storage>(
         token_id,
         action,
         memo,
+        sealed_memo: None,
     };
-    let mut tx_store = PrefixedStorage::new(PREFIX_TXS, storage);
-    json_save(&mut tx_store, &config.tx_cnt.to_le_bytes(), &tx)?;
-    if let StoredTxAction::Transfer {
-        from,
-        sender,
-        recipient,
-    } = tx.action
-    {
-        append_tx_for_addr(storage, config.tx_cnt, &from)?;
-        append_tx_for_addr(storage, config.tx_cnt, &recipient)?;
-        if let Some(sndr) = sender.as_ref() {
-            if *sndr != recipient {
-                append_tx_for_addr(storage, config.tx_cnt, sndr)?;
-            }
-        }
-    }
-    config.tx_cnt += 1;
-    Ok(())
+    store_tx(storage, config, tx)
 }
 
 /// Returns StdResult<()> after storing tx
@@ -261,17 +532,9 @@ pub fn store_mint<S: Storage>(
         token_id,
         action,
         memo,
+        sealed_memo: None,
     };
-    let mut tx_store = PrefixedStorage::new(PREFIX_TXS, storage);
-    json_save(&mut tx_store, &config.tx_cnt.to_le_bytes(), &tx)?;
-    if let StoredTxAction::Mint { minter, recipient } = tx.action {
-        append_tx_for_addr(storage, config.tx_cnt, &recipient)?;
-        if recipient != minter {
-            append_tx_for_addr(storage, config.tx_cnt, &minter)?;
-        }
-    }
-    config.tx_cnt += 1;
-    Ok(())
+    store_tx(storage, config, tx)
 }
 
 /// Returns StdResult<()> after storing tx
@@ -301,52 +564,264 @@ pub fn store_burn<S: Storage>(
         token_id,
         action,
         memo,
+        sealed_memo: None,
     };
-    let mut tx_store = PrefixedStorage::new(PREFIX_TXS, storage);
-    json_save(&mut tx_store, &config.tx_cnt.to_le_bytes(), &tx)?;
-    if let StoredTxAction::Burn { owner, burner } = tx.action {
-        append_tx_for_addr(storage, config.tx_cnt, &owner)?;
-        if let Some(bnr) = burner.as_ref() {
-            append_tx_for_addr(storage, config.tx_cnt, bnr)?;
+    store_tx(storage, config, tx)
+}
+
+/// Returns StdResult<()> after storing a tx and adding it to the history of every address
+/// involved, unless the retention limits keep no transaction history.  If tx records are
+/// private, the token id is replaced by its keyed hash, the memo is encrypted, and the burner
+/// of a burnt token is dropped, so the burn is only recorded in the previous owner's history
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the storage this item should go to
+/// * `config` - a mutable reference to the contract Config
+/// * `tx` - a reference to the tx to store
+fn store_tx<S: Storage>(storage: &mut S, config: &mut Config, mut tx: StoredTx) -> StdResult<()> {
+    if config.tx_retention.max_txs != Some(0) {
+        if config.private_tx_records {
+            let seed: Vec<u8> = load(storage, PRNG_SEED_KEY)?;
+            tx.token_id = hash_token_id(&seed, &tx.token_id);
+            if let Some(memo) = tx.memo.take() {
+                let key = memo_key(&seed, tx.action.memo_owner(), tx.tx_id);
+                tx.sealed_memo = Some(Binary::from(apply_memo_key(&key, memo.as_bytes())));
+            }
+            if let StoredTxAction::Burn { burner, .. } = &mut tx.action {
+                *burner = None;
+            }
+        }
+        let mut tx_store = PrefixedStorage::new(PREFIX_TXS, storage);
+        json_save(&mut tx_store, &tx.tx_id.to_le_bytes(), &tx)?;
+        for address in tx.action.participants() {
+            append_tx_for_addr(
+                storage,
+                &config.tx_retention,
+                tx.tx_id,
+                &address,
+                tx.blockheight,
+            )?;
         }
     }
     config.tx_cnt += 1;
     Ok(())
 }
 
-/// Returns StdResult<()> after saving tx id
+/// Returns StdResult<()> after saving tx id.  Once an address' history holds twice as many
+/// txs as the retention limits keep, it is pruned, so the cost of compacting it is spread
+/// over many txs.  If txs are only kept for a number of blocks, the history is pruned as soon
+/// as its oldest tx expires, so expired txs do not linger in storage
 ///
 /// # Arguments
 ///
 /// * `storage` - a mutable reference to the storage this item should go to
+/// * `retention` - a reference to the transaction history retention limits
 /// * `tx_id` - the tx id to store
 /// * `address` - a reference to the address for which to store this tx id
+/// * `height` - the current block height
 fn append_tx_for_addr<S: Storage>(
     storage: &mut S,
+    retention: &TxRetention,
     tx_id: u64,
     address: &CanonicalAddr,
+    height: u64,
 ) -> StdResult<()> {
-    let mut store = PrefixedStorage::multilevel(&[PREFIX_TX_IDS, address.as_slice()], storage);
-    let mut store = AppendStoreMut::attach_or_create(&mut store)?;
-    store.push(&tx_id)
+    let (len, oldest) = {
+        let mut store = PrefixedStorage::multilevel(&[PREFIX_TX_IDS, address.as_slice()], storage);
+        let mut store = AppendStoreMut::attach_or_create(&mut store)?;
+        store.push(&tx_id)?;
+        (store.len(), store.get_at(0)?)
+    };
+    let oldest_expired = match retention.max_blocks {
+        Some(max_blocks) => {
+            let tx_store = ReadonlyPrefixedStorage::new(PREFIX_TXS, storage);
+            let tx: Option<StoredTx> = json_may_load(&tx_store, &oldest.to_le_bytes())?;
+            match tx {
+                Some(tx) => tx.blockheight.saturating_add(max_blocks) <= height,
+                // only expired txs are deleted while an address' history still lists them
+                None => true,
+            }
+        }
+        None => false,
+    };
+    if oldest_expired || matches!(retention.max_txs, Some(max) if len >= max.saturating_mul(2)) {
+        prune_txs(storage, address, retention, height, None)?;
+    }
+    Ok(())
+}
+
+/// Returns StdResult<u32> of the position of the oldest tx in an address' history that is
+/// within the retention limits
+///
+/// # Arguments
+///
+/// * `id_store` - a reference to the address' AppendStore of tx ids
+/// * `tx_store` - a reference to the storage of txs
+/// * `retention` - a reference to the transaction history retention limits
+/// * `height` - the current block height
+/// * `keep` - optional max number of txs to keep, in addition to the retention limits
+fn first_retained<S: ReadonlyStorage, T: ReadonlyStorage>(
+    id_store: &AppendStore<u64, S>,
+    tx_store: &T,
+    retention: &TxRetention,
+    height: u64,
+    keep: Option<u32>,
+) -> StdResult<u32> {
+    let len = id_store.len();
+    let max_txs = match (retention.max_txs, keep) {
+        (Some(max), Some(kp)) => max.min(kp),
+        (max, kp) => max.or(kp).unwrap_or(len),
+    };
+    let mut start = len - max_txs.min(len);
+    if let Some(max_blocks) = retention.max_blocks {
+        // block heights never decrease as tx ids increase, so binary search for the oldest
+        // tx that has not expired
+        let mut end = len;
+        while start < end {
+            let mid = start + (end - start) / 2;
+            let id = id_store.get_at(mid)?;
+            let tx: Option<StoredTx> = json_may_load(tx_store, &id.to_le_bytes())?;
+            // a tx that was already deleted had expired
+            let expired = match tx {
+                Some(tx) => tx.blockheight.saturating_add(max_blocks) <= height,
+                None => true,
+            };
+            if expired {
+                start = mid + 1;
+            } else {
+                end = mid;
+            }
+        }
+    }
+    Ok(start)
+}
+
+/// Returns StdResult<u32> of the number of txs removed from an address' history after
+/// removing every tx outside the retention limits.  The remaining tx ids are moved to the
+/// front of the address' AppendStore.  A removed tx that expired is deleted immediately,
+/// because it has expired for every address involved.  Otherwise it is deleted once it is no
+/// longer in any address' history
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `address` - a reference to the address whose history should be pruned
+/// * `retention` - a reference to the transaction history retention limits
+/// * `height` - the current block height
+/// * `keep` - optional max number of txs to keep, in addition to the retention limits
+pub fn prune_txs<S: Storage>(
+    storage: &mut S,
+    address: &CanonicalAddr,
+    retention: &TxRetention,
+    height: u64,
+    keep: Option<u32>,
+) -> StdResult<u32> {
+    let start = {
+        let id_store =
+            ReadonlyPrefixedStorage::multilevel(&[PREFIX_TX_IDS, address.as_slice()], storage);
+        let id_store = if let Some(result) = AppendStore::<u64, _>::attach(&id_store) {
+            result?
+        } else {
+            return Ok(0);
+        };
+        let tx_store = ReadonlyPrefixedStorage::new(PREFIX_TXS, storage);
+        first_retained(&id_store, &tx_store, retention, height, keep)?
+    };
+    if start == 0 {
+        return Ok(0);
+    }
+    let mut pruned: Vec<u64> = Vec::new();
+    {
+        let mut store = PrefixedStorage::multilevel(&[PREFIX_TX_IDS, address.as_slice()], storage);
+        let mut id_store = AppendStoreMut::<u64, _>::attach_or_create(&mut store)?;
+        let len = id_store.len();
+        for pos in 0..start {
+            pruned.push(id_store.get_at(pos)?);
+        }
+        for pos in start..len {
+            let id = id_store.get_at(pos)?;
+            id_store.set_at(pos - start, &id)?;
+        }
+        for _ in 0..start {
+            id_store.pop()?;
+        }
+        // pop only shortens the store, so also remove the vacated slots
+        for pos in len - start..len {
+            id_store.storage().remove(&pos.to_be_bytes());
+        }
+    }
+    for id in pruned {
+        release_tx(storage, id, retention, height)?;
+    }
+    Ok(start)
 }
 
-/// Returns StdResult<Vec<Tx>> of the txs to display
+/// Returns StdResult<()> after noting that a tx was removed from an address' history, and
+/// deleting it if it expired or no address' history still includes it
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `tx_id` - the id of the removed tx
+/// * `retention` - a reference to the transaction history retention limits
+/// * `height` - the current block height
+fn release_tx<S: Storage>(
+    storage: &mut S,
+    tx_id: u64,
+    retention: &TxRetention,
+    height: u64,
+) -> StdResult<()> {
+    let key = tx_id.to_le_bytes();
+    let tx_store = ReadonlyPrefixedStorage::new(PREFIX_TXS, storage);
+    let tx: StoredTx = match json_may_load(&tx_store, &key)? {
+        Some(tx) => tx,
+        // already deleted when it expired
+        None => return Ok(()),
+    };
+    let expired =
+        matches!(retention.max_blocks, Some(max) if tx.blockheight.saturating_add(max) <= height);
+    let refs_store = ReadonlyPrefixedStorage::new(PREFIX_TX_REFS, storage);
+    let refs: u32 = match may_load(&refs_store, &key)? {
+        Some(r) => r,
+        // first time this tx is pruned, so every participant still has it
+        None => tx.action.participants().len() as u32,
+    };
+    let mut refs_store = PrefixedStorage::new(PREFIX_TX_REFS, storage);
+    if refs > 1 && !expired {
+        save(&mut refs_store, &key, &(refs - 1))
+    } else {
+        remove(&mut refs_store, &key);
+        let mut tx_store = PrefixedStorage::new(PREFIX_TXS, storage);
+        remove(&mut tx_store, &key);
+        Ok(())
+    }
+}
+
+/// Returns StdResult<(Vec<Tx>, u64)> of the txs to display and the total number of txs
+/// involving the address that are within the retention limits.  The total ignores the filter,
+/// because counting the matching txs would mean loading every tx in the history.  The height
+/// filters are found by binary search, but the action and token id filters are checked by
+/// walking back through the history
 ///
 /// # Arguments
 ///
 /// * `api` - a reference to the Api used to convert human and canonical addresses
 /// * `storage` - a reference to the contract's storage
 /// * `address` - a reference to the address whose txs to display
-/// * `page` - page to start displaying
+/// * `page` - page to start displaying.  Ignored if a cursor is given
 /// * `page_size` - number of txs per page
+/// * `cursor` - optional tx id of the last tx already displayed
+/// * `filter` - a reference to the criteria the displayed txs must meet
 pub fn get_txs<A: Api, S: ReadonlyStorage>(
     api: &A,
     storage: &S,
     address: &CanonicalAddr,
     page: u32,
     page_size: u32,
-) -> StdResult<Vec<Tx>> {
+    cursor: Option<u64>,
+    filter: &TxFilter,
+) -> StdResult<(Vec<Tx>, u64)> {
     let id_store =
         ReadonlyPrefixedStorage::multilevel(&[PREFIX_TX_IDS, address.as_slice()], storage);
 
@@ -355,27 +830,71 @@ pub fn get_txs<A: Api, S: ReadonlyStorage>(
     let id_store = if let Some(result) = AppendStore::<u64, _>::attach(&id_store) {
         result?
     } else {
-        return Ok(vec![]);
+        return Ok((vec![], 0));
     };
     // access tx storage
     let tx_store = ReadonlyPrefixedStorage::new(PREFIX_TXS, storage);
-    // Take `page_size` txs starting from the latest tx, potentially skipping `page * page_size`
-    // txs from the start.
-    let txs: StdResult<Vec<Tx>> = id_store
-        .iter()
-        .rev()
-        .skip((page * page_size) as usize)
-        .take(page_size as usize)
-        .map(|id| {
-            id.map(|id| {
-                json_load(&tx_store, &id.to_le_bytes())
-                    .and_then(|tx: StoredTx| tx.into_humanized(api))
-            })
-            .and_then(|x| x)
-        })
-        .collect();
-
-    txs
+    // hide any txs outside the retention limits that have not been pruned yet
+    let config = load_config(storage)?;
+    let height = may_load::<BlockInfo, _>(storage, BLOCK_KEY)?.map_or(0, |b| b.height);
+    let start = first_retained(&id_store, &tx_store, &config.tx_retention, height, None)?;
+    let seed: Vec<u8> = load(storage, PRNG_SEED_KEY)?;
+    // private tx records can only be matched by the hash of the token id
+    let mut filter = filter.clone();
+    if config.private_tx_records {
+        filter.token_id = filter.token_id.map(|id| hash_token_id(&seed, &id));
+    }
+    let total = (id_store.len() - start) as u64;
+    // tx ids are appended in increasing order, so binary search for the position of the first
+    // tx that is not older than the cursor
+    let mut end = id_store.len();
+    let mut skip = page * page_size;
+    if let Some(last_id) = cursor {
+        let mut low = start;
+        while low < end {
+            let mid = low + (end - low) / 2;
+            if id_store.get_at(mid)? < last_id {
+                low = mid + 1;
+            } else {
+                end = mid;
+            }
+        }
+        skip = 0;
+    }
+    // likewise binary search for the position after the last tx not above the max height
+    if let Some(max) = filter.max_height {
+        let mut low = start;
+        while low < end {
+            let mid = low + (end - low) / 2;
+            let id = id_store.get_at(mid)?;
+            let tx: StoredTx = json_load(&tx_store, &id.to_le_bytes())?;
+            if tx.blockheight <= max {
+                low = mid + 1;
+            } else {
+                end = mid;
+            }
+        }
+    }
+    let mut txs: Vec<Tx> = Vec::new();
+    // walk back from the latest tx, skipping any that do not match the filter
+    while end > start && txs.len() < page_size as usize {
+        end -= 1;
+        let id = id_store.get_at(end)?;
+        let tx: StoredTx = json_load(&tx_store, &id.to_le_bytes())?;
+        // block heights never decrease as tx ids increase, so the remaining txs are too old
+        if matches!(filter.min_height, Some(min) if tx.blockheight < min) {
+            break;
+        }
+        if !tx.matches(&filter) {
+            continue;
+        }
+        if skip > 0 {
+            skip -= 1;
+            continue;
+        }
+        txs.push(tx.into_humanized(api, address, &seed)?);
+    }
+    Ok((txs, total))
 }
 
 /// permission to view token info/transfer tokens
@@ -387,6 +906,56 @@ pub struct Permission {
     pub expirations: [Option<Expiration>; 3],
 }
 
+impl Permission {
+    /// Returns StdResult<()> after appending the compact binary encoding of this permission
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - a mutable reference to the buffer being encoded into
+    pub fn encode(&self, buf: &mut Vec<u8>) -> StdResult<()> {
+        encode_addr(&self.address, buf)?;
+        for exp in self.expirations.iter() {
+            match exp {
+                None => buf.push(EXP_NONE),
+                Some(Expiration::AtHeight(height)) => {
+                    buf.push(EXP_AT_HEIGHT);
+                    buf.extend_from_slice(&height.to_le_bytes());
+                }
+                Some(Expiration::AtTime(time)) => {
+                    buf.push(EXP_AT_TIME);
+                    buf.extend_from_slice(&time.to_le_bytes());
+                }
+                Some(Expiration::Never) => buf.push(EXP_NEVER),
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns StdResult<Permission> decoded from the front of a compact binary encoding,
+    /// advancing the slice past the bytes read
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - a mutable reference to the remaining encoded bytes
+    pub fn decode(bytes: &mut &[u8]) -> StdResult<Self> {
+        let address = decode_addr(bytes)?;
+        let mut expirations = [None; 3];
+        for exp in expirations.iter_mut() {
+            *exp = match take_bytes(bytes, 1)?[0] {
+                EXP_NONE => None,
+                EXP_AT_HEIGHT => Some(Expiration::AtHeight(decode_u64(bytes)?)),
+                EXP_AT_TIME => Some(Expiration::AtTime(decode_u64(bytes)?)),
+                EXP_NEVER => Some(Expiration::Never),
+                _ => return Err(corrupt_record()),
+            };
+        }
+        Ok(Permission {
+            address,
+            expirations,
+        })
+    }
+}
+
 /// permission types
 #[derive(Serialize, Deserialize, Debug)]
 pub enum PermissionType {
@@ -411,7 +980,7 @@ impl PermissionType {
     }
 }
 
-/// list of one owner's tokens authorized to a single address
+/// legacy list of one owner's tokens authorized to a single address
 #[derive(Serialize, Deserialize, Debug)]
 pub struct AuthList {
     /// whitelisted address
@@ -429,6 +998,776 @@ pub struct ReceiveRegistration {
     pub impl_batch: bool,
 }
 
+/// who minted a token and when.  Tokens minted before this was recorded do not have one
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MintInfo {
+    /// address that minted the token
+    pub creator: CanonicalAddr,
+    /// block height of the mint
+    pub height: u64,
+}
+
+/// a named viewing key limited to some queries and optionally some tokens
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScopedKey {
+    /// name the owner uses to list and revoke the key
+    pub name: String,
+    /// hash of the key
+    pub key: [u8; VIEWING_KEY_SIZE],
+    /// queries the key may be used for
+    pub queries: Vec<ScopedQuery>,
+    /// if given, the only tokens the key may be used for
+    pub token_ids: Option<Vec<String>>,
+    /// when the key stops working
+    pub expires: Expiration,
+}
+
+/// a request for a secret to be minted for the requester
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SecretRequest {
+    /// address the secret will be minted for
+    pub requester: CanonicalAddr,
+    /// description of the requested secret
+    pub description: String,
+    /// the only address that may fulfill the request, if any
+    pub responder: Option<CanonicalAddr>,
+    /// when the request can no longer be fulfilled
+    pub expires: Expiration,
+    /// hash of the one-time code
+    pub code: [u8; VIEWING_KEY_SIZE],
+    /// id of the token minted when the request was fulfilled
+    pub token_id: Option<String>,
+}
+
+/// Returns StdResult<u32> of the number of tokens the contract controls
+///
+/// contracts deployed before the count was stored only have the legacy list of tokens, so
+/// its length is used until the count is first saved
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+pub fn get_token_count<S: ReadonlyStorage>(storage: &S) -> StdResult<u32> {
+    if let Some(count) = may_load::<u32, _>(storage, TOKEN_COUNT_KEY)? {
+        return Ok(count);
+    }
+    let legacy: Option<HashSet<String>> = may_load(storage, TOKENS_KEY)?;
+    Ok(legacy.map_or(0, |tokens| tokens.len() as u32))
+}
+
+/// Returns StdResult<()> after saving the number of tokens the contract controls.  Also
+/// removes the legacy list of tokens if it is still present
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `count` - the number of tokens the contract controls
+pub fn set_token_count<S: Storage>(storage: &mut S, count: u32) -> StdResult<()> {
+    remove(storage, TOKENS_KEY);
+    save(storage, TOKEN_COUNT_KEY, &count)
+}
+
+/// Returns StdResult<()> after adding a token to its owner's inventory
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `owner` - a reference to the token owner's address
+/// * `idx` - the token's index
+pub fn add_to_inventory<S: Storage>(
+    storage: &mut S,
+    owner: &CanonicalAddr,
+    idx: u32,
+) -> StdResult<()> {
+    migrate_legacy_inventory(storage, owner)?;
+    push_indexed(
+        storage,
+        (PREFIX_INVENTORY, PREFIX_INVENTORY_POS),
+        owner,
+        &idx,
+        &idx.to_le_bytes(),
+    )
+}
+
+/// Returns StdResult<()> after removing a token from its owner's inventory.  The last token
+/// of the inventory is moved into the removed token's position
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `owner` - a reference to the token owner's address
+/// * `idx` - the token's index
+pub fn remove_from_inventory<S: Storage>(
+    storage: &mut S,
+    owner: &CanonicalAddr,
+    idx: u32,
+) -> StdResult<()> {
+    migrate_legacy_inventory(storage, owner)?;
+    swap_remove_indexed(
+        storage,
+        (PREFIX_INVENTORY, PREFIX_INVENTORY_POS),
+        owner,
+        &idx.to_le_bytes(),
+        |i: &u32| i.to_le_bytes().to_vec(),
+    )
+}
+
+/// Returns StdResult<Option<u32>> of the position of a token in its owner's inventory, or
+/// None if the owner does not have the token
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `owner` - a reference to the token owner's address
+/// * `idx` - the token's index
+pub fn get_inventory_pos<S: ReadonlyStorage>(
+    storage: &S,
+    owner: &CanonicalAddr,
+    idx: u32,
+) -> StdResult<Option<u32>> {
+    if let Some(legacy) = may_load_legacy_inventory(storage, owner)? {
+        return Ok(legacy.iter().position(|i| *i == idx).map(|p| p as u32));
+    }
+    let pos_store =
+        ReadonlyPrefixedStorage::multilevel(&[PREFIX_INVENTORY_POS, owner.as_slice()], storage);
+    may_load(&pos_store, &idx.to_le_bytes())
+}
+
+/// Returns StdResult<Vec<u32>> of up to `count` token indices from an owner's inventory,
+/// beginning at position `start`
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `owner` - a reference to the token owner's address
+/// * `start` - position in the inventory to start from
+/// * `count` - max number of token indices to return
+pub fn get_inventory<S: ReadonlyStorage>(
+    storage: &S,
+    owner: &CanonicalAddr,
+    start: u32,
+    count: u32,
+) -> StdResult<Vec<u32>> {
+    if let Some(legacy) = may_load_legacy_inventory(storage, owner)? {
+        return Ok(legacy
+            .into_iter()
+            .skip(start as usize)
+            .take(count as usize)
+            .collect());
+    }
+    let store = ReadonlyPrefixedStorage::multilevel(&[PREFIX_INVENTORY, owner.as_slice()], storage);
+    // if the owner has never had an inventory, return an empty list
+    let store = if let Some(result) = AppendStore::<u32, _>::attach(&store) {
+        result?
+    } else {
+        return Ok(vec![]);
+    };
+    store
+        .iter()
+        .skip(start as usize)
+        .take(count as usize)
+        .collect()
+}
+
+/// Returns StdResult<()> after adding a token minted for a recipient to the recipient's inbox
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `recipient` - a reference to the recipient's address
+/// * `idx` - the token's index
+pub fn add_to_inbox<S: Storage>(
+    storage: &mut S,
+    recipient: &CanonicalAddr,
+    idx: u32,
+) -> StdResult<()> {
+    push_indexed(
+        storage,
+        (PREFIX_INBOX, PREFIX_INBOX_POS),
+        recipient,
+        &idx,
+        &idx.to_le_bytes(),
+    )
+}
+
+/// Returns StdResult<()> after removing a token from an address' inbox.  Does nothing if the
+/// token is not in the inbox
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `recipient` - a reference to the recipient's address
+/// * `idx` - the token's index
+pub fn remove_from_inbox<S: Storage>(
+    storage: &mut S,
+    recipient: &CanonicalAddr,
+    idx: u32,
+) -> StdResult<()> {
+    swap_remove_indexed(
+        storage,
+        (PREFIX_INBOX, PREFIX_INBOX_POS),
+        recipient,
+        &idx.to_le_bytes(),
+        |i: &u32| i.to_le_bytes().to_vec(),
+    )
+}
+
+/// Returns StdResult<Option<u32>> of the position of a token in an address' inbox, or None
+/// if the token is not in the inbox
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `recipient` - a reference to the recipient's address
+/// * `idx` - the token's index
+pub fn get_inbox_pos<S: ReadonlyStorage>(
+    storage: &S,
+    recipient: &CanonicalAddr,
+    idx: u32,
+) -> StdResult<Option<u32>> {
+    let pos_store =
+        ReadonlyPrefixedStorage::multilevel(&[PREFIX_INBOX_POS, recipient.as_slice()], storage);
+    may_load(&pos_store, &idx.to_le_bytes())
+}
+
+/// Returns StdResult<Vec<u32>> of up to `count` token indices from an address' inbox,
+/// beginning at position `start`
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `recipient` - a reference to the recipient's address
+/// * `start` - position in the inbox to start from
+/// * `count` - max number of token indices to return
+pub fn get_inbox<S: ReadonlyStorage>(
+    storage: &S,
+    recipient: &CanonicalAddr,
+    start: u32,
+    count: u32,
+) -> StdResult<Vec<u32>> {
+    let store = ReadonlyPrefixedStorage::multilevel(&[PREFIX_INBOX, recipient.as_slice()], storage);
+    // if nothing was ever minted for the address, return an empty list
+    let store = if let Some(result) = AppendStore::<u32, _>::attach(&store) {
+        result?
+    } else {
+        return Ok(vec![]);
+    };
+    store
+        .iter()
+        .skip(start as usize)
+        .take(count as usize)
+        .collect()
+}
+
+/// Returns StdResult<()> after adding a secret request to the list of requests its
+/// requester created
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `requester` - a reference to the requester's address
+/// * `request_id` - the request's id
+pub fn add_secret_request<S: Storage>(
+    storage: &mut S,
+    requester: &CanonicalAddr,
+    request_id: u32,
+) -> StdResult<()> {
+    let mut store =
+        PrefixedStorage::multilevel(&[PREFIX_REQUEST_IDS, requester.as_slice()], storage);
+    let mut store = AppendStoreMut::<u32, _>::attach_or_create(&mut store)?;
+    store.push(&request_id)
+}
+
+/// Returns StdResult<(Vec<u32>, u32)> of a page of the ids of the secret requests an address
+/// created, most recent first, and the total number of requests it created
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `requester` - a reference to the requester's address
+/// * `page` - page to display
+/// * `page_size` - number of request ids per page
+pub fn get_secret_requests<S: ReadonlyStorage>(
+    storage: &S,
+    requester: &CanonicalAddr,
+    page: u32,
+    page_size: u32,
+) -> StdResult<(Vec<u32>, u32)> {
+    let store =
+        ReadonlyPrefixedStorage::multilevel(&[PREFIX_REQUEST_IDS, requester.as_slice()], storage);
+    // if the address never created a request, return an empty list
+    let store = if let Some(result) = AppendStore::<u32, _>::attach(&store) {
+        result?
+    } else {
+        return Ok((vec![], 0));
+    };
+    let ids = store
+        .iter()
+        .rev()
+        .skip(page.saturating_mul(page_size) as usize)
+        .take(page_size as usize)
+        .collect::<StdResult<Vec<u32>>>()?;
+    Ok((ids, store.len()))
+}
+
+/// Returns StdResult<u32> of the number of tokens in an owner's inventory
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `owner` - a reference to the token owner's address
+pub fn get_inventory_len<S: ReadonlyStorage>(storage: &S, owner: &CanonicalAddr) -> StdResult<u32> {
+    if let Some(legacy) = may_load_legacy_inventory(storage, owner)? {
+        return Ok(legacy.len() as u32);
+    }
+    let store = ReadonlyPrefixedStorage::multilevel(&[PREFIX_INVENTORY, owner.as_slice()], storage);
+    AppendStore::<u32, _>::attach(&store).map_or(Ok(0), |result| result.map(|s| s.len()))
+}
+
+/// Returns StdResult<()> after moving an owner's legacy list of tokens into their indexed
+/// inventory.  Does nothing if the owner does not have a legacy list
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `owner` - a reference to the token owner's address
+pub fn migrate_legacy_inventory<S: Storage>(
+    storage: &mut S,
+    owner: &CanonicalAddr,
+) -> StdResult<()> {
+    if let Some(legacy) = may_load_legacy_inventory(storage, owner)? {
+        let mut owned_store = PrefixedStorage::new(PREFIX_OWNED, storage);
+        remove(&mut owned_store, owner.as_slice());
+        for idx in legacy {
+            add_to_inventory(storage, owner, idx)?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns StdResult<Option<Vec<u32>>> of an owner's legacy list of tokens sorted in mint
+/// order, or None if the owner does not have a legacy list
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `owner` - a reference to the token owner's address
+fn may_load_legacy_inventory<S: ReadonlyStorage>(
+    storage: &S,
+    owner: &CanonicalAddr,
+) -> StdResult<Option<Vec<u32>>> {
+    let owned_store = ReadonlyPrefixedStorage::new(PREFIX_OWNED, storage);
+    let may_owned: Option<HashSet<u32>> = may_load(&owned_store, owner.as_slice())?;
+    Ok(may_owned.map(|owned| {
+        let mut list: Vec<u32> = owned.into_iter().collect();
+        list.sort_unstable();
+        list
+    }))
+}
+
+/// Returns StdResult<()> after appending an item to one of an owner's indexed lists and
+/// saving the item's position
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `prefixes` - the storage prefixes of the list and of the item positions
+/// * `owner` - a reference to the address that owns the list
+/// * `item` - a reference to the item to add
+/// * `pos_key` - the key the item's position is saved under
+fn push_indexed<T: Serialize + DeserializeOwned, S: Storage>(
+    storage: &mut S,
+    prefixes: (&[u8], &[u8]),
+    owner: &CanonicalAddr,
+    item: &T,
+    pos_key: &[u8],
+) -> StdResult<()> {
+    let (list_prefix, pos_prefix) = prefixes;
+    let pos = {
+        let mut store = PrefixedStorage::multilevel(&[list_prefix, owner.as_slice()], storage);
+        let mut store = AppendStoreMut::attach_or_create(&mut store)?;
+        store.push(item)?;
+        store.len() - 1
+    };
+    let mut pos_store = PrefixedStorage::multilevel(&[pos_prefix, owner.as_slice()], storage);
+    save(&mut pos_store, pos_key, &pos)
+}
+
+/// Returns StdResult<()> after removing an item from one of an owner's indexed lists.  The
+/// last item of the list is moved into the removed item's position.  Does nothing if the
+/// item is not in the list
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `prefixes` - the storage prefixes of the list and of the item positions
+/// * `owner` - a reference to the address that owns the list
+/// * `pos_key` - the key the item's position is saved under
+/// * `key_of` - returns the key an item's position is saved under
+fn swap_remove_indexed<T, S, F>(
+    storage: &mut S,
+    prefixes: (&[u8], &[u8]),
+    owner: &CanonicalAddr,
+    pos_key: &[u8],
+    key_of: F,
+) -> StdResult<()>
+where
+    T: Serialize + DeserializeOwned,
+    S: Storage,
+    F: Fn(&T) -> Vec<u8>,
+{
+    let (list_prefix, pos_prefix) = prefixes;
+    let mut pos_store = PrefixedStorage::multilevel(&[pos_prefix, owner.as_slice()], storage);
+    let pos: u32 = match may_load(&pos_store, pos_key)? {
+        Some(p) => p,
+        None => return Ok(()),
+    };
+    remove(&mut pos_store, pos_key);
+    let moved = {
+        let mut store = PrefixedStorage::multilevel(&[list_prefix, owner.as_slice()], storage);
+        let mut store = AppendStoreMut::<T, _>::attach_or_create(&mut store)?;
+        let last = store.pop()?;
+        let last_key = key_of(&last);
+        if last_key != pos_key {
+            store.set_at(pos, &last)?;
+            Some(last_key)
+        } else {
+            None
+        }
+    };
+    if let Some(last_key) = moved {
+        let mut pos_store = PrefixedStorage::multilevel(&[pos_prefix, owner.as_slice()], storage);
+        save(&mut pos_store, &last_key, &pos)?;
+    }
+    Ok(())
+}
+
+/// Returns StdResult<()> after making sure an address is only in one of an owner's indexed
+/// lists of addresses while it still has approvals
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `prefixes` - the storage prefixes of the list and of the address positions
+/// * `owner` - a reference to the owner's address
+/// * `address` - a reference to the approved address
+/// * `has_approval` - true if the address still has approvals
+fn sync_address_index<S: Storage>(
+    storage: &mut S,
+    prefixes: (&[u8], &[u8]),
+    owner: &CanonicalAddr,
+    address: &CanonicalAddr,
+    has_approval: bool,
+) -> StdResult<()> {
+    let pos_store = ReadonlyPrefixedStorage::multilevel(&[prefixes.1, owner.as_slice()], storage);
+    let listed = may_load::<u32, _>(&pos_store, address.as_slice())?.is_some();
+    if has_approval && !listed {
+        push_indexed(storage, prefixes, owner, address, address.as_slice())?;
+    } else if !has_approval && listed {
+        swap_remove_indexed(
+            storage,
+            prefixes,
+            owner,
+            address.as_slice(),
+            |a: &CanonicalAddr| a.as_slice().to_vec(),
+        )?;
+    }
+    Ok(())
+}
+
+/// Returns StdResult<Vec<CanonicalAddr>> of every address in one of an owner's indexed lists
+/// of addresses
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `list_prefix` - the storage prefix of the list
+/// * `owner` - a reference to the owner's address
+fn get_addresses<S: ReadonlyStorage>(
+    storage: &S,
+    list_prefix: &[u8],
+    owner: &CanonicalAddr,
+) -> StdResult<Vec<CanonicalAddr>> {
+    let store = ReadonlyPrefixedStorage::multilevel(&[list_prefix, owner.as_slice()], storage);
+    // if the owner has never had a list, return an empty one
+    let store = if let Some(result) = AppendStore::<CanonicalAddr, _>::attach(&store) {
+        result?
+    } else {
+        return Ok(vec![]);
+    };
+    store.iter().collect()
+}
+
+/// Returns Vec<u8> of the key an approval for an address and permission type is saved under
+///
+/// # Arguments
+///
+/// * `address` - a reference to the approved address
+/// * `perm_type` - the permission type represented as usize
+fn approval_key(address: &CanonicalAddr, perm_type: usize) -> Vec<u8> {
+    let mut key = vec![perm_type as u8];
+    key.extend_from_slice(address.as_slice());
+    key
+}
+
+/// Returns StdResult<Option<Expiration>> of the expiration of the "all" permission an owner
+/// has given an address for a permission type, or None if there is no such permission
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `owner` - a reference to the owner's address
+/// * `address` - a reference to the permitted address
+/// * `perm_type` - the permission type represented as usize
+pub fn may_load_all_perm<S: ReadonlyStorage>(
+    storage: &S,
+    owner: &CanonicalAddr,
+    address: &CanonicalAddr,
+    perm_type: usize,
+) -> StdResult<Option<Expiration>> {
+    if let Some(legacy) = may_load_legacy_all_perms(storage, owner)? {
+        return Ok(legacy
+            .iter()
+            .find(|p| p.address == *address)
+            .and_then(|p| p.expirations[perm_type]));
+    }
+    let store = ReadonlyPrefixedStorage::multilevel(&[PREFIX_ALL_PERM, owner.as_slice()], storage);
+    may_load(&store, &approval_key(address, perm_type))
+}
+
+/// Returns StdResult<()> after setting or removing the "all" permission an owner has given an
+/// address for a permission type
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `owner` - a reference to the owner's address
+/// * `address` - a reference to the permitted address
+/// * `perm_type` - the permission type represented as usize
+/// * `expiration` - the permission's expiration, or None to remove the permission
+pub fn set_all_perm<S: Storage>(
+    storage: &mut S,
+    owner: &CanonicalAddr,
+    address: &CanonicalAddr,
+    perm_type: usize,
+    expiration: Option<Expiration>,
+) -> StdResult<()> {
+    migrate_legacy_approvals(storage, owner)?;
+    let has_perm = {
+        let mut store = PrefixedStorage::multilevel(&[PREFIX_ALL_PERM, owner.as_slice()], storage);
+        let key = approval_key(address, perm_type);
+        if let Some(exp) = expiration {
+            save(&mut store, &key, &exp)?;
+        } else {
+            remove(&mut store, &key);
+        }
+        (0..PermissionType::ViewOwner.num_types())
+            .any(|i| store.get(&approval_key(address, i)).is_some())
+    };
+    // keep the owner's list of addresses with "all" permissions in sync
+    sync_address_index(
+        storage,
+        (PREFIX_ALL_PERM_ADDRS, PREFIX_ALL_PERM_POS),
+        owner,
+        address,
+        has_perm,
+    )
+}
+
+/// Returns StdResult<Vec<Permission>> of every "all" permission an owner has given
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `owner` - a reference to the owner's address
+pub fn get_all_perms<S: ReadonlyStorage>(
+    storage: &S,
+    owner: &CanonicalAddr,
+) -> StdResult<Vec<Permission>> {
+    if let Some(legacy) = may_load_legacy_all_perms(storage, owner)? {
+        return Ok(legacy);
+    }
+    let store = ReadonlyPrefixedStorage::multilevel(&[PREFIX_ALL_PERM, owner.as_slice()], storage);
+    let mut perms = Vec::new();
+    for address in get_addresses(storage, PREFIX_ALL_PERM_ADDRS, owner)? {
+        let mut expirations = [None; 3];
+        for (i, exp) in expirations.iter_mut().enumerate() {
+            *exp = may_load(&store, &approval_key(&address, i))?;
+        }
+        perms.push(Permission {
+            address,
+            expirations,
+        });
+    }
+    Ok(perms)
+}
+
+/// Returns StdResult<Vec<u32>> of the sorted indices of an owner's tokens that an address
+/// has been individually permitted for a permission type
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `owner` - a reference to the owner's address
+/// * `address` - a reference to the permitted address
+/// * `perm_type` - the permission type represented as usize
+pub fn get_auth_tokens<S: ReadonlyStorage>(
+    storage: &S,
+    owner: &CanonicalAddr,
+    address: &CanonicalAddr,
+    perm_type: usize,
+) -> StdResult<Vec<u32>> {
+    if let Some(legacy) = may_load_legacy_auth_lists(storage, owner)? {
+        let mut tokens = legacy
+            .into_iter()
+            .find(|a| a.address == *address)
+            .map_or_else(Vec::new, |a| a.tokens[perm_type].clone());
+        tokens.sort_unstable();
+        return Ok(tokens);
+    }
+    let store =
+        ReadonlyPrefixedStorage::multilevel(&[PREFIX_AUTH_TOKENS, owner.as_slice()], storage);
+    Ok(may_load(&store, &approval_key(address, perm_type))?.unwrap_or_else(Vec::new))
+}
+
+/// Returns StdResult<()> after saving the sorted indices of an owner's tokens that an address
+/// has been individually permitted for a permission type
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `owner` - a reference to the owner's address
+/// * `address` - a reference to the permitted address
+/// * `perm_type` - the permission type represented as usize
+/// * `tokens` - the sorted token indices
+pub fn set_auth_tokens<S: Storage>(
+    storage: &mut S,
+    owner: &CanonicalAddr,
+    address: &CanonicalAddr,
+    perm_type: usize,
+    tokens: &[u32],
+) -> StdResult<()> {
+    migrate_legacy_approvals(storage, owner)?;
+    let has_tokens = {
+        let mut store =
+            PrefixedStorage::multilevel(&[PREFIX_AUTH_TOKENS, owner.as_slice()], storage);
+        let key = approval_key(address, perm_type);
+        if tokens.is_empty() {
+            remove(&mut store, &key);
+        } else {
+            save(&mut store, &key, &tokens)?;
+        }
+        (0..PermissionType::ViewOwner.num_types())
+            .any(|i| store.get(&approval_key(address, i)).is_some())
+    };
+    // keep the owner's list of addresses with token permissions in sync
+    sync_address_index(
+        storage,
+        (PREFIX_AUTH_ADDRS, PREFIX_AUTH_POS),
+        owner,
+        address,
+        has_tokens,
+    )
+}
+
+/// Returns StdResult<Vec<AuthList>> of the tokens an owner has individually permitted to
+/// each address
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `owner` - a reference to the owner's address
+pub fn get_auth_lists<S: ReadonlyStorage>(
+    storage: &S,
+    owner: &CanonicalAddr,
+) -> StdResult<Vec<AuthList>> {
+    if let Some(mut legacy) = may_load_legacy_auth_lists(storage, owner)? {
+        for auth in legacy.iter_mut() {
+            for tokens in auth.tokens.iter_mut() {
+                tokens.sort_unstable();
+            }
+        }
+        return Ok(legacy);
+    }
+    let mut lists = Vec::new();
+    for address in get_addresses(storage, PREFIX_AUTH_ADDRS, owner)? {
+        let tokens = [
+            get_auth_tokens(storage, owner, &address, 0)?,
+            get_auth_tokens(storage, owner, &address, 1)?,
+            get_auth_tokens(storage, owner, &address, 2)?,
+        ];
+        lists.push(AuthList { address, tokens });
+    }
+    Ok(lists)
+}
+
+/// Returns StdResult<()> after moving an owner's legacy lists of "all" permissions and
+/// permitted tokens into the keyed approval storage.  Does nothing if the owner does not have
+/// legacy lists
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `owner` - a reference to the owner's address
+pub fn migrate_legacy_approvals<S: Storage>(
+    storage: &mut S,
+    owner: &CanonicalAddr,
+) -> StdResult<()> {
+    if let Some(legacy) = may_load_legacy_all_perms(storage, owner)? {
+        let mut all_store = PrefixedStorage::new(PREFIX_ALL_PERMISSIONS, storage);
+        remove(&mut all_store, owner.as_slice());
+        for perm in legacy {
+            for (i, exp) in perm.expirations.iter().enumerate() {
+                if exp.is_some() {
+                    set_all_perm(storage, owner, &perm.address, i, *exp)?;
+                }
+            }
+        }
+    }
+    if let Some(legacy) = may_load_legacy_auth_lists(storage, owner)? {
+        let mut auth_store = PrefixedStorage::new(PREFIX_AUTHLIST, storage);
+        remove(&mut auth_store, owner.as_slice());
+        for auth in legacy {
+            for (i, tokens) in auth.tokens.iter().enumerate() {
+                let mut tokens = tokens.clone();
+                tokens.sort_unstable();
+                set_auth_tokens(storage, owner, &auth.address, i, &tokens)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns StdResult<Option<Vec<Permission>>> of an owner's legacy list of "all" permissions,
+/// or None if the owner does not have a legacy list
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `owner` - a reference to the owner's address
+fn may_load_legacy_all_perms<S: ReadonlyStorage>(
+    storage: &S,
+    owner: &CanonicalAddr,
+) -> StdResult<Option<Vec<Permission>>> {
+    let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, storage);
+    json_may_load(&all_store, owner.as_slice())
+}
+
+/// Returns StdResult<Option<Vec<AuthList>>> of an owner's legacy lists of permitted tokens,
+/// or None if the owner does not have legacy lists
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `owner` - a reference to the owner's address
+fn may_load_legacy_auth_lists<S: ReadonlyStorage>(
+    storage: &S,
+    owner: &CanonicalAddr,
+) -> StdResult<Option<Vec<AuthList>>> {
+    let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, storage);
+    may_load(&auth_store, owner.as_slice())
+}
+
 /// Returns StdResult<()> resulting from saving an item to storage
 ///
 /// # Arguments
@@ -483,6 +1822,106 @@ pub fn may_load<T: DeserializeOwned, S: ReadonlyStorage>(
     }
 }
 
+/// Returns StdResult<()> resulting from saving a token with its compact binary encoding
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the storage this token should go to
+/// * `key` - a byte slice representing the key to access the stored token
+/// * `token` - a reference to the token to store
+pub fn save_token<S: Storage>(storage: &mut S, key: &[u8], token: &Token) -> StdResult<()> {
+    storage.set(key, &token.to_bytes()?);
+    Ok(())
+}
+
+/// Returns StdResult<Token> from retrieving the token with the specified key.  Returns a
+/// StdError::NotFound if there is no token with that key
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the storage this token is in
+/// * `key` - a byte slice representing the key that accesses the stored token
+pub fn load_token<S: ReadonlyStorage>(storage: &S, key: &[u8]) -> StdResult<Token> {
+    may_load_token(storage, key)?.ok_or_else(|| StdError::not_found(type_name::<Token>()))
+}
+
+/// Returns StdResult<Option<Token>> from retrieving the token with the specified key.  Tokens
+/// saved before the compact encoding was introduced are read as Json.  Returns Ok(None) if
+/// there is no token with that key
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the storage this token is in
+/// * `key` - a byte slice representing the key that accesses the stored token
+pub fn may_load_token<S: ReadonlyStorage>(storage: &S, key: &[u8]) -> StdResult<Option<Token>> {
+    match storage.get(key) {
+        // a Json object always starts with an open brace, which is never a valid format byte
+        Some(value) if value.first() == Some(&b'{') => Json::deserialize(&value).map(Some),
+        Some(value) => Token::from_bytes(&value).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Returns StdResult<()> after appending the compact binary encoding of an address
+///
+/// # Arguments
+///
+/// * `address` - a reference to the address to encode
+/// * `buf` - a mutable reference to the buffer being encoded into
+pub fn encode_addr(address: &CanonicalAddr, buf: &mut Vec<u8>) -> StdResult<()> {
+    let len = address.len();
+    if len > u8::MAX as usize {
+        return Err(StdError::generic_err("Address is too long to encode"));
+    }
+    buf.push(len as u8);
+    buf.extend_from_slice(address.as_slice());
+    Ok(())
+}
+
+/// Returns StdResult<CanonicalAddr> decoded from the front of a compact binary encoding,
+/// advancing the slice past the bytes read
+///
+/// # Arguments
+///
+/// * `bytes` - a mutable reference to the remaining encoded bytes
+pub fn decode_addr(bytes: &mut &[u8]) -> StdResult<CanonicalAddr> {
+    let len = take_bytes(bytes, 1)?[0] as usize;
+    Ok(CanonicalAddr::from(take_bytes(bytes, len)?))
+}
+
+/// Returns StdResult<u64> decoded from the front of a compact binary encoding, advancing the
+/// slice past the bytes read
+///
+/// # Arguments
+///
+/// * `bytes` - a mutable reference to the remaining encoded bytes
+pub fn decode_u64(bytes: &mut &[u8]) -> StdResult<u64> {
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(take_bytes(bytes, 8)?);
+    Ok(u64::from_le_bytes(buf))
+}
+
+/// Returns StdResult<&[u8]> of the next len bytes of a compact binary encoding, advancing the
+/// slice past them
+///
+/// # Arguments
+///
+/// * `bytes` - a mutable reference to the remaining encoded bytes
+/// * `len` - number of bytes to take
+pub fn take_bytes<'a>(bytes: &mut &'a [u8], len: usize) -> StdResult<&'a [u8]> {
+    if bytes.len() < len {
+        return Err(corrupt_record());
+    }
+    let (taken, rest) = bytes.split_at(len);
+    *bytes = rest;
+    Ok(taken)
+}
+
+/// Returns StdError for a stored record that can not be decoded
+pub fn corrupt_record() -> StdError {
+    StdError::generic_err("Stored record is corrupted")
+}
+
 /// Returns StdResult<()> resulting from saving an item to storage using Json (de)serialization
 /// because bincode2 annoyingly uses a float op when deserializing an enum
 ///
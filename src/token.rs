@@ -1,9 +1,18 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::CanonicalAddr;
+use cosmwasm_std::{CanonicalAddr, StdResult};
 
-use crate::state::Permission;
+use crate::state::{corrupt_record, decode_addr, encode_addr, take_bytes, Permission};
+
+/// format byte that starts the compact binary encoding of a token
+pub const TOKEN_FORMAT_COMPACT: u8 = 1;
+/// flag bit set if the token has been unwrapped
+const FLAG_UNWRAPPED: u8 = 1;
+/// flag bit set if the token has a public teaser
+const FLAG_PUBLIC_TEASER: u8 = 1 << 1;
+/// flag bit set if only the owner may burn the token
+const FLAG_RECIPIENT_ONLY: u8 = 1 << 2;
 
 /// token
 #[derive(Serialize, Deserialize)]
@@ -15,6 +24,68 @@ pub struct Token {
     /// true if this token has been unwrapped.  If sealed metadata is not enabled, all
     /// tokens are considered unwrapped
     pub unwrapped: bool,
+    /// true if the public metadata can be viewed before the token is burned
+    #[serde(default)]
+    pub public_teaser: bool,
+    /// true if the token was transferred to a recipient who is the only address that may
+    /// burn it
+    #[serde(default)]
+    pub recipient_only: bool,
+}
+
+impl Token {
+    /// Returns StdResult<Vec<u8>> of the token's compact binary encoding.  The layout is the
+    /// format byte, a flags byte, the owner, the number of permissions as a little-endian u32,
+    /// and then each permission.  Unlike bincode2, decoding it does not need a float op
+    pub fn to_bytes(&self) -> StdResult<Vec<u8>> {
+        let mut buf = vec![TOKEN_FORMAT_COMPACT, 0];
+        if self.unwrapped {
+            buf[1] |= FLAG_UNWRAPPED;
+        }
+        if self.public_teaser {
+            buf[1] |= FLAG_PUBLIC_TEASER;
+        }
+        if self.recipient_only {
+            buf[1] |= FLAG_RECIPIENT_ONLY;
+        }
+        encode_addr(&self.owner, &mut buf)?;
+        buf.extend_from_slice(&(self.permissions.len() as u32).to_le_bytes());
+        for perm in self.permissions.iter() {
+            perm.encode(&mut buf)?;
+        }
+        Ok(buf)
+    }
+
+    /// Returns StdResult<Token> decoded from its compact binary encoding
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - the encoded token
+    pub fn from_bytes(mut bytes: &[u8]) -> StdResult<Self> {
+        let header = take_bytes(&mut bytes, 2)?;
+        if header[0] != TOKEN_FORMAT_COMPACT {
+            return Err(corrupt_record());
+        }
+        let flags = header[1];
+        let owner = decode_addr(&mut bytes)?;
+        let mut cnt = [0u8; 4];
+        cnt.copy_from_slice(take_bytes(&mut bytes, 4)?);
+        let cnt = u32::from_le_bytes(cnt);
+        let mut permissions = Vec::new();
+        for _ in 0..cnt {
+            permissions.push(Permission::decode(&mut bytes)?);
+        }
+        if !bytes.is_empty() {
+            return Err(corrupt_record());
+        }
+        Ok(Token {
+            owner,
+            permissions,
+            unwrapped: flags & FLAG_UNWRAPPED != 0,
+            public_teaser: flags & FLAG_PUBLIC_TEASER != 0,
+            recipient_only: flags & FLAG_RECIPIENT_ONLY != 0,
+        })
+    }
 }
 
 /// token metadata
@@ -2,13 +2,12 @@
 mod tests {
     // use crate::unittest::helpers::helpers::helpers::*;
     // use crate::unittest::helpers::helpers::helpers::*;
-    use crate::msg::{HandleMsg, ContractStatus, Burn, TxAction};
-    use cosmwasm_std::{HumanAddr, Api};
-    use crate::contract::handle;
-    use cosmwasm_std::testing::{mock_env};
+    use crate::msg::{HandleMsg, HandleAnswer, ContractStatus, Burn, TxAction, TokenData, BurnOutcome, BurnResult, TxFilter, InitConfig, InitMsg};
+    use cosmwasm_std::{HumanAddr, Api, from_binary};
+    use crate::contract::{handle, init};
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
     use crate::token::{Metadata, Token};
-    use crate::state::{PermissionType, load, TOKENS_KEY, PREFIX_MAP_TO_INDEX, may_load, PREFIX_INFOS, json_may_load, PREFIX_PRIV_META, json_load, AuthList, PREFIX_OWNED, PREFIX_AUTHLIST, get_txs, PREFIX_PUB_META, PREFIX_MAP_TO_ID};
-    use std::collections::HashSet;
+    use crate::state::{PermissionType, load, TOKEN_COUNT_KEY, PREFIX_MAP_TO_INDEX, may_load, PREFIX_INFOS, PREFIX_PRIV_META, get_inventory, get_inventory_len, get_auth_lists, get_txs, PREFIX_PUB_META, PREFIX_MAP_TO_ID, load_token, may_load_token};
     use cosmwasm_storage::ReadonlyPrefixedStorage;
     use crate::expiration::Expiration;
     use crate::unittest::helpers::helpers::helpers::*;
@@ -103,13 +102,16 @@ mod tests {
         ];
         let handle_msg = HandleMsg::BatchBurnNft {
             burns,
+            best_effort: None,
             padding: None,
         };
         let handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
         let error = extract_error_msg(handle_result);
-        // because the token no longer exists after burning it, it will say you are not
-        // authorized if supply is private, and token not found if public
-        assert!(error.contains("You are not authorized to perform this action on token NFT6"));
+        // because the token no longer exists after burning it, it will say the token is
+        // unavailable if supply is private, and token not found if public
+        assert!(error.contains(
+            "Token NFT6 does not exist or you are not authorized to access it"
+        ));
     }
 
     #[test]
@@ -163,6 +165,7 @@ mod tests {
 
         let handle_msg = HandleMsg::BatchBurnNft {
             burns,
+            best_effort: None,
             padding: None,
         };
         let result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
@@ -181,28 +184,17 @@ mod tests {
             .api
             .canonical_address(&HumanAddr("alice".to_string()))
             .unwrap();
-        let alice_key = alice_raw.as_slice();
         let bob_raw = deps
             .api
             .canonical_address(&HumanAddr("bob".to_string()))
             .unwrap();
-        let bob_key = bob_raw.as_slice();
         let charlie_raw = deps
             .api
             .canonical_address(&HumanAddr("charlie".to_string()))
             .unwrap();
-        let charlie_key = charlie_raw.as_slice();
         // confirm correct tokens were removed from the maps
-        let tokens: HashSet<String> = load(&deps.storage, TOKENS_KEY).unwrap();
-        assert_eq!(tokens.len(), 3);
-        assert!(!tokens.contains("NFT1"));
-        assert!(tokens.contains("NFT2"));
-        assert!(!tokens.contains("NFT3"));
-        assert!(tokens.contains("NFT4"));
-        assert!(tokens.contains("NFT5"));
-        assert!(!tokens.contains("NFT6"));
-        assert!(!tokens.contains("NFT7"));
-        assert!(!tokens.contains("NFT8"));
+        let count: u32 = load(&deps.storage, TOKEN_COUNT_KEY).unwrap();
+        assert_eq!(count, 3);
         let map2idx = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_INDEX, &deps.storage);
         let index: Option<u32> = may_load(&map2idx, "NFT1".as_bytes()).unwrap();
         assert!(index.is_none());
@@ -239,15 +231,15 @@ mod tests {
         assert!(id.is_none());
         // confirm token infos were deleted from storage
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Option<Token> = json_may_load(&info_store, &tok1_key).unwrap();
+        let token: Option<Token> = may_load_token(&info_store, &tok1_key).unwrap();
         assert!(token.is_none());
-        let token: Option<Token> = json_may_load(&info_store, &tok3_key).unwrap();
+        let token: Option<Token> = may_load_token(&info_store, &tok3_key).unwrap();
         assert!(token.is_none());
-        let token: Option<Token> = json_may_load(&info_store, &tok6_key).unwrap();
+        let token: Option<Token> = may_load_token(&info_store, &tok6_key).unwrap();
         assert!(token.is_none());
-        let token: Option<Token> = json_may_load(&info_store, &tok7_key).unwrap();
+        let token: Option<Token> = may_load_token(&info_store, &tok7_key).unwrap();
         assert!(token.is_none());
-        let token: Option<Token> = json_may_load(&info_store, &tok8_key).unwrap();
+        let token: Option<Token> = may_load_token(&info_store, &tok8_key).unwrap();
         assert!(token.is_none());
         // confirm NFT3 metadata has been deleted from storage
         let priv_store = ReadonlyPrefixedStorage::new(PREFIX_PRIV_META, &deps.storage);
@@ -257,7 +249,7 @@ mod tests {
         let pub_meta: Option<Metadata> = may_load(&pub_store, &tok3_key).unwrap();
         assert!(pub_meta.is_none());
         // confirm NFT2 is intact
-        let token: Token = json_load(&info_store, &tok2_key).unwrap();
+        let token: Token = load_token(&info_store, &tok2_key).unwrap();
         assert_eq!(token.permissions.len(), 1);
         let charlie_tok_perm = token
             .permissions
@@ -273,23 +265,19 @@ mod tests {
         assert_eq!(token.owner, alice_raw);
         assert!(!token.unwrapped);
         // confirm owner lists are correct
-        let owned_store = ReadonlyPrefixedStorage::new(PREFIX_OWNED, &deps.storage);
         // alice only owns NFT2
-        let alice_owns: HashSet<u32> = load(&owned_store, alice_key).unwrap();
-        assert_eq!(alice_owns.len(), 1);
-        assert!(alice_owns.contains(&1u32));
+        let alice_owns = get_inventory(&deps.storage, &alice_raw, 0, 30).unwrap();
+        assert_eq!(alice_owns, vec![1u32]);
         // bob owns NFT4 and NFT5
-        let bob_owns: HashSet<u32> = load(&owned_store, bob_key).unwrap();
+        let bob_owns = get_inventory(&deps.storage, &bob_raw, 0, 30).unwrap();
         assert_eq!(bob_owns.len(), 2);
         assert!(bob_owns.contains(&3u32));
         assert!(bob_owns.contains(&4u32));
         // charlie does not own any
-        let charlie_owns: Option<HashSet<u32>> = may_load(&owned_store, charlie_key).unwrap();
-        assert!(charlie_owns.is_none());
+        assert_eq!(get_inventory_len(&deps.storage, &charlie_raw).unwrap(), 0);
         // confirm AuthLists are correct
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
         // alice gave charlie view metadata permission on NFT2
-        let alice_list: Vec<AuthList> = load(&auth_store, alice_key).unwrap();
+        let alice_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
         assert_eq!(alice_list.len(), 1);
         let charlie_auth = alice_list
             .iter()
@@ -300,7 +288,7 @@ mod tests {
         assert!(charlie_auth.tokens[transfer_idx].is_empty());
         assert!(charlie_auth.tokens[view_owner_idx].is_empty());
         // bob gave charlie view owner and view metadata permission on NFT5
-        let bob_list: Vec<AuthList> = load(&auth_store, bob_key).unwrap();
+        let bob_list = get_auth_lists(&deps.storage, &bob_raw).unwrap();
         assert_eq!(bob_list.len(), 2);
         let charlie_auth = bob_list.iter().find(|a| a.address == charlie_raw).unwrap();
         assert_eq!(charlie_auth.tokens[view_meta_idx].len(), 1);
@@ -318,10 +306,10 @@ mod tests {
         assert!(alice_auth.tokens[view_owner_idx].contains(&3u32));
         assert!(alice_auth.tokens[view_owner_idx].contains(&4u32));
         // charlie has no tokens so should not have any AuthLists
-        let charlie_list: Option<Vec<AuthList>> = may_load(&auth_store, charlie_key).unwrap();
-        assert!(charlie_list.is_none());
+        let charlie_list = get_auth_lists(&deps.storage, &charlie_raw).unwrap();
+        assert!(charlie_list.is_empty());
         // confirm one of the txs
-        let txs = get_txs(&deps.api, &deps.storage, &bob_raw, 0, 3).unwrap();
+        let txs = get_txs(&deps.api, &deps.storage, &bob_raw, 0, 3, None, &TxFilter::default()).unwrap().0;
         assert_eq!(txs.len(), 3);
         assert_eq!(txs[0].token_id, "NFT8".to_string());
         assert_eq!(
@@ -334,7 +322,341 @@ mod tests {
         assert_eq!(txs[0].memo, Some("Phew!".to_string()));
         assert_eq!(txs[1].memo, Some("Phew!".to_string()));
         assert_eq!(txs[2].memo, Some("Phew!".to_string()));
-        let tx2 = get_txs(&deps.api, &deps.storage, &charlie_raw, 0, 1).unwrap();
+        let tx2 = get_txs(&deps.api, &deps.storage, &charlie_raw, 0, 1, None, &TxFilter::default()).unwrap().0;
         assert_eq!(txs[0], tx2[0]);
     }
+
+    #[test]
+    fn test_batch_burn_returns_secrets() {
+        let (init_result, mut deps) =
+            init_helper_with_config(false, false, false, false, false, false, true);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+
+        mint_nft3_alice_grant_bob(&mut deps);
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("NFT9".to_string()),
+            owner: Some(HumanAddr("alice".to_string())),
+            private_metadata: Some(Metadata {
+                name: None,
+                description: Some("privmetadata9".to_string()),
+                image: Some("privuri9".to_string()),
+            }),
+            public_metadata: Some(Metadata {
+                name: Some("MyNFT9".to_string()),
+                description: None,
+                image: None,
+            }),
+            public_teaser: None,
+            memo: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(result.is_ok());
+
+        let burns = vec![Burn {
+            token_ids: vec!["NFT3".to_string(), "NFT9".to_string()],
+            memo: None,
+        }];
+        let handle_msg = HandleMsg::BatchBurnNft {
+            burns,
+            best_effort: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        let (secrets, results) = match from_binary(&handle_result.unwrap().data.unwrap()).unwrap()
+        {
+            HandleAnswer::BatchBurnNft { secrets, results } => (secrets, results),
+            _ => panic!("unexpected"),
+        };
+        assert!(results.is_none());
+        assert_eq!(
+            secrets,
+            vec![
+                TokenData {
+                    token_id: "NFT3".to_string(),
+                    name: Some("MyNFT3".to_string()),
+                    description: Some("privmetadata3".to_string()),
+                    image: Some("privuri3".to_string()),
+                },
+                TokenData {
+                    token_id: "NFT9".to_string(),
+                    name: Some("MyNFT9".to_string()),
+                    description: Some("privmetadata9".to_string()),
+                    image: Some("privuri9".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_batch_burn_best_effort() {
+        let (init_result, mut deps) =
+            init_helper_with_config(false, false, true, false, false, false, true);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+
+        mint_nft3_alice_grant_bob(&mut deps);
+        let handle_msg = HandleMsg::Reveal {
+            token_id: "NFT3".to_string(),
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(result.is_ok());
+        // NFT1 keeps its private metadata sealed
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("NFT1".to_string()),
+            owner: Some(HumanAddr("alice".to_string())),
+            private_metadata: Some(Metadata {
+                name: Some("MySealedNFT".to_string()),
+                description: None,
+                image: None,
+            }),
+            public_metadata: None,
+            public_teaser: None,
+            memo: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(result.is_ok());
+
+        // test a failing token aborts the batch without best effort
+        let burns = vec![Burn {
+            token_ids: vec!["NFT1".to_string(), "NFT3".to_string()],
+            memo: None,
+        }];
+        let handle_msg = HandleMsg::BatchBurnNft {
+            burns,
+            best_effort: Some(false),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains(
+            "Token NFT1 does not exist or you are not authorized to access it"
+        ));
+
+        // test best effort skips and reports tokens that can not be burned
+        let burns = vec![
+            Burn {
+                token_ids: vec!["NFT3".to_string(), "NFT1".to_string()],
+                memo: None,
+            },
+            Burn {
+                token_ids: vec!["NFT2".to_string(), "NFT3".to_string()],
+                memo: None,
+            },
+        ];
+        let handle_msg = HandleMsg::BatchBurnNft {
+            burns,
+            best_effort: Some(true),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        let (secrets, results) = match from_binary(&handle_result.unwrap().data.unwrap()).unwrap()
+        {
+            HandleAnswer::BatchBurnNft { secrets, results } => (secrets, results),
+            _ => panic!("unexpected"),
+        };
+        assert_eq!(secrets.len(), 1);
+        assert_eq!(secrets[0].token_id, "NFT3".to_string());
+        assert_eq!(secrets[0].description, Some("privmetadata3".to_string()));
+        assert_eq!(
+            results,
+            Some(vec![
+                BurnResult {
+                    token_id: "NFT3".to_string(),
+                    outcome: BurnOutcome::Revealed,
+                },
+                // private supply does not leak that NFT1 is sealed
+                BurnResult {
+                    token_id: "NFT1".to_string(),
+                    outcome: BurnOutcome::NotPermitted,
+                },
+                // or that NFT2 does not exist
+                BurnResult {
+                    token_id: "NFT2".to_string(),
+                    outcome: BurnOutcome::NotPermitted,
+                },
+                // already burned earlier in the batch
+                BurnResult {
+                    token_id: "NFT3".to_string(),
+                    outcome: BurnOutcome::NotPermitted,
+                },
+            ])
+        );
+        let map2idx = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_INDEX, &deps.storage);
+        let index: Option<u32> = may_load(&map2idx, "NFT3".as_bytes()).unwrap();
+        assert!(index.is_none());
+        let index: Option<u32> = may_load(&map2idx, "NFT1".as_bytes()).unwrap();
+        assert!(index.is_some());
+
+        // test nonexistent tokens are reported when supply is public
+        let (init_result, mut deps) =
+            init_helper_with_config(true, false, false, false, false, false, true);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        mint_nft3_alice_grant_bob(&mut deps);
+        let handle_msg = HandleMsg::BatchBurnNft {
+            burns: vec![Burn {
+                token_ids: vec!["NFT2".to_string(), "NFT3".to_string()],
+                memo: None,
+            }],
+            best_effort: Some(true),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        let results = match from_binary(&handle_result.unwrap().data.unwrap()).unwrap() {
+            HandleAnswer::BatchBurnNft { results, .. } => results,
+            _ => panic!("unexpected"),
+        };
+        assert_eq!(
+            results,
+            Some(vec![
+                BurnResult {
+                    token_id: "NFT2".to_string(),
+                    outcome: BurnOutcome::NotFound,
+                },
+                BurnResult {
+                    token_id: "NFT3".to_string(),
+                    outcome: BurnOutcome::Revealed,
+                },
+            ])
+        );
+    }
+
+    // test each failure is reported by its cause when supply is public
+    #[test]
+    fn test_batch_burn_best_effort_outcomes() {
+        let (init_result, mut deps) =
+            init_helper_with_config(true, false, true, false, false, false, true);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        mint_nft3_alice_grant_bob(&mut deps);
+        let handle_msg = HandleMsg::Reveal {
+            token_id: "NFT3".to_string(),
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(result.is_ok());
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("NFT1".to_string()),
+            owner: Some(HumanAddr("alice".to_string())),
+            private_metadata: Some(Metadata {
+                name: Some("MySealedNFT".to_string()),
+                description: None,
+                image: None,
+            }),
+            public_metadata: None,
+            public_teaser: None,
+            memo: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(result.is_ok());
+        let handle_msg = HandleMsg::BatchBurnNft {
+            burns: vec![Burn {
+                token_ids: vec!["NFT2".to_string(), "NFT1".to_string(), "NFT3".to_string()],
+                memo: None,
+            }],
+            best_effort: Some(true),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        let results = match from_binary(&handle_result.unwrap().data.unwrap()).unwrap() {
+            HandleAnswer::BatchBurnNft { results, .. } => results,
+            _ => panic!("unexpected"),
+        };
+        assert_eq!(
+            results,
+            Some(vec![
+                BurnResult {
+                    token_id: "NFT2".to_string(),
+                    outcome: BurnOutcome::NotFound,
+                },
+                // an existing token is only reported as sealed if it is sealed
+                BurnResult {
+                    token_id: "NFT1".to_string(),
+                    outcome: BurnOutcome::Sealed,
+                },
+                BurnResult {
+                    token_id: "NFT3".to_string(),
+                    outcome: BurnOutcome::Revealed,
+                },
+            ])
+        );
+
+        // test a token the sender may not burn is not reported as sealed
+        let mut deps = mock_dependencies(20, &[]);
+        let init_msg = InitMsg {
+            name: "sec721".to_string(),
+            symbol: "S721".to_string(),
+            admin: Some(HumanAddr("admin".to_string())),
+            entropy: "We're going to need a bigger boat".to_string(),
+            config: Some(InitConfig {
+                public_token_supply: Some(true),
+                enable_sealed_metadata: Some(true),
+                enable_burn: Some(true),
+                burn_reveals_secret: Some(false),
+                ..InitConfig::default()
+            }),
+            post_init_callback: None,
+        };
+        let init_result = init(&mut deps, mock_env("instantiator", &[]), init_msg);
+        assert!(init_result.is_ok());
+        mint_nft3_alice_grant_bob(&mut deps);
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("NFT1".to_string()),
+            owner: Some(HumanAddr("alice".to_string())),
+            private_metadata: None,
+            public_metadata: None,
+            public_teaser: None,
+            memo: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(result.is_ok());
+        let handle_msg = HandleMsg::BatchBurnNft {
+            burns: vec![Burn {
+                token_ids: vec!["NFT1".to_string(), "NFT2".to_string(), "NFT3".to_string()],
+                memo: None,
+            }],
+            best_effort: Some(true),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        let results = match from_binary(&handle_result.unwrap().data.unwrap()).unwrap() {
+            HandleAnswer::BatchBurnNft { results, .. } => results,
+            _ => panic!("unexpected"),
+        };
+        assert_eq!(
+            results,
+            Some(vec![
+                BurnResult {
+                    token_id: "NFT1".to_string(),
+                    outcome: BurnOutcome::NotPermitted,
+                },
+                BurnResult {
+                    token_id: "NFT2".to_string(),
+                    outcome: BurnOutcome::NotFound,
+                },
+                BurnResult {
+                    token_id: "NFT3".to_string(),
+                    outcome: BurnOutcome::Burned,
+                },
+            ])
+        );
+    }
 }
@@ -1,13 +1,12 @@
 #[cfg(test)]
 mod tests {
-    use std::collections::HashSet;
     use cosmwasm_std::{HumanAddr, from_binary, Api};
     use cosmwasm_std::testing::mock_env;
     use cosmwasm_storage::ReadonlyPrefixedStorage;
 
     use crate::contract::{handle};
-    use crate::msg::{HandleMsg, ContractStatus, Mint, HandleAnswer, TxAction};
-    use crate::state::{load, TOKENS_KEY, PREFIX_MAP_TO_INDEX, PREFIX_MAP_TO_ID, PREFIX_INFOS, json_load, PREFIX_PUB_META, may_load, PREFIX_PRIV_META, PREFIX_OWNED, get_txs};
+    use crate::msg::{HandleMsg, ContractStatus, Mint, HandleAnswer, TxAction, TxFilter};
+    use crate::state::{load, TOKEN_COUNT_KEY, PREFIX_MAP_TO_INDEX, PREFIX_MAP_TO_ID, PREFIX_INFOS, PREFIX_PUB_META, may_load, PREFIX_PRIV_META, get_inventory, get_txs, load_token};
     use crate::token::{Metadata, Token};
     use crate::unittest::helpers::helpers::helpers::*;
 
@@ -24,6 +23,7 @@ mod tests {
                 owner: None,
                 public_metadata: None,
                 private_metadata: None,
+                public_teaser: None,
                 memo: None,
             },
         ];
@@ -49,6 +49,7 @@ mod tests {
                 owner: None,
                 public_metadata: None,
                 private_metadata: None,
+                public_teaser: None,
                 memo: None,
             },
         ];
@@ -83,6 +84,7 @@ mod tests {
                     owner: None,
                     public_metadata: None,
                     private_metadata: None,
+                    public_teaser: None,
                     memo: None,
                 },
             ],
@@ -115,6 +117,7 @@ mod tests {
                 owner: Some(admin.clone()),
                 public_metadata: Some(pub1.clone()),
                 private_metadata: None,
+                public_teaser: None,
                 memo: None,
             },
             Mint {
@@ -122,6 +125,7 @@ mod tests {
                 owner: None,
                 public_metadata: None,
                 private_metadata: Some(priv2.clone()),
+                public_teaser: None,
                 memo: None,
             },
             Mint {
@@ -129,6 +133,7 @@ mod tests {
                 owner: Some(admin.clone()),
                 public_metadata: None,
                 private_metadata: None,
+                public_teaser: None,
                 memo: None,
             },
             Mint {
@@ -136,6 +141,7 @@ mod tests {
                 owner: Some(admin.clone()),
                 public_metadata: None,
                 private_metadata: None,
+                public_teaser: None,
                 memo: Some("has id 3".to_string()),
             },
         ];
@@ -162,12 +168,8 @@ mod tests {
         }
 
         // verify the tokens are in the id and index maps
-        let tokens: HashSet<String> = load(&deps.storage, TOKENS_KEY).unwrap();
-        assert_eq!(tokens.len(), 4);
-        assert!(tokens.contains("0"));
-        assert!(tokens.contains("NFT2"));
-        assert!(tokens.contains("NFT3"));
-        assert!(tokens.contains("3"));
+        let count: u32 = load(&deps.storage, TOKEN_COUNT_KEY).unwrap();
+        assert_eq!(count, 4);
         let map2idx = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_INDEX, &deps.storage);
         let index1: u32 = load(&map2idx, "0".as_bytes()).unwrap();
         let token_key1 = index1.to_le_bytes();
@@ -189,11 +191,11 @@ mod tests {
 
         // verify all the token info
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token1: Token = json_load(&info_store, &token_key1).unwrap();
+        let token1: Token = load_token(&info_store, &token_key1).unwrap();
         assert_eq!(token1.owner, admin_raw);
         assert_eq!(token1.permissions, Vec::new());
         assert!(token1.unwrapped);
-        let token2: Token = json_load(&info_store, &token_key2).unwrap();
+        let token2: Token = load_token(&info_store, &token_key2).unwrap();
         assert_eq!(token2.owner, admin_raw);
         assert_eq!(token2.permissions, Vec::new());
         assert!(token2.unwrapped);
@@ -211,17 +213,11 @@ mod tests {
         assert_eq!(priv_meta2, priv2);
 
         // verify owner lists
-        let owned_store = ReadonlyPrefixedStorage::new(PREFIX_OWNED, &deps.storage);
-        let owned: HashSet<u32> = load(&owned_store, admin_raw.as_slice()).unwrap();
-        assert!(owned.contains(&0));
-        assert!(owned.contains(&2));
-        let owned_store = ReadonlyPrefixedStorage::new(PREFIX_OWNED, &deps.storage);
-        let owned: HashSet<u32> = load(&owned_store, admin_raw.as_slice()).unwrap();
-        assert!(owned.contains(&1));
-        assert!(owned.contains(&3));
+        let owned = get_inventory(&deps.storage, &admin_raw, 0, 30).unwrap();
+        assert_eq!(owned, vec![0u32, 1, 2, 3]);
 
         // verify mint tx was logged
-        let txs = get_txs(&deps.api, &deps.storage, &admin_raw, 0, 4).unwrap();
+        let txs = get_txs(&deps.api, &deps.storage, &admin_raw, 0, 4, None, &TxFilter::default()).unwrap().0;
         assert_eq!(txs.len(), 4);
         assert_eq!(txs[0].token_id, "3".to_string());
         assert_eq!(
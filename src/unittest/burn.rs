@@ -1,14 +1,13 @@
 #[cfg(test)]
 mod tests {
     use crate::unittest::helpers::helpers::helpers::*;
-    use crate::msg::{HandleMsg, ContractStatus, TxAction, HandleAnswer};
-    use cosmwasm_std::{HumanAddr, Env, BlockInfo, MessageInfo, Api, from_binary};
+    use crate::msg::{AccessLevel, HandleMsg, ContractStatus, TxAction, HandleAnswer, QueryAnswer, QueryMsg, TxFilter, ViewerInfo};
+    use cosmwasm_std::{HumanAddr, Env, BlockInfo, MessageInfo, Api, Extern, from_binary};
     use crate::token::{Metadata, Token};
-    use crate::contract::{handle};
-    use cosmwasm_std::testing::{mock_env, MOCK_CONTRACT_ADDR};
+    use crate::contract::{handle, query};
+    use cosmwasm_std::testing::{mock_env, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
     use crate::expiration::Expiration;
-    use std::collections::HashSet;
-    use crate::state::{load, TOKENS_KEY, PREFIX_MAP_TO_INDEX, may_load, PREFIX_MAP_TO_ID, PREFIX_INFOS, json_may_load, PREFIX_PRIV_META, PREFIX_PUB_META, get_txs, PREFIX_AUTHLIST, AuthList, PREFIX_OWNED, PermissionType, json_load};
+    use crate::state::{load, TOKEN_COUNT_KEY, PREFIX_MAP_TO_INDEX, may_load, PREFIX_MAP_TO_ID, PREFIX_INFOS, PREFIX_PRIV_META, PREFIX_PUB_META, get_txs, get_auth_lists, get_inventory, get_inventory_len, PermissionType, load_token, may_load_token};
     use cosmwasm_storage::ReadonlyPrefixedStorage;
 
     // test burn
@@ -27,7 +26,6 @@ mod tests {
             .api
             .canonical_address(&HumanAddr("alice".to_string()))
             .unwrap();
-        let alice_key = alice_raw.as_slice();
 
         // Mint NFT3
         let handle_msg = HandleMsg::MintNft {
@@ -43,6 +41,7 @@ mod tests {
                 description: Some("pubmetadata3".to_string()),
                 image: Some("puburi3".to_string()),
             }),
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -59,8 +58,8 @@ mod tests {
         assert!(handle_result.is_ok());
 
         // confirm token was removed from the maps
-        let tokens: HashSet<String> = load(&deps.storage, TOKENS_KEY).unwrap();
-        assert!(tokens.is_empty());
+        let count: u32 = load(&deps.storage, TOKEN_COUNT_KEY).unwrap();
+        assert_eq!(count, 0);
         let map2idx = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_INDEX, &deps.storage);
         let index: Option<u32> = may_load(&map2idx, "MyNFT3".as_bytes()).unwrap();
         assert!(index.is_none());
@@ -69,7 +68,7 @@ mod tests {
         assert!(id.is_none());
         // confirm token info was deleted from storage
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Option<Token> = json_may_load(&info_store, &token_key).unwrap();
+        let token: Option<Token> = may_load_token(&info_store, &token_key).unwrap();
         assert!(token.is_none());
         // confirm the metadata has been deleted from storage
         let priv_store = ReadonlyPrefixedStorage::new(PREFIX_PRIV_META, &deps.storage);
@@ -79,7 +78,7 @@ mod tests {
         let pub_meta: Option<Metadata> = may_load(&pub_store, &token_key).unwrap();
         assert!(pub_meta.is_none());
         // confirm the tx was logged
-        let txs = get_txs(&deps.api, &deps.storage, &alice_raw, 0, 1).unwrap();
+        let txs = get_txs(&deps.api, &deps.storage, &alice_raw, 0, 1, None, &TxFilter::default()).unwrap().0;
         assert_eq!(txs[0].token_id, "MyNFT3".to_string());
         assert_eq!(
             txs[0].action,
@@ -90,13 +89,10 @@ mod tests {
         );
         assert!(txs[0].memo.is_none());
         // confirm david's AuthList was removed because the only token was burned
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Option<Vec<AuthList>> = may_load(&auth_store, alice_key).unwrap();
-        assert!(auth_list.is_none());
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
+        assert!(auth_list.is_empty());
         // confirm the token was removed form the owner's list
-        let owned_store = ReadonlyPrefixedStorage::new(PREFIX_OWNED, &deps.storage);
-        let owned: Option<HashSet<u32>> = may_load(&owned_store, alice_key).unwrap();
-        assert!(owned.is_none());
+        assert_eq!(get_inventory_len(&deps.storage, &alice_raw).unwrap(), 0);
     }
 
     #[test]
@@ -115,7 +111,6 @@ mod tests {
             .api
             .canonical_address(&HumanAddr("alice".to_string()))
             .unwrap();
-        let alice_key = alice_raw.as_slice();
         let charlie_raw = deps
             .api
             .canonical_address(&HumanAddr("charlie".to_string()))
@@ -143,6 +138,7 @@ mod tests {
                 description: Some("pubmetadata2".to_string()),
                 image: Some("puburi2".to_string()),
             }),
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -163,6 +159,7 @@ mod tests {
                 description: Some("pubmetadata3".to_string()),
                 image: Some("puburi3".to_string()),
             }),
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -218,8 +215,6 @@ mod tests {
         assert!(handle_result.is_ok());
 
         // confirm token was removed from the maps
-        let tokens: HashSet<String> = load(&deps.storage, TOKENS_KEY).unwrap();
-        assert!(!tokens.contains("MyNFT2"));
         let map2idx = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_INDEX, &deps.storage);
         let index: Option<u32> = may_load(&map2idx, "MyNFT2".as_bytes()).unwrap();
         assert!(index.is_none());
@@ -228,10 +223,10 @@ mod tests {
         assert!(id.is_none());
         // confirm token info was deleted from storage
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Option<Token> = json_may_load(&info_store, &tok1_key).unwrap();
+        let token: Option<Token> = may_load_token(&info_store, &tok1_key).unwrap();
         assert!(token.is_none());
         // confirm MyNFT3 is intact
-        let token: Token = json_load(&info_store, &tok2_key).unwrap();
+        let token: Token = load_token(&info_store, &tok2_key).unwrap();
         let david_perm = token.permissions.iter().find(|p| p.address == david_raw);
         assert!(david_perm.is_some());
         assert_eq!(token.owner, alice_raw);
@@ -254,7 +249,7 @@ mod tests {
         let pub_meta: Option<Metadata> = may_load(&pub_store, &tok1_key).unwrap();
         assert!(pub_meta.is_none());
         // confirm the tx was logged to both parties
-        let txs = get_txs(&deps.api, &deps.storage, &alice_raw, 0, 1).unwrap();
+        let txs = get_txs(&deps.api, &deps.storage, &alice_raw, 0, 1, None, &TxFilter::default()).unwrap().0;
         assert_eq!(txs[0].token_id, "MyNFT2".to_string());
         assert_eq!(
             txs[0].action,
@@ -264,11 +259,10 @@ mod tests {
             }
         );
         assert!(txs[0].memo.is_none());
-        let tx2 = get_txs(&deps.api, &deps.storage, &charlie_raw, 0, 1).unwrap();
+        let tx2 = get_txs(&deps.api, &deps.storage, &charlie_raw, 0, 1, None, &TxFilter::default()).unwrap().0;
         assert_eq!(txs, tx2);
         // confirm charlie's AuthList was removed because his only approved token was burned
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Vec<AuthList> = load(&auth_store, alice_key).unwrap();
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
         assert_eq!(auth_list.len(), 1);
         let charlie_auth = auth_list.iter().find(|a| a.address == charlie_raw);
         assert!(charlie_auth.is_none());
@@ -276,10 +270,8 @@ mod tests {
         assert_eq!(david_auth.tokens[transfer_idx].len(), 1);
         assert!(david_auth.tokens[transfer_idx].contains(&1u32));
         // confirm the token was removed form the owner's list
-        let owned_store = ReadonlyPrefixedStorage::new(PREFIX_OWNED, &deps.storage);
-        let owned: HashSet<u32> = load(&owned_store, alice_key).unwrap();
-        assert!(!owned.contains(&0u32));
-        assert!(owned.contains(&1u32));
+        let owned = get_inventory(&deps.storage, &alice_raw, 0, 30).unwrap();
+        assert_eq!(owned, vec![1u32]);
     }
 
     #[test]
@@ -301,6 +293,7 @@ mod tests {
                 image: Some("uri".to_string()),
             }),
             public_metadata: None,
+            public_teaser: None,
             memo: Some("Mint it baby!".to_string()),
             padding: None,
         };
@@ -321,7 +314,6 @@ mod tests {
             .api
             .canonical_address(&HumanAddr("alice".to_string()))
             .unwrap();
-        let alice_key = alice_raw.as_slice();
         let bob_raw = deps
             .api
             .canonical_address(&HumanAddr("bob".to_string()))
@@ -356,7 +348,9 @@ mod tests {
 
         let answer: HandleAnswer = from_binary(&handle_result.unwrap().data.unwrap()).unwrap();
         match answer {
-            HandleAnswer::BurnNft { secret } => {
+            HandleAnswer::BurnNft {
+                secret: Some(secret),
+            } => {
                 assert_eq!(secret.description.unwrap(), "metadata");
                 assert_eq!(secret.image.unwrap(), "uri");
                 assert_eq!(secret.name.unwrap(), "MyNFT")
@@ -365,8 +359,8 @@ mod tests {
         };
 
         // confirm token was removed from the maps
-        let tokens: HashSet<String> = load(&deps.storage, TOKENS_KEY).unwrap();
-        assert!(!tokens.contains("MyNFT"));
+        let count: u32 = load(&deps.storage, TOKEN_COUNT_KEY).unwrap();
+        assert_eq!(count, 0);
         let map2idx = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_INDEX, &deps.storage);
         let index: Option<u32> = may_load(&map2idx, "MyNFT".as_bytes()).unwrap();
         assert!(index.is_none());
@@ -375,7 +369,7 @@ mod tests {
         assert!(id.is_none());
         // confirm token info was deleted from storage
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Option<Token> = json_may_load(&info_store, &tok_key).unwrap();
+        let token: Option<Token> = may_load_token(&info_store, &tok_key).unwrap();
         assert!(token.is_none());
         // confirm the metadata has been deleted from storage
         let priv_store = ReadonlyPrefixedStorage::new(PREFIX_PRIV_META, &deps.storage);
@@ -385,7 +379,7 @@ mod tests {
         let pub_meta: Option<Metadata> = may_load(&pub_store, &tok_key).unwrap();
         assert!(pub_meta.is_none());
         // confirm the tx was logged to both parties
-        let txs = get_txs(&deps.api, &deps.storage, &alice_raw, 0, 1).unwrap();
+        let txs = get_txs(&deps.api, &deps.storage, &alice_raw, 0, 1, None, &TxFilter::default()).unwrap().0;
         assert_eq!(txs.len(), 1);
         assert_eq!(txs[0].token_id, "MyNFT".to_string());
         assert_eq!(
@@ -396,16 +390,13 @@ mod tests {
             }
         );
         assert_eq!(txs[0].memo, Some("Burn, baby, burn!".to_string()));
-        let tx2 = get_txs(&deps.api, &deps.storage, &bob_raw, 0, 1).unwrap();
+        let tx2 = get_txs(&deps.api, &deps.storage, &bob_raw, 0, 1, None, &TxFilter::default()).unwrap().0;
         assert_eq!(txs, tx2);
         // confirm charlie's AuthList was removed because the only token was burned
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Option<Vec<AuthList>> = may_load(&auth_store, alice_key).unwrap();
-        assert!(auth_list.is_none());
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
+        assert!(auth_list.is_empty());
         // confirm the token was removed form the owner's list
-        let owned_store = ReadonlyPrefixedStorage::new(PREFIX_OWNED, &deps.storage);
-        let owned: Option<HashSet<u32>> = may_load(&owned_store, alice_key).unwrap();
-        assert!(owned.is_none());
+        assert_eq!(get_inventory_len(&deps.storage, &alice_raw).unwrap(), 0);
     }
 
     #[test]
@@ -431,6 +422,7 @@ mod tests {
                 description: Some("public metadata".to_string()),
                 image: Some("public uri".to_string()),
             }),
+            public_teaser: None,
             memo: Some("Mint it baby!".to_string()),
             padding: None,
         };
@@ -473,7 +465,9 @@ mod tests {
         );
         let answer: HandleAnswer = from_binary(&result.unwrap().data.unwrap()).unwrap();
         match answer {
-            HandleAnswer::BurnNft { secret } => {
+            HandleAnswer::BurnNft {
+                secret: Some(secret),
+            } => {
                 assert_eq!(secret.description.unwrap(), "public metadata");
                 assert_eq!(secret.image.unwrap(), "uri");
                 assert_eq!(secret.name.unwrap(), "MyNFT")
@@ -501,6 +495,7 @@ mod tests {
                 description: Some("metadata".to_string()),
                 image: Some("uri".to_string()),
             }),
+            public_teaser: None,
             memo: Some("Mint it baby!".to_string()),
             padding: None,
         };
@@ -544,7 +539,9 @@ mod tests {
         );
         let answer: HandleAnswer = from_binary(&result.unwrap().data.unwrap()).unwrap();
         match answer {
-            HandleAnswer::BurnNft { secret } => {
+            HandleAnswer::BurnNft {
+                secret: Some(secret),
+            } => {
                 assert_eq!(secret.description.unwrap(), "metadata");
                 assert_eq!(secret.image.unwrap(), "uri");
                 assert_eq!(secret.name.unwrap(), "MyNFT")
@@ -576,6 +573,7 @@ mod tests {
                 description: Some("pubmetadata".to_string()),
                 image: Some("puburi".to_string()),
             }),
+            public_teaser: None,
             memo: Some("Mint public with metadata!".to_string()),
             padding: None,
         };
@@ -591,7 +589,9 @@ mod tests {
         let result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
         let answer: HandleAnswer = from_binary(&result.unwrap().data.unwrap()).unwrap();
         match answer {
-            HandleAnswer::BurnNft { secret } => {
+            HandleAnswer::BurnNft {
+                secret: Some(secret),
+            } => {
                 assert_eq!(secret.description.unwrap(), "privmetadata");
                 assert_eq!(secret.image.unwrap(), "privuri");
                 assert_eq!(secret.name.unwrap(), "MyNFT")
@@ -600,6 +600,110 @@ mod tests {
         };
     }
 
+    #[test]
+    fn test_burn_without_revealing() {
+        let (init_result, mut deps) = init_helper_full_profile();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("MyNFT".to_string()),
+            owner: None,
+            private_metadata: Some(Metadata {
+                name: None,
+                description: Some("privmetadata".to_string()),
+                image: Some("privuri".to_string()),
+            }),
+            public_metadata: None,
+            public_teaser: None,
+            memo: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+        let mut keys = Vec::new();
+        for address in &["admin", "alice"] {
+            let handle_msg = HandleMsg::CreateViewingKey {
+                entropy: "key".to_string(),
+                padding: None,
+            };
+            let result = handle(&mut deps, mock_env(*address, &[]), handle_msg);
+            match from_binary(&result.unwrap().data.unwrap()).unwrap() {
+                HandleAnswer::ViewingKey { key } => keys.push(key),
+                _ => panic!("NOPE"),
+            }
+        }
+        let can_retrieve = |deps: &Extern<MockStorage, MockApi, MockQuerier>, idx: usize| {
+            let query_msg = QueryMsg::CanRetrieve {
+                token_id: "MyNFT".to_string(),
+                viewer: Some(ViewerInfo {
+                    address: HumanAddr(["admin", "alice"][idx].to_string()),
+                    viewing_key: keys[idx].clone(),
+                }),
+            };
+            match from_binary(&query(deps, query_msg).unwrap()).unwrap() {
+                QueryAnswer::CanRetrieve {
+                    retrievable,
+                    sealed,
+                    reason,
+                } => (retrievable, sealed, reason),
+                _ => panic!("NOPE"),
+            }
+        };
+
+        // only the owner or an approved address may burn
+        let handle_msg = HandleMsg::BurnNft {
+            token_id: "MyNFT".to_string(),
+            memo: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let error = extract_error_msg(result);
+        assert!(error.contains("Token MyNFT does not exist or you are not authorized"));
+        let (retrievable, sealed, reason) = can_retrieve(&deps, 1);
+        assert!(!retrievable);
+        assert!(!sealed);
+        assert_eq!(
+            reason,
+            Some("Token MyNFT does not exist or you are not authorized to access it".to_string())
+        );
+
+        // CanRetrieve never reports that a burn reveals the secret
+        assert_eq!(can_retrieve(&deps, 0), (false, false, None));
+        let handle_msg = HandleMsg::SetWhitelistedApproval {
+            address: HumanAddr("alice".to_string()),
+            token_id: Some("MyNFT".to_string()),
+            view_owner: None,
+            view_private_metadata: None,
+            transfer: Some(AccessLevel::ApproveToken),
+            expires: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(result.is_ok());
+        assert_eq!(can_retrieve(&deps, 1), (false, false, None));
+
+        // the secret is destroyed without being revealed
+        let handle_msg = HandleMsg::BurnNft {
+            token_id: "MyNFT".to_string(),
+            memo: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let answer: HandleAnswer = from_binary(&result.unwrap().data.unwrap()).unwrap();
+        match answer {
+            HandleAnswer::BurnNft { secret } => assert!(secret.is_none()),
+            _ => panic!("NOPE"),
+        };
+        let count: u32 = load(&deps.storage, TOKEN_COUNT_KEY).unwrap();
+        assert_eq!(count, 0);
+        let priv_store = ReadonlyPrefixedStorage::new(PREFIX_PRIV_META, &deps.storage);
+        let priv_meta: Option<Metadata> = may_load(&priv_store, &0u32.to_le_bytes()).unwrap();
+        assert!(priv_meta.is_none());
+    }
+
     #[test]
     fn test_burn_when_not_found_and_supply_is_private() {
         let (init_result, mut deps) =
@@ -618,7 +722,9 @@ mod tests {
         };
         let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
         let error = extract_error_msg(handle_result);
-        assert!(error.contains("You are not authorized to perform this action on token MyNFT"));
+        assert!(error.contains(
+            "Token MyNFT does not exist or you are not authorized to access it"
+        ));
     }
 
     #[test]
@@ -661,6 +767,7 @@ mod tests {
                 image: Some("uri".to_string()),
             }),
             public_metadata: None,
+            public_teaser: None,
             memo: Some("Mint it baby!".to_string()),
             padding: None,
         };
@@ -696,6 +803,7 @@ mod tests {
                 image: Some("uri".to_string()),
             }),
             public_metadata: None,
+            public_teaser: None,
             memo: Some("Mint it baby!".to_string()),
             padding: None,
         };
@@ -7,7 +7,7 @@ mod tests {
     use crate::contract::{handle, check_permission};
     use cosmwasm_std::testing::mock_env;
     use cosmwasm_storage::ReadonlyPrefixedStorage;
-    use crate::state::{PREFIX_INFOS, json_load, PermissionType};
+    use crate::state::{PREFIX_INFOS, PermissionType, load_token};
     use crate::expiration::Expiration;
 
     // test permissioning works
@@ -48,6 +48,7 @@ mod tests {
                 image: Some("URI 1".to_string()),
             }),
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -63,6 +64,7 @@ mod tests {
                 image: Some("URI 2".to_string()),
             }),
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -71,7 +73,7 @@ mod tests {
 
         // test not approved
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token1: Token = json_load(&info_store, &nft1_key).unwrap();
+        let token1: Token = load_token(&info_store, &nft1_key).unwrap();
         let check_perm = check_permission(
             &deps,
             &block,
@@ -103,6 +105,7 @@ mod tests {
                 image: Some("URI 1".to_string()),
             }),
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -118,6 +121,7 @@ mod tests {
                 image: Some("URI 2".to_string()),
             }),
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -168,7 +172,7 @@ mod tests {
         assert!(result.is_ok());
 
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token1: Token = json_load(&info_store, &nft1_key).unwrap();
+        let token1: Token = load_token(&info_store, &nft1_key).unwrap();
         let check_perm = check_permission(
             &deps,
             &block,
@@ -197,7 +201,7 @@ mod tests {
 
         // test global approval for all tokens
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token2: Token = json_load(&info_store, &nft2_key).unwrap();
+        let token2: Token = load_token(&info_store, &nft2_key).unwrap();
         let check_perm = check_permission(
             &deps,
             &block,
@@ -222,7 +226,7 @@ mod tests {
         assert!(result.is_ok());
 
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token2: Token = json_load(&info_store, &nft2_key).unwrap();
+        let token2: Token = load_token(&info_store, &nft2_key).unwrap();
         let check_perm = check_permission(
             &deps,
             &block,
@@ -302,7 +306,7 @@ mod tests {
         assert!(result.is_ok());
 
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token2: Token = json_load(&info_store, &nft2_key).unwrap();
+        let token2: Token = load_token(&info_store, &nft2_key).unwrap();
         let check_perm = check_permission(
             &deps,
             &block,
@@ -377,7 +381,7 @@ mod tests {
         assert!(result.is_ok());
 
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token1: Token = json_load(&info_store, &nft1_key).unwrap();
+        let token1: Token = load_token(&info_store, &nft1_key).unwrap();
         let check_perm = check_permission(
             &deps,
             &block,
@@ -439,6 +443,7 @@ mod tests {
                 image: Some("URI 1".to_string()),
             }),
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -454,6 +459,7 @@ mod tests {
                 image: Some("URI 2".to_string()),
             }),
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -489,7 +495,7 @@ mod tests {
             chain_id: "secret-2".to_string(),
         };
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token1: Token = json_load(&info_store, &nft1_key).unwrap();
+        let token1: Token = load_token(&info_store, &nft1_key).unwrap();
         let check_perm = check_permission(
             &deps,
             &block,
@@ -532,7 +538,7 @@ mod tests {
             chain_id: "secret-2".to_string(),
         };
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token1: Token = json_load(&info_store, &nft1_key).unwrap();
+        let token1: Token = load_token(&info_store, &nft1_key).unwrap();
         let check_perm = check_permission(
             &deps,
             &block,
@@ -575,7 +581,7 @@ mod tests {
             chain_id: "secret-2".to_string(),
         };
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token1: Token = json_load(&info_store, &nft1_key).unwrap();
+        let token1: Token = load_token(&info_store, &nft1_key).unwrap();
         let check_perm = check_permission(
             &deps,
             &block,
@@ -618,7 +624,7 @@ mod tests {
             chain_id: "secret-2".to_string(),
         };
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token1: Token = json_load(&info_store, &nft1_key).unwrap();
+        let token1: Token = load_token(&info_store, &nft1_key).unwrap();
         let check_perm = check_permission(
             &deps,
             &block,
@@ -647,6 +653,7 @@ mod tests {
                 image: Some("URI 1".to_string()),
             }),
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -662,6 +669,7 @@ mod tests {
                 image: Some("URI 2".to_string()),
             }),
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -699,7 +707,7 @@ mod tests {
             chain_id: "secret-2".to_string(),
         };
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token1: Token = json_load(&info_store, &nft1_key).unwrap();
+        let token1: Token = load_token(&info_store, &nft1_key).unwrap();
         let check_perm = check_permission(
             &deps,
             &block,
@@ -712,7 +720,7 @@ mod tests {
             false,
         );
         let error = extract_error_msg(check_perm);
-        assert!(error.contains("not approved"));
+        assert!(error.contains("Token NFT1 does not exist"));
         let check_perm = check_permission(
             &deps,
             &block,
@@ -791,11 +799,14 @@ mod tests {
             expires: Some(Expiration::AtTime(100)),
             padding: None,
         };
-        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        // approve before charlie's transfer approval expires so it is not cleaned up
+        let mut env = mock_env("alice", &[]);
+        env.block.time = 50;
+        let result = handle(&mut deps, env, handle_msg);
         assert!(result.is_ok());
 
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token1: Token = json_load(&info_store, &nft1_key).unwrap();
+        let token1: Token = load_token(&info_store, &nft1_key).unwrap();
         let check_perm = check_permission(
             &deps,
             &block,
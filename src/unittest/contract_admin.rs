@@ -1,10 +1,10 @@
 #[cfg(test)]
 mod tests {
     use crate::unittest::helpers::helpers::helpers::*;
-    use crate::msg::{ContractStatus, HandleMsg};
-    use cosmwasm_std::{HumanAddr, Api};
-    use crate::contract::handle;
-    use cosmwasm_std::testing::mock_env;
+    use crate::msg::{ContractStatus, HandleMsg, QueryAnswer, QueryMsg};
+    use cosmwasm_std::{from_binary, Api, Extern, HumanAddr};
+    use crate::contract::{handle, query};
+    use cosmwasm_std::testing::{mock_env, MockApi, MockQuerier, MockStorage};
     use crate::state::{load, Config, CONFIG_KEY};
 
     // test add minters
@@ -64,6 +64,67 @@ mod tests {
         assert!(error.contains("Minting is not restricted, minter list not supported"));
     }
 
+    fn minters(deps: &Extern<MockStorage, MockApi, MockQuerier>) -> Vec<HumanAddr> {
+        match from_binary(&query(deps, QueryMsg::Minters {}).unwrap()).unwrap() {
+            QueryAnswer::Minters { minters } => minters,
+            _ => panic!("unexpected"),
+        }
+    }
+
+    // test managing the minter list when minting is restricted
+    #[test]
+    fn test_minter_list_full_profile() {
+        let (init_result, mut deps) = init_helper_full_profile();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let admin = HumanAddr("admin".to_string());
+        let alice = HumanAddr("alice".to_string());
+        let bob = HumanAddr("bob".to_string());
+
+        // only the admin may change the list
+        let handle_msg = HandleMsg::AddMinters {
+            minters: vec![alice.clone()],
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("This is an admin command"));
+
+        let handle_msg = HandleMsg::AddMinters {
+            minters: vec![alice.clone(), bob.clone(), alice.clone()],
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+        assert_eq!(minters(&deps), vec![admin.clone(), alice.clone(), bob.clone()]);
+
+        let handle_msg = HandleMsg::RemoveMinters {
+            minters: vec![admin.clone(), HumanAddr("charlie".to_string())],
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+        assert_eq!(minters(&deps), vec![alice.clone(), bob.clone()]);
+
+        let handle_msg = HandleMsg::SetMinters {
+            minters: vec![bob.clone(), bob.clone()],
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("This is an admin command"));
+        let handle_msg = HandleMsg::SetMinters {
+            minters: vec![bob.clone(), bob.clone()],
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+        assert_eq!(minters(&deps), vec![bob]);
+    }
+
     // test change admin
     #[test]
     fn test_change_admin() {
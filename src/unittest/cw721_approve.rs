@@ -7,7 +7,7 @@ mod tests {
     use cosmwasm_std::testing::{mock_env, MOCK_CONTRACT_ADDR};
     use crate::token::{Metadata, Token};
     use crate::expiration::Expiration;
-    use crate::state::{PermissionType, PREFIX_ALL_PERMISSIONS, Permission, json_load, PREFIX_INFOS, AuthList, PREFIX_AUTHLIST, may_load, PREFIX_PRIV_META, load, PREFIX_PUB_META, json_may_load};
+    use crate::state::{PermissionType, get_all_perms, PREFIX_INFOS, get_auth_lists, may_load, PREFIX_PRIV_META, load, PREFIX_PUB_META, load_token};
     use cosmwasm_storage::ReadonlyPrefixedStorage;
 
     #[test]
@@ -47,9 +47,7 @@ mod tests {
         };
         let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
         let error = extract_error_msg(handle_result);
-        assert!(
-            error.contains("Not authorized to grant/revoke transfer permission for token MyNFT")
-        );
+        assert!(error.contains("Token MyNFT does not exist"));
 
         let handle_msg = HandleMsg::MintNft {
             token_id: Some("MyNFT".to_string()),
@@ -60,6 +58,7 @@ mod tests {
                 image: Some("uri".to_string()),
             }),
             public_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -89,9 +88,7 @@ mod tests {
         };
         let handle_result = handle(&mut deps, mock_env("charlie", &[]), handle_msg);
         let error = extract_error_msg(handle_result);
-        assert!(
-            error.contains("Not authorized to grant/revoke transfer permission for token MyNFT")
-        );
+        assert!(error.contains("Token MyNFT does not exist"));
 
         // test expired operator attempt
         let handle_msg = HandleMsg::SetWhitelistedApproval {
@@ -145,7 +142,8 @@ mod tests {
             handle_msg,
         );
         let error = extract_error_msg(handle_result);
-        assert!(error.contains("Transfer authority for all tokens of alice has expired"));
+        // private supply does not reveal that the operator's access expired
+        assert!(error.contains("Token MyNFT does not exist"));
 
         let tok_key = 0u32.to_le_bytes();
         let tok2_key = 1u32.to_le_bytes();
@@ -166,7 +164,6 @@ mod tests {
             .api
             .canonical_address(&HumanAddr("alice".to_string()))
             .unwrap();
-        let alice_key = alice_raw.as_slice();
         let view_owner_idx = PermissionType::ViewOwner.to_usize();
         let view_meta_idx = PermissionType::ViewMetadata.to_usize();
         let transfer_idx = PermissionType::Transfer.to_usize();
@@ -202,8 +199,7 @@ mod tests {
         assert!(result.is_ok());
 
         // confirm charlie still has ALL permission
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Vec<Permission> = json_load(&all_store, alice_key).unwrap();
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
         assert_eq!(all_perm.len(), 2);
         let charlie_oper_perm = all_perm.iter().find(|p| p.address == charlie_raw).unwrap();
         assert_eq!(
@@ -214,12 +210,11 @@ mod tests {
         assert_eq!(charlie_oper_perm.expirations[view_owner_idx], None);
         // confirm token permission did not add charlie
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &tok_key).unwrap();
+        let token: Token = load_token(&info_store, &tok_key).unwrap();
         assert!(token.permissions.is_empty());
         // confirm charlie did not get added to Authlist
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Option<Vec<AuthList>> = may_load(&auth_store, alice_key).unwrap();
-        assert!(auth_list.is_none());
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
+        assert!(auth_list.is_empty());
 
         // sanity check:  operator sets approval for an expired operator
         let handle_msg = HandleMsg::Approve {
@@ -251,13 +246,12 @@ mod tests {
         assert!(result.is_ok());
 
         // confirm charlie's expired ALL permission was removed
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Vec<Permission> = json_load(&all_store, alice_key).unwrap();
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
         assert_eq!(all_perm.len(), 1);
         assert!(all_perm.iter().find(|p| p.address == charlie_raw).is_none());
         // confirm token permission added charlie with default expiration
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &tok_key).unwrap();
+        let token: Token = load_token(&info_store, &tok_key).unwrap();
         assert_eq!(token.owner, alice_raw);
         assert!(token.unwrapped);
         let priv_store = ReadonlyPrefixedStorage::new(PREFIX_PRIV_META, &deps.storage);
@@ -281,8 +275,7 @@ mod tests {
         assert_eq!(charlie_tok_perm.expirations[view_meta_idx], None);
         assert_eq!(charlie_tok_perm.expirations[view_owner_idx], None);
         // confirm AuthList added charlie
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Vec<AuthList> = load(&auth_store, alice_key).unwrap();
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
         assert_eq!(auth_list.len(), 1);
         let charlie_auth = auth_list.iter().find(|a| a.address == charlie_raw).unwrap();
         assert_eq!(charlie_auth.tokens[transfer_idx].len(), 1);
@@ -320,12 +313,11 @@ mod tests {
         assert!(result.is_ok());
 
         // confirm bob's ALL permission was removed
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Option<Vec<Permission>> = json_may_load(&all_store, alice_key).unwrap();
-        assert!(all_perm.is_none());
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
+        assert!(all_perm.is_empty());
         // confirm token permission added bob
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &tok_key).unwrap();
+        let token: Token = load_token(&info_store, &tok_key).unwrap();
         assert_eq!(token.owner, alice_raw);
         assert!(token.unwrapped);
         let priv_store = ReadonlyPrefixedStorage::new(PREFIX_PRIV_META, &deps.storage);
@@ -349,8 +341,7 @@ mod tests {
         assert_eq!(bob_tok_perm.expirations[view_meta_idx], None);
         assert_eq!(bob_tok_perm.expirations[view_owner_idx], None);
         // confirm AuthList added bob
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Vec<AuthList> = load(&auth_store, alice_key).unwrap();
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
         assert_eq!(auth_list.len(), 2);
         let bob_auth = auth_list.iter().find(|a| a.address == bob_raw).unwrap();
         assert_eq!(bob_auth.tokens[transfer_idx].len(), 1);
@@ -369,6 +360,7 @@ mod tests {
                 image: Some("uri2".to_string()),
             }),
             public_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -384,6 +376,7 @@ mod tests {
                 image: Some("uri3".to_string()),
             }),
             public_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -403,8 +396,7 @@ mod tests {
         assert!(result.is_ok());
 
         // confirm david is an operator
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Vec<Permission> = json_load(&all_store, alice_key).unwrap();
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
         assert_eq!(all_perm.len(), 1);
         let david_oper_perm = all_perm.iter().find(|p| p.address == david_raw).unwrap();
         assert_eq!(
@@ -442,12 +434,11 @@ mod tests {
         assert!(result.is_ok());
 
         // confirm david's ALL permission was removed
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Option<Vec<Permission>> = json_may_load(&all_store, alice_key).unwrap();
-        assert!(all_perm.is_none());
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
+        assert!(all_perm.is_empty());
         // confirm MyNFT token permission added david with ALL permission's expiration
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &tok_key).unwrap();
+        let token: Token = load_token(&info_store, &tok_key).unwrap();
         assert_eq!(token.permissions.len(), 3);
         let david_tok_perm = token
             .permissions
@@ -462,7 +453,7 @@ mod tests {
         assert_eq!(david_tok_perm.expirations[view_owner_idx], None);
         // confirm MyNFT2 token permission added david with input expiration
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &tok2_key).unwrap();
+        let token: Token = load_token(&info_store, &tok2_key).unwrap();
         assert_eq!(token.permissions.len(), 1);
         let david_tok_perm = token
             .permissions
@@ -487,7 +478,7 @@ mod tests {
         assert!(pub_meta.is_none());
         // confirm MyNFT3 token permission added david with ALL permission's expiration
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &tok3_key).unwrap();
+        let token: Token = load_token(&info_store, &tok3_key).unwrap();
         assert_eq!(token.permissions.len(), 1);
         let david_tok_perm = token
             .permissions
@@ -501,8 +492,7 @@ mod tests {
         assert_eq!(david_tok_perm.expirations[view_meta_idx], None);
         assert_eq!(david_tok_perm.expirations[view_owner_idx], None);
         // confirm AuthList added david
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Vec<AuthList> = load(&auth_store, alice_key).unwrap();
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
         assert_eq!(auth_list.len(), 3);
         let david_auth = auth_list.iter().find(|a| a.address == david_raw).unwrap();
         assert_eq!(david_auth.tokens[transfer_idx].len(), 3);
@@ -5,7 +5,7 @@ mod tests {
     use cosmwasm_std::{HumanAddr, Api};
     use crate::contract::handle;
     use cosmwasm_std::testing::mock_env;
-    use crate::state::{PermissionType, PREFIX_ALL_PERMISSIONS, Permission, json_load, PREFIX_INFOS, PREFIX_AUTHLIST, AuthList, load, may_load};
+    use crate::state::{PermissionType, get_all_perms, PREFIX_INFOS, get_auth_lists, load_token};
     use cosmwasm_storage::ReadonlyPrefixedStorage;
     use crate::expiration::Expiration;
     use crate::token::Token;
@@ -24,6 +24,7 @@ mod tests {
             owner: Some(HumanAddr("alice".to_string())),
             public_metadata: None,
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -35,6 +36,7 @@ mod tests {
             owner: Some(HumanAddr("alice".to_string())),
             public_metadata: None,
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -46,6 +48,7 @@ mod tests {
             owner: Some(HumanAddr("alice".to_string())),
             public_metadata: None,
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -75,7 +78,6 @@ mod tests {
             .api
             .canonical_address(&HumanAddr("alice".to_string()))
             .unwrap();
-        let alice_key = alice_raw.as_slice();
         let view_owner_idx = PermissionType::ViewOwner.to_usize();
         let view_meta_idx = PermissionType::ViewMetadata.to_usize();
         let transfer_idx = PermissionType::Transfer.to_usize();
@@ -120,8 +122,7 @@ mod tests {
         assert!(result.is_ok());
 
         // confirm bob has transfer token permissions but not transfer all permission
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Vec<Permission> = json_load(&all_store, alice_key).unwrap();
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
         assert_eq!(all_perm.len(), 1);
         let bob_oper_perm = all_perm.iter().find(|p| p.address == bob_raw).unwrap();
         assert_eq!(
@@ -132,7 +133,7 @@ mod tests {
         assert_eq!(bob_oper_perm.expirations[transfer_idx], None);
         // confirm NFT1 permission has bob
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft1_key).unwrap();
+        let token: Token = load_token(&info_store, &nft1_key).unwrap();
         assert_eq!(token.permissions.len(), 1);
         let bob_tok_perm = token
             .permissions
@@ -146,7 +147,7 @@ mod tests {
             Some(Expiration::Never)
         );
         // confirm NFT2 permission has bob
-        let token: Token = json_load(&info_store, &nft2_key).unwrap();
+        let token: Token = load_token(&info_store, &nft2_key).unwrap();
         assert_eq!(token.permissions.len(), 1);
         let bob_tok_perm = token
             .permissions
@@ -160,7 +161,7 @@ mod tests {
             Some(Expiration::Never)
         );
         // confirm NFT3 permission has bob
-        let token: Token = json_load(&info_store, &nft3_key).unwrap();
+        let token: Token = load_token(&info_store, &nft3_key).unwrap();
         assert_eq!(token.permissions.len(), 1);
         let bob_tok_perm = token
             .permissions
@@ -174,8 +175,7 @@ mod tests {
             Some(Expiration::Never)
         );
         // confirm AuthLists has bob
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Vec<AuthList> = load(&auth_store, alice_key).unwrap();
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
         assert_eq!(auth_list.len(), 1);
         let bob_auth = auth_list.iter().find(|a| a.address == bob_raw).unwrap();
         assert_eq!(bob_auth.tokens[transfer_idx].len(), 3);
@@ -195,8 +195,7 @@ mod tests {
         assert!(result.is_ok());
 
         // confirm bob has transfer all permission
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Vec<Permission> = json_load(&all_store, alice_key).unwrap();
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
         assert_eq!(all_perm.len(), 1);
         let bob_oper_perm = all_perm.iter().find(|p| p.address == bob_raw).unwrap();
         assert_eq!(
@@ -210,17 +209,16 @@ mod tests {
         );
         // confirm bob's NFT1 permission is gone
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft1_key).unwrap();
+        let token: Token = load_token(&info_store, &nft1_key).unwrap();
         assert!(token.permissions.is_empty());
         // confirm bob's NFT2 permission is gone
-        let token: Token = json_load(&info_store, &nft2_key).unwrap();
+        let token: Token = load_token(&info_store, &nft2_key).unwrap();
         assert!(token.permissions.is_empty());
         // confirm bob's NFT3 permission is gone
-        let token: Token = json_load(&info_store, &nft3_key).unwrap();
+        let token: Token = load_token(&info_store, &nft3_key).unwrap();
         assert!(token.permissions.is_empty());
         // confirm AuthLists no longer have bob
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Option<Vec<AuthList>> = may_load(&auth_store, alice_key).unwrap();
-        assert!(auth_list.is_none());
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
+        assert!(auth_list.is_empty());
     }
 }
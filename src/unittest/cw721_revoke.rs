@@ -7,7 +7,7 @@ mod tests {
     use cosmwasm_std::testing::{mock_env, MOCK_CONTRACT_ADDR};
     use crate::token::{Metadata, Token};
     use crate::expiration::Expiration;
-    use crate::state::{PermissionType, PREFIX_ALL_PERMISSIONS, Permission, json_load, PREFIX_INFOS, PREFIX_PRIV_META, load, PREFIX_PUB_META, may_load, PREFIX_AUTHLIST, AuthList, json_may_load};
+    use crate::state::{PermissionType, get_all_perms, PREFIX_INFOS, PREFIX_PRIV_META, load, PREFIX_PUB_META, may_load, get_auth_lists, load_token};
     use cosmwasm_storage::ReadonlyPrefixedStorage;
 
     #[test]
@@ -45,9 +45,7 @@ mod tests {
         };
         let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
         let error = extract_error_msg(handle_result);
-        assert!(
-            error.contains("Not authorized to grant/revoke transfer permission for token MyNFT")
-        );
+        assert!(error.contains("Token MyNFT does not exist"));
 
         let handle_msg = HandleMsg::MintNft {
             token_id: Some("MyNFT".to_string()),
@@ -58,6 +56,7 @@ mod tests {
                 image: Some("uri".to_string()),
             }),
             public_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -86,9 +85,7 @@ mod tests {
         };
         let handle_result = handle(&mut deps, mock_env("charlie", &[]), handle_msg);
         let error = extract_error_msg(handle_result);
-        assert!(
-            error.contains("Not authorized to grant/revoke transfer permission for token MyNFT")
-        );
+        assert!(error.contains("Token MyNFT does not exist"));
 
         // test expired operator attempt
         let handle_msg = HandleMsg::SetWhitelistedApproval {
@@ -141,7 +138,8 @@ mod tests {
             handle_msg,
         );
         let error = extract_error_msg(handle_result);
-        assert!(error.contains("Transfer authority for all tokens of alice has expired"));
+        // private supply does not reveal that the operator's access expired
+        assert!(error.contains("Token MyNFT does not exist"));
 
         let tok_key = 0u32.to_le_bytes();
         let tok2_key = 1u32.to_le_bytes();
@@ -158,7 +156,6 @@ mod tests {
             .api
             .canonical_address(&HumanAddr("alice".to_string()))
             .unwrap();
-        let alice_key = alice_raw.as_slice();
         let view_owner_idx = PermissionType::ViewOwner.to_usize();
         let view_meta_idx = PermissionType::ViewMetadata.to_usize();
         let transfer_idx = PermissionType::Transfer.to_usize();
@@ -222,13 +219,12 @@ mod tests {
         assert!(result.is_ok());
 
         // confirm charlie's expired ALL permission was removed
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Vec<Permission> = json_load(&all_store, alice_key).unwrap();
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
         assert_eq!(all_perm.len(), 1);
         assert!(all_perm.iter().find(|p| p.address == charlie_raw).is_none());
         // confirm token permission is still empty
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &tok_key).unwrap();
+        let token: Token = load_token(&info_store, &tok_key).unwrap();
         assert!(token.permissions.is_empty());
         let priv_store = ReadonlyPrefixedStorage::new(PREFIX_PRIV_META, &deps.storage);
         let priv_meta: Metadata = load(&priv_store, &tok_key).unwrap();
@@ -239,9 +235,8 @@ mod tests {
         let pub_meta: Option<Metadata> = may_load(&pub_store, &tok_key).unwrap();
         assert!(pub_meta.is_none());
         // confirm AuthList is still empty
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Option<Vec<AuthList>> = may_load(&auth_store, alice_key).unwrap();
-        assert!(auth_list.is_none());
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
+        assert!(auth_list.is_empty());
 
         // sanity check: operator approves, then revokes
         let handle_msg = HandleMsg::Approve {
@@ -273,13 +268,12 @@ mod tests {
         assert!(result.is_ok());
 
         // confirm charlie does not have ALL permission
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Vec<Permission> = json_load(&all_store, alice_key).unwrap();
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
         assert_eq!(all_perm.len(), 1);
         assert!(all_perm.iter().find(|p| p.address == charlie_raw).is_none());
         // confirm token permission added charlie
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &tok_key).unwrap();
+        let token: Token = load_token(&info_store, &tok_key).unwrap();
         assert_eq!(token.permissions.len(), 1);
         let charlie_tok_perm = token
             .permissions
@@ -320,13 +314,12 @@ mod tests {
         assert!(result.is_ok());
 
         // confirm charlie does not have ALL permission
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Vec<Permission> = json_load(&all_store, alice_key).unwrap();
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
         assert_eq!(all_perm.len(), 1);
         assert!(all_perm.iter().find(|p| p.address == charlie_raw).is_none());
         // confirm token permission removed charlie
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &tok_key).unwrap();
+        let token: Token = load_token(&info_store, &tok_key).unwrap();
         assert_eq!(token.owner, alice_raw);
         assert!(token.unwrapped);
         let priv_store = ReadonlyPrefixedStorage::new(PREFIX_PRIV_META, &deps.storage);
@@ -339,9 +332,8 @@ mod tests {
         assert!(pub_meta.is_none());
         assert!(token.permissions.is_empty());
         // confirm AuthList removed charlie
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Option<Vec<AuthList>> = may_load(&auth_store, alice_key).unwrap();
-        assert!(auth_list.is_none());
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
+        assert!(auth_list.is_empty());
 
         // verify revoking a non-existent permission does not break anything
         let handle_msg = HandleMsg::Revoke {
@@ -353,13 +345,12 @@ mod tests {
         assert!(result.is_ok());
 
         // confirm charlie does not have ALL permission
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Vec<Permission> = json_load(&all_store, alice_key).unwrap();
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
         assert_eq!(all_perm.len(), 1);
         assert!(all_perm.iter().find(|p| p.address == charlie_raw).is_none());
         // confirm token does not list charlie
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &tok_key).unwrap();
+        let token: Token = load_token(&info_store, &tok_key).unwrap();
         assert_eq!(token.owner, alice_raw);
         assert!(token.unwrapped);
         let priv_store = ReadonlyPrefixedStorage::new(PREFIX_PRIV_META, &deps.storage);
@@ -372,9 +363,8 @@ mod tests {
         assert!(pub_meta.is_none());
         assert!(token.permissions.is_empty());
         // confirm AuthList doesn not contain charlie
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Option<Vec<AuthList>> = may_load(&auth_store, alice_key).unwrap();
-        assert!(auth_list.is_none());
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
+        assert!(auth_list.is_empty());
 
         // sanity check:  owner revokes token approval for an operator with only that one token
         let handle_msg = HandleMsg::Revoke {
@@ -405,12 +395,11 @@ mod tests {
         assert!(result.is_ok());
 
         // confirm bob's ALL permission was removed
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Option<Vec<Permission>> = json_may_load(&all_store, alice_key).unwrap();
-        assert!(all_perm.is_none());
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
+        assert!(all_perm.is_empty());
         // confirm token permission is empty
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &tok_key).unwrap();
+        let token: Token = load_token(&info_store, &tok_key).unwrap();
         assert_eq!(token.owner, alice_raw);
         assert!(token.unwrapped);
         let priv_store = ReadonlyPrefixedStorage::new(PREFIX_PRIV_META, &deps.storage);
@@ -423,9 +412,8 @@ mod tests {
         assert!(pub_meta.is_none());
         assert!(token.permissions.is_empty());
         // confirm AuthList does not contain bob
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Option<Vec<AuthList>> = may_load(&auth_store, alice_key).unwrap();
-        assert!(auth_list.is_none());
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
+        assert!(auth_list.is_empty());
 
         // used to test auto-setting individual token permissions when only one token
         // of many is revoked from an operator
@@ -440,6 +428,7 @@ mod tests {
                 image: Some("uri2".to_string()),
             }),
             public_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -455,6 +444,7 @@ mod tests {
                 image: Some("uri3".to_string()),
             }),
             public_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -474,8 +464,7 @@ mod tests {
         assert!(result.is_ok());
 
         // confirm david is an operator
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Vec<Permission> = json_load(&all_store, alice_key).unwrap();
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
         assert_eq!(all_perm.len(), 1);
         let david_oper_perm = all_perm.iter().find(|p| p.address == david_raw).unwrap();
         assert_eq!(
@@ -512,12 +501,11 @@ mod tests {
         assert!(result.is_ok());
 
         // confirm david's ALL permission was removed
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Option<Vec<Permission>> = json_may_load(&all_store, alice_key).unwrap();
-        assert!(all_perm.is_none());
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
+        assert!(all_perm.is_empty());
         // confirm MyNFT token permission added david with ALL permission's expiration
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &tok_key).unwrap();
+        let token: Token = load_token(&info_store, &tok_key).unwrap();
         assert_eq!(token.permissions.len(), 1);
         let david_tok_perm = token
             .permissions
@@ -532,7 +520,7 @@ mod tests {
         assert_eq!(david_tok_perm.expirations[view_owner_idx], None);
         // confirm MyNFT2 token permission does not contain david
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &tok2_key).unwrap();
+        let token: Token = load_token(&info_store, &tok2_key).unwrap();
         assert!(token.permissions.is_empty());
         assert_eq!(token.owner, alice_raw);
         assert!(token.unwrapped);
@@ -546,7 +534,7 @@ mod tests {
         assert!(pub_meta.is_none());
         // confirm MyNFT3 token permission added david with ALL permission's expiration
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &tok3_key).unwrap();
+        let token: Token = load_token(&info_store, &tok3_key).unwrap();
         assert_eq!(token.permissions.len(), 1);
         let david_tok_perm = token
             .permissions
@@ -560,8 +548,7 @@ mod tests {
         assert_eq!(david_tok_perm.expirations[view_meta_idx], None);
         assert_eq!(david_tok_perm.expirations[view_owner_idx], None);
         // confirm AuthList added david
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Vec<AuthList> = load(&auth_store, alice_key).unwrap();
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
         assert_eq!(auth_list.len(), 1);
         let david_auth = auth_list.iter().find(|a| a.address == david_raw).unwrap();
         assert_eq!(david_auth.tokens[transfer_idx].len(), 2);
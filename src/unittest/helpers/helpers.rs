@@ -73,6 +73,32 @@ pub(crate) mod helpers {
         (init(&mut deps, env, init_msg), deps)
     }
 
+    pub fn init_helper_full_profile() -> (
+        StdResult<InitResponse>,
+        Extern<MockStorage, MockApi, MockQuerier>,
+    ) {
+        let mut deps = mock_dependencies(20, &[]);
+        let env = mock_env("instantiator", &[]);
+
+        let init_msg = InitMsg {
+            name: "sec721".to_string(),
+            symbol: "S721".to_string(),
+            admin: Some(HumanAddr("admin".to_string())),
+            entropy: "We're going to need a bigger boat".to_string(),
+            config: Some(InitConfig {
+                enable_burn: Some(true),
+                transfers_enabled: Some(true),
+                open_minting: Some(false),
+                metadata_queries_enabled: Some(true),
+                burn_reveals_secret: Some(false),
+                ..InitConfig::default()
+            }),
+            post_init_callback: None,
+        };
+
+        (init(&mut deps, env, init_msg), deps)
+    }
+
     pub fn extract_error_msg<T: Any>(error: StdResult<T>) -> String {
         match error {
             Ok(_response) => panic!("Expected error, but had Ok response"),
@@ -130,6 +156,7 @@ pub(crate) mod helpers {
             owner: Some(HumanAddr("alice".to_string())),
             private_metadata: None,
             public_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -167,6 +194,7 @@ pub(crate) mod helpers {
             owner: Some(HumanAddr("alice".to_string())),
             private_metadata: None,
             public_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -200,6 +228,7 @@ pub(crate) mod helpers {
                 description: Some("pubmetadata3".to_string()),
                 image: Some("puburi3".to_string()),
             }),
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -233,6 +262,7 @@ pub(crate) mod helpers {
             owner: Some(HumanAddr("bob".to_string())),
             private_metadata: None,
             public_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -258,6 +288,7 @@ pub(crate) mod helpers {
             owner: Some(HumanAddr("bob".to_string())),
             private_metadata: None,
             public_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -295,6 +326,7 @@ pub(crate) mod helpers {
             owner: Some(HumanAddr("bob".to_string())),
             private_metadata: None,
             public_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -320,6 +352,7 @@ pub(crate) mod helpers {
             owner: Some(HumanAddr("charlie".to_string())),
             private_metadata: None,
             public_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -345,6 +378,7 @@ pub(crate) mod helpers {
             owner: Some(HumanAddr("charlie".to_string())),
             private_metadata: None,
             public_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -359,6 +393,7 @@ pub(crate) mod helpers {
             owner: Some(HumanAddr("alice".to_string())),
             public_metadata: None,
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -17,6 +17,7 @@ pub(crate) mod minters {
                 image: Some("URI 1".to_string()),
             }),
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -34,6 +35,7 @@ pub(crate) mod minters {
                 image: Some("URI 2".to_string()),
             }),
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -51,6 +53,7 @@ pub(crate) mod minters {
                 image: Some("URI 3".to_string()),
             }),
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -68,6 +71,7 @@ pub(crate) mod minters {
                 image: Some("URI 4".to_string()),
             }),
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -85,6 +89,7 @@ pub(crate) mod minters {
                 image: Some("URI 5".to_string()),
             }),
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
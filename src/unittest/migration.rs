@@ -0,0 +1,188 @@
+#[cfg(test)]
+mod tests {
+    use crate::unittest::helpers::helpers::helpers::*;
+    use crate::msg::{HandleMsg, HandleAnswer, QueryMsg, QueryAnswer, ViewerInfo};
+    use cosmwasm_std::{from_binary, Api, Binary, CanonicalAddr, HumanAddr};
+    use crate::contract::{handle, query};
+    use cosmwasm_std::testing::mock_env;
+    use crate::expiration::Expiration;
+    use crate::state::{get_all_perms, json_save, load, may_load, save, Config, MigrationProgress, Permission, CONFIG_KEY, MIGRATION_KEY, PREFIX_ALL_PERMISSIONS, SCHEMA_VERSION};
+    use cosmwasm_storage::{PrefixedStorage, ReadonlyPrefixedStorage};
+    use serde::Serialize;
+
+    // layout of the config saved before the storage schema was versioned
+    #[derive(Serialize)]
+    struct LegacyConfig {
+        name: String,
+        symbol: String,
+        admin: CanonicalAddr,
+        mint_cnt: u32,
+        tx_cnt: u64,
+        status: u8,
+        token_supply_is_public: bool,
+        owner_is_public: bool,
+        sealed_metadata_is_enabled: bool,
+        unwrap_to_private: bool,
+        minter_may_update_metadata: bool,
+        owner_may_update_metadata: bool,
+        burn_is_enabled: bool,
+    }
+
+    fn migrate_msg(batch_size: u32) -> HandleMsg {
+        HandleMsg::Migrate {
+            batch_size,
+            padding: None,
+        }
+    }
+
+    fn migrate_complete(result: &[u8]) -> bool {
+        match from_binary(&Binary::from(result)).unwrap() {
+            HandleAnswer::Migrate { complete, .. } => complete,
+            _ => panic!("unexpected"),
+        }
+    }
+
+    #[test]
+    fn test_migrate_storage() {
+        let (init_result, mut deps) = init_helper_default();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        mint_generic_token(&mut deps, "NFT1");
+        mint_generic_token(&mut deps, "NFT2");
+        let alice_raw = deps
+            .api
+            .canonical_address(&HumanAddr("alice".to_string()))
+            .unwrap();
+        let bob_raw = deps
+            .api
+            .canonical_address(&HumanAddr("bob".to_string()))
+            .unwrap();
+
+        // save the config and approvals an unversioned contract would have stored
+        let config: Config = load(&deps.storage, CONFIG_KEY).unwrap();
+        let legacy = LegacyConfig {
+            name: config.name,
+            symbol: config.symbol,
+            admin: config.admin,
+            mint_cnt: config.mint_cnt,
+            tx_cnt: config.tx_cnt,
+            status: config.status,
+            token_supply_is_public: config.token_supply_is_public,
+            owner_is_public: config.owner_is_public,
+            sealed_metadata_is_enabled: config.sealed_metadata_is_enabled,
+            unwrap_to_private: config.unwrap_to_private,
+            minter_may_update_metadata: config.minter_may_update_metadata,
+            owner_may_update_metadata: config.owner_may_update_metadata,
+            burn_is_enabled: config.burn_is_enabled,
+        };
+        save(&mut deps.storage, CONFIG_KEY, &legacy).unwrap();
+        let legacy_perms = vec![Permission {
+            address: bob_raw.clone(),
+            expirations: [None, None, Some(Expiration::Never)],
+        }];
+        let mut all_store = PrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &mut deps.storage);
+        json_save(&mut all_store, alice_raw.as_slice(), &legacy_perms).unwrap();
+
+        // test normal operations are refused before migrating
+        let handle_result = handle(
+            &mut deps,
+            mock_env("alice", &[]),
+            HandleMsg::MintNft {
+                token_id: Some("NFT3".to_string()),
+                owner: None,
+                public_metadata: None,
+                private_metadata: None,
+                public_teaser: None,
+                memo: None,
+                padding: None,
+            },
+        );
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("The contract admin has temporarily disabled this action"));
+
+        // test the counts are hidden from non-admins while the supply is private
+        let query_msg = QueryMsg::MigrationStatus { viewer: None };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::MigrationStatus {
+                schema_version,
+                target_version,
+                complete,
+                tokens_migrated,
+                total_txs,
+                ..
+            } => {
+                assert_eq!(schema_version, 0);
+                assert_eq!(target_version, SCHEMA_VERSION);
+                assert!(!complete);
+                assert!(tokens_migrated.is_none());
+                assert!(total_txs.is_none());
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test non-admin migrate attempt
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), migrate_msg(3));
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("This is an admin command and can only be run from the admin address"));
+
+        // test a partial migration saves its progress
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), migrate_msg(3));
+        assert!(!migrate_complete(handle_result.unwrap().data.unwrap().as_slice()));
+        let progress: Option<MigrationProgress> =
+            may_load(&deps.storage, MIGRATION_KEY).unwrap();
+        assert_eq!(
+            progress,
+            Some(MigrationProgress { tokens: 2, txs: 1 })
+        );
+        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
+        let stale: Option<Vec<u8>> = may_load(&all_store, alice_raw.as_slice()).unwrap();
+        assert!(stale.is_none());
+        assert_eq!(get_all_perms(&deps.storage, &alice_raw).unwrap(), legacy_perms);
+        let query_msg = QueryMsg::MigrationStatus {
+            viewer: Some(ViewerInfo {
+                address: HumanAddr("admin".to_string()),
+                viewing_key: "key".to_string(),
+            }),
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::MigrationStatus {
+                complete,
+                tokens_migrated,
+                total_tokens,
+                txs_migrated,
+                total_txs,
+                ..
+            } => {
+                assert!(!complete);
+                assert_eq!(tokens_migrated, Some(2));
+                assert_eq!(total_tokens, Some(2));
+                assert_eq!(txs_migrated, Some(1));
+                assert_eq!(total_txs, Some(2));
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test finishing the migration resumes normal operations
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), migrate_msg(3));
+        assert!(migrate_complete(handle_result.unwrap().data.unwrap().as_slice()));
+        let config: Config = load(&deps.storage, CONFIG_KEY).unwrap();
+        assert_eq!(config.schema_version, SCHEMA_VERSION);
+        assert_eq!(config.mint_cnt, 2);
+        assert!(config.retrieval_window.is_none());
+        let progress: Option<MigrationProgress> =
+            may_load(&deps.storage, MIGRATION_KEY).unwrap();
+        assert!(progress.is_none());
+        mint_generic_token(&mut deps, "NFT3");
+
+        // test migrating a current contract is a no-op
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), migrate_msg(1));
+        assert!(migrate_complete(handle_result.unwrap().data.unwrap().as_slice()));
+    }
+}
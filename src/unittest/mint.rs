@@ -1,13 +1,12 @@
 #[cfg(test)]
 mod tests {
     use crate::unittest::helpers::helpers::helpers::*;
-    use crate::msg::{HandleMsg, ContractStatus, HandleAnswer, TxAction};
+    use crate::msg::{HandleMsg, ContractStatus, HandleAnswer, Mint, TxAction, TxFilter};
     use crate::contract::{handle};
     use cosmwasm_std::testing::mock_env;
     use cosmwasm_std::{HumanAddr, from_binary, Api};
     use crate::token::{Metadata, Token};
-    use std::collections::HashSet;
-    use crate::state::{load, TOKENS_KEY, PREFIX_MAP_TO_INDEX, PREFIX_MAP_TO_ID, PREFIX_INFOS, json_load, PREFIX_PUB_META, PREFIX_PRIV_META, may_load, PREFIX_OWNED, get_txs};
+    use crate::state::{load, TOKEN_COUNT_KEY, PREFIX_MAP_TO_INDEX, PREFIX_MAP_TO_ID, PREFIX_INFOS, PREFIX_PUB_META, PREFIX_PRIV_META, may_load, get_inventory, get_txs, load_token};
     use cosmwasm_storage::ReadonlyPrefixedStorage;
 
     // test minting when status prevents it
@@ -28,6 +27,7 @@ mod tests {
                 image: Some("uri".to_string()),
             }),
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -48,6 +48,7 @@ mod tests {
                 image: Some("uri".to_string()),
             }),
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -68,6 +69,7 @@ mod tests {
                 image: Some("uri".to_string()),
             }),
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -86,6 +88,7 @@ mod tests {
             owner: None,
             public_metadata: None,
             private_metadata: None,
+            public_teaser: None,
             memo: Some("First instance".to_string()),
             padding: None,
         };
@@ -97,6 +100,7 @@ mod tests {
             owner: None,
             public_metadata: None,
             private_metadata: None,
+            public_teaser: None,
             memo: Some("Instance with duplicate id".to_string()),
             padding: None,
         };
@@ -119,6 +123,7 @@ mod tests {
                 image: None,
             }),
             private_metadata: None,
+            public_teaser: None,
             memo: Some("Admin wants his own".to_string()),
             padding: None,
         };
@@ -134,8 +139,8 @@ mod tests {
         }
 
         // verify token is in the token list
-        let tokens: HashSet<String> = load(&deps.storage, TOKENS_KEY).unwrap();
-        assert!(tokens.contains("0"));
+        let count: u32 = load(&deps.storage, TOKEN_COUNT_KEY).unwrap();
+        assert_eq!(count, 1);
         let map2idx = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_INDEX, &deps.storage);
         let index: u32 = load(&map2idx, "0".as_bytes()).unwrap();
         let token_key = index.to_le_bytes();
@@ -145,7 +150,7 @@ mod tests {
 
         // verify token info
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &token_key).unwrap();
+        let token: Token = load_token(&info_store, &token_key).unwrap();
         let admin_raw = deps
             .api
             .canonical_address(&HumanAddr("admin".to_string()))
@@ -165,12 +170,11 @@ mod tests {
         assert!(priv_meta.is_none());
 
         // verify token is in the owner list
-        let owned_store = ReadonlyPrefixedStorage::new(PREFIX_OWNED, &deps.storage);
-        let owned: HashSet<u32> = load(&owned_store, admin_raw.as_slice()).unwrap();
-        assert!(owned.contains(&0));
+        let owned = get_inventory(&deps.storage, &admin_raw, 0, 30).unwrap();
+        assert_eq!(owned, vec![0u32]);
 
         // verify mint tx was logged
-        let txs = get_txs(&deps.api, &deps.storage, &admin_raw, 0, 10).unwrap();
+        let txs = get_txs(&deps.api, &deps.storage, &admin_raw, 0, 10, None, &TxFilter::default()).unwrap().0;
         assert_eq!(txs.len(), 1);
         assert_eq!(txs[0].token_id, "0".to_string());
         assert_eq!(
@@ -196,6 +200,7 @@ mod tests {
                 image: Some("uri".to_string()),
             }),
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -203,6 +208,105 @@ mod tests {
         assert!(handle_result.is_ok());
     }
 
+    // test minting by non-minter when minting is restricted
+    #[test]
+    fn test_mint_by_non_minter_full_profile() {
+        let (init_result, mut deps) = init_helper_full_profile();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("AlicesSecret".to_string()),
+            owner: None,
+            public_metadata: None,
+            private_metadata: None,
+            public_teaser: None,
+            memo: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Only designated minters are allowed to mint"));
+        let handle_msg = HandleMsg::BatchMintNft {
+            mints: vec![Mint {
+                token_id: Some("AlicesSecret".to_string()),
+                owner: None,
+                public_metadata: None,
+                private_metadata: None,
+                public_teaser: None,
+                memo: None,
+            }],
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Only designated minters are allowed to mint"));
+
+        // the admin is a minter
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("AdminsSecret".to_string()),
+            owner: None,
+            public_metadata: None,
+            private_metadata: None,
+            public_teaser: None,
+            memo: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+    }
+
+    // test minters minting on behalf of others when minting is restricted
+    #[test]
+    fn test_mint_for_someone_else_full_profile() {
+        let (init_result, mut deps) = init_helper_full_profile();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("AlicesSecret".to_string()),
+            owner: Some(HumanAddr("alice".to_string())),
+            public_metadata: None,
+            private_metadata: None,
+            public_teaser: None,
+            memo: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(handle_result.is_ok(), "Mint failed: {}", handle_result.err().unwrap());
+        let handle_msg = HandleMsg::BatchMintNft {
+            mints: vec![Mint {
+                token_id: Some("BobsSecret".to_string()),
+                owner: Some(HumanAddr("bob".to_string())),
+                public_metadata: None,
+                private_metadata: None,
+                public_teaser: None,
+                memo: None,
+            }],
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(handle_result.is_ok(), "Mint failed: {}", handle_result.err().unwrap());
+
+        let alice_raw = deps
+            .api
+            .canonical_address(&HumanAddr("alice".to_string()))
+            .unwrap();
+        let bob_raw = deps
+            .api
+            .canonical_address(&HumanAddr("bob".to_string()))
+            .unwrap();
+        let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
+        let token: Token = load_token(&info_store, &0u32.to_le_bytes()).unwrap();
+        assert_eq!(token.owner, alice_raw);
+        let token: Token = load_token(&info_store, &1u32.to_le_bytes()).unwrap();
+        assert_eq!(token.owner, bob_raw);
+    }
+
     // test minting
     #[test]
     fn test_mint() {
@@ -221,6 +325,7 @@ mod tests {
                 description: Some("Nifty".to_string()),
                 image: Some("privuri".to_string()),
             }),
+            public_teaser: None,
             memo: Some("Mint it baby!".to_string()),
             padding: None,
         };
@@ -229,8 +334,8 @@ mod tests {
         assert!(minted.contains("MyNFT"));
 
         // verify the token is in the id and index maps
-        let tokens: HashSet<String> = load(&deps.storage, TOKENS_KEY).unwrap();
-        assert!(tokens.contains("MyNFT"));
+        let count: u32 = load(&deps.storage, TOKEN_COUNT_KEY).unwrap();
+        assert_eq!(count, 1);
         let map2idx = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_INDEX, &deps.storage);
         let index: u32 = load(&map2idx, "MyNFT".as_bytes()).unwrap();
         let token_key = index.to_le_bytes();
@@ -240,7 +345,7 @@ mod tests {
 
         // verify all the token info
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &token_key).unwrap();
+        let token: Token = load_token(&info_store, &token_key).unwrap();
         let admin_raw = deps
             .api
             .canonical_address(&HumanAddr("admin".to_string()))
@@ -262,12 +367,11 @@ mod tests {
         assert_eq!(priv_meta.image, Some("privuri".to_string()));
 
         // verify token is in owner list
-        let owned_store = ReadonlyPrefixedStorage::new(PREFIX_OWNED, &deps.storage);
-        let owned: HashSet<u32> = load(&owned_store, admin_raw.as_slice()).unwrap();
-        assert!(owned.contains(&0));
+        let owned = get_inventory(&deps.storage, &admin_raw, 0, 30).unwrap();
+        assert_eq!(owned, vec![0u32]);
 
         // verify mint tx was logged
-        let txs = get_txs(&deps.api, &deps.storage, &admin_raw, 0, 1).unwrap();
+        let txs = get_txs(&deps.api, &deps.storage, &admin_raw, 0, 1, None, &TxFilter::default()).unwrap().0;
         assert_eq!(txs.len(), 1);
         assert_eq!(txs[0].token_id, "MyNFT".to_string());
         assert_eq!(
@@ -278,7 +382,7 @@ mod tests {
             }
         );
         assert_eq!(txs[0].memo, Some("Mint it baby!".to_string()));
-        let tx2 = get_txs(&deps.api, &deps.storage, &admin_raw, 0, 1).unwrap();
+        let tx2 = get_txs(&deps.api, &deps.storage, &admin_raw, 0, 1, None, &TxFilter::default()).unwrap().0;
         assert_eq!(txs, tx2);
     }
 }
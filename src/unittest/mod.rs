@@ -9,13 +9,18 @@ mod cw721_approve_all;
 mod cw721_revoke;
 mod cw721_revoke_all;
 mod make_ownership_private;
+mod migration;
+mod permits;
 mod mint;
 mod private_metadata;
 mod public_metadata;
 mod queries;
 mod reveal;
+mod retrieved_secrets;
+mod rotate_token_link;
 mod query_all_nft_info;
 mod query_all_tokens;
+mod query_can_retrieve;
 mod query_approved_for_all;
 mod query_contract_config;
 mod query_contract_info;
@@ -23,6 +28,8 @@ mod query_minters;
 mod query_nft_dossier;
 mod query_nft_info;
 mod query_num_tokens;
+mod query_inbox;
+mod query_outbox;
 mod query_owner_of;
 mod query_private_metadata;
 mod query_token_approvals;
@@ -30,7 +37,13 @@ mod query_tokens;
 mod query_unwrapped;
 mod query_verify_transfer_approval;
 mod register_receive_nft;
+mod secret_padding;
+mod secret_requests;
 mod send;
+mod token_encoding;
+mod token_errors;
+mod tx_privacy;
+mod tx_retention;
 mod set_global_approval;
 mod viewing_key;
 mod whitelisted_approval;
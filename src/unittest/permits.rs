@@ -0,0 +1,160 @@
+#[cfg(test)]
+mod tests {
+    use crate::unittest::helpers::helpers::helpers::*;
+    use crate::contract::{handle, init, query};
+    use crate::msg::{HandleMsg, InitConfig, InitMsg, QueryAnswer, QueryMsg, QueryWithPermit};
+    use crate::permit::{
+        pubkey_to_account, Permit, PermitParams, PermitSignature, PubKey, TokenPermissions,
+    };
+    use crate::rand::sha_256;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{from_binary, Binary, Extern, HumanAddr, InitResponse, StdResult};
+
+    // the mock api must accept bech32 addresses
+    fn init_permit_helper() -> (
+        StdResult<InitResponse>,
+        Extern<MockStorage, MockApi, MockQuerier>,
+    ) {
+        let mut deps = mock_dependencies(45, &[]);
+        let env = mock_env("instantiator", &[]);
+        let init_msg = InitMsg {
+            name: "sec721".to_string(),
+            symbol: "S721".to_string(),
+            admin: Some(HumanAddr("admin".to_string())),
+            entropy: "We're going to need a bigger boat".to_string(),
+            config: Some(InitConfig {
+                enable_burn: Some(true),
+                transfers_enabled: Some(true),
+                open_minting: Some(false),
+                metadata_queries_enabled: Some(true),
+                ..InitConfig::default()
+            }),
+            post_init_callback: None,
+        };
+        (init(&mut deps, env, init_msg), deps)
+    }
+
+    fn signing_key() -> libsecp256k1::SecretKey {
+        libsecp256k1::SecretKey::parse(&[7u8; 32]).unwrap()
+    }
+
+    fn signer() -> HumanAddr {
+        let pubkey = libsecp256k1::PublicKey::from_secret_key(&signing_key());
+        pubkey_to_account(&pubkey.serialize_compressed()).unwrap()
+    }
+
+    fn sign_permit(
+        permit_name: &str,
+        contract: &str,
+        permissions: Vec<TokenPermissions>,
+    ) -> Permit {
+        let key = signing_key();
+        let pubkey = libsecp256k1::PublicKey::from_secret_key(&key);
+        let mut permit = Permit {
+            params: PermitParams {
+                allowed_tokens: vec![HumanAddr(contract.to_string())],
+                permit_name: permit_name.to_string(),
+                chain_id: "secret-4".to_string(),
+                permissions,
+            },
+            signature: PermitSignature {
+                pub_key: PubKey {
+                    r#type: "tendermint/PubKeySecp256k1".to_string(),
+                    value: Binary::from(&pubkey.serialize_compressed()[..]),
+                },
+                signature: Binary::from(&[][..]),
+            },
+        };
+        let hash = sha_256(permit.signed_bytes().unwrap().as_slice());
+        let (signature, _) = libsecp256k1::sign(&libsecp256k1::Message::parse(&hash), &key);
+        permit.signature.signature = Binary::from(&signature.serialize()[..]);
+        permit
+    }
+
+    fn query_tokens(
+        deps: &Extern<MockStorage, MockApi, MockQuerier>,
+        permit: Permit,
+    ) -> StdResult<Vec<String>> {
+        let query_msg = QueryMsg::WithPermit {
+            permit,
+            query: QueryWithPermit::Tokens {
+                owner: signer(),
+                start_after: None,
+                limit: None,
+            },
+        };
+        let query_answer: QueryAnswer = from_binary(&query(deps, query_msg)?)?;
+        match query_answer {
+            QueryAnswer::TokenList { tokens } => Ok(tokens),
+            _ => panic!("unexpected"),
+        }
+    }
+
+    #[test]
+    fn test_query_with_permit() {
+        let (init_result, mut deps) = init_permit_helper();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("NFT1".to_string()),
+            owner: Some(signer()),
+            public_metadata: None,
+            private_metadata: None,
+            public_teaser: None,
+            memo: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(handle_result.is_ok(), "Mint failed: {}", handle_result.err().unwrap());
+
+        // the recipient never created a viewing key
+        let permit = sign_permit("inbox", "cosmos2contract", vec![TokenPermissions::Owner]);
+        assert_eq!(query_tokens(&deps, permit).unwrap(), vec!["NFT1".to_string()]);
+
+        // the history permission only allows the transaction history
+        let permit = sign_permit("history", "cosmos2contract", vec![TokenPermissions::History]);
+        let query_msg = QueryMsg::WithPermit {
+            permit: permit.clone(),
+            query: QueryWithPermit::TransactionHistory {
+                page: None,
+                page_size: None,
+                cursor: None,
+                filter: None,
+            },
+        };
+        let query_answer: QueryAnswer = from_binary(&query(&deps, query_msg).unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::TransactionHistory { total, .. } => assert_eq!(total, 1),
+            _ => panic!("unexpected"),
+        }
+        let error = extract_error_msg(query_tokens(&deps, permit));
+        assert!(error.contains("Permit does not grant permission for this query"));
+
+        // a permit for another contract
+        let permit = sign_permit("inbox", "other contract", vec![TokenPermissions::Owner]);
+        let error = extract_error_msg(query_tokens(&deps, permit));
+        assert!(error.contains("Permit does not apply to this contract"));
+
+        // altering the signed params invalidates the signature
+        let mut permit = sign_permit("inbox", "cosmos2contract", vec![TokenPermissions::History]);
+        permit.params.permissions = vec![TokenPermissions::Owner];
+        let error = extract_error_msg(query_tokens(&deps, permit));
+        assert!(error.contains("Failed to verify the signature of the permit"));
+
+        // a revoked permit is rejected, but other permits still work
+        let handle_msg = HandleMsg::RevokePermit {
+            permit_name: "inbox".to_string(),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env(signer().as_str(), &[]), handle_msg);
+        assert!(handle_result.is_ok(), "Revoke failed: {}", handle_result.err().unwrap());
+        let permit = sign_permit("inbox", "cosmos2contract", vec![TokenPermissions::Owner]);
+        let error = extract_error_msg(query_tokens(&deps, permit));
+        assert!(error.contains("Permit inbox was revoked"));
+        let permit = sign_permit("wallet", "cosmos2contract", vec![TokenPermissions::Owner]);
+        assert_eq!(query_tokens(&deps, permit).unwrap(), vec!["NFT1".to_string()]);
+    }
+}
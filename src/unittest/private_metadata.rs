@@ -6,8 +6,7 @@ mod tests {
     use crate::contract::handle;
     use cosmwasm_std::testing::mock_env;
     use cosmwasm_std::HumanAddr;
-    use std::collections::HashSet;
-    use crate::state::{load, TOKENS_KEY, PREFIX_MAP_TO_INDEX, PREFIX_MAP_TO_ID, PREFIX_INFOS, json_load, PREFIX_PRIV_META, PREFIX_PUB_META, may_load};
+    use crate::state::{load, PREFIX_MAP_TO_INDEX, PREFIX_MAP_TO_ID, PREFIX_INFOS, PREFIX_PRIV_META, PREFIX_PUB_META, may_load, load_token};
     use cosmwasm_storage::ReadonlyPrefixedStorage;
 
     #[test]
@@ -32,7 +31,7 @@ mod tests {
         };
         let handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
         let error = extract_error_msg(handle_result);
-        assert!(error.contains("Not authorized to update metadata of token SNIP20"));
+        assert!(error.contains("Token SNIP20 does not exist"));
 
         let (init_result, mut deps) =
             init_helper_with_config(false, false, false, false, true, false, false);
@@ -51,6 +50,7 @@ mod tests {
                 image: Some("uri".to_string()),
             }),
             private_metadata: None,
+            public_teaser: None,
             memo: Some("Mint it baby!".to_string()),
             padding: None,
         };
@@ -75,6 +75,7 @@ mod tests {
                 image: Some("uri".to_string()),
             }),
             public_metadata: None,
+            public_teaser: None,
             memo: Some("Mint it baby!".to_string()),
             padding: None,
         };
@@ -116,8 +117,6 @@ mod tests {
         let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
         assert!(result.is_ok());
 
-        let tokens: HashSet<String> = load(&deps.storage, TOKENS_KEY).unwrap();
-        assert!(tokens.contains("MyNFT"));
         let map2idx = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_INDEX, &deps.storage);
         let index: u32 = load(&map2idx, "MyNFT".as_bytes()).unwrap();
         let token_key = index.to_le_bytes();
@@ -125,7 +124,7 @@ mod tests {
         let id: String = load(&map2id, &token_key).unwrap();
         assert_eq!("MyNFT".to_string(), id);
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &token_key).unwrap();
+        let token: Token = load_token(&info_store, &token_key).unwrap();
         assert!(token.unwrapped);
         let handle_msg = HandleMsg::SetPrivateMetadata {
             token_id: "MyNFT".to_string(),
@@ -199,6 +198,7 @@ mod tests {
                 image: Some("uri".to_string()),
             }),
             private_metadata: None,
+            public_teaser: None,
             memo: Some("Mint it baby!".to_string()),
             padding: None,
         };
@@ -52,7 +52,7 @@ mod tests {
         };
         let handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
         let error = extract_error_msg(handle_result);
-        assert!(error.contains("Not authorized to update metadata of token SNIP20"));
+        assert!(error.contains("Token SNIP20 does not exist"));
 
         // test setting metadata when status prevents it
         set_contract_status(&mut deps, ContractStatus::StopAll);
@@ -81,6 +81,7 @@ mod tests {
                 image: Some("uri".to_string()),
             }),
             private_metadata: None,
+            public_teaser: None,
             memo: Some("Mint it baby!".to_string()),
             padding: None,
         };
@@ -99,7 +100,7 @@ mod tests {
         };
         let handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
         let error = extract_error_msg(handle_result);
-        assert!(error.contains("Not authorized to update metadata"));
+        assert!(error.contains("Token MyNFT does not exist"));
 
         // test owner tries but not allowed to change metadata
         let handle_msg = HandleMsg::SetPublicMetadata {
@@ -113,7 +114,7 @@ mod tests {
         };
         let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
         let error = extract_error_msg(handle_result);
-        assert!(error.contains("Not authorized to update metadata"));
+        assert!(error.contains("Token MyNFT does not exist"));
 
         // test minter tries, but not allowed
         let (init_result, mut deps) =
@@ -132,6 +133,7 @@ mod tests {
                 image: Some("uri".to_string()),
             }),
             private_metadata: None,
+            public_teaser: None,
             memo: Some("Mint it baby!".to_string()),
             padding: None,
         };
@@ -149,7 +151,7 @@ mod tests {
         };
         let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
         let error = extract_error_msg(handle_result);
-        assert!(error.contains("Not authorized to update metadata"));
+        assert!(error.contains("Token MyNFT does not exist"));
 
         // sanity check: minter updates
         let (init_result, mut deps) = init_helper_default();
@@ -167,6 +169,7 @@ mod tests {
                 image: Some("uri".to_string()),
             }),
             private_metadata: None,
+            public_teaser: None,
             memo: Some("Mint it baby!".to_string()),
             padding: None,
         };
@@ -4,7 +4,7 @@ mod tests {
 
     use crate::contract::{handle, query};
     use crate::expiration::Expiration;
-    use crate::msg::{AccessLevel, HandleMsg, QueryAnswer, QueryMsg, Snip721Approval, Tx, TxAction, HandleAnswer};
+    use crate::msg::{AccessLevel, HandleMsg, QueryAnswer, QueryMsg, Snip721Approval, Tx, TxAction, TxActionType, TxFilter, HandleAnswer};
     use cosmwasm_std::testing::*;
     use cosmwasm_std::{
         from_binary, BlockInfo, Env, HumanAddr, MessageInfo,
@@ -239,12 +239,15 @@ mod tests {
             viewing_key: admin_viewing_key.clone(),
             page: None,
             page_size: None,
+            cursor: None,
+            filter: None,
         };
         let query_result = query(&deps, query_msg);
         let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
         match query_answer {
-            QueryAnswer::TransactionHistory { txs } => {
-                assert!(txs.is_empty());
+            QueryAnswer::TransactionHistory { total, txs } => {
+                assert_eq!(total, 0);
+assert!(txs.is_empty());
             }
             _ => panic!("unexpected"),
         }
@@ -253,6 +256,7 @@ mod tests {
             owner: None,
             public_metadata: None,
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -264,6 +268,7 @@ mod tests {
             owner: None,
             public_metadata: None,
             private_metadata: None,
+            public_teaser: None,
             memo: Some("Mint 2".to_string()),
             padding: None,
         };
@@ -315,12 +320,15 @@ mod tests {
             viewing_key: admin_viewing_key.clone(),
             page: None,
             page_size: None,
+            cursor: None,
+            filter: None,
         };
         let query_result = query(&deps, query_msg);
         let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
         match query_answer {
-            QueryAnswer::TransactionHistory { txs } => {
-                assert_eq!(
+            QueryAnswer::TransactionHistory { total, txs } => {
+                assert_eq!(total, 3);
+assert_eq!(
                     txs,
                     vec![burn2.clone(), mint2.clone(), mint1.clone()]
                 );
@@ -334,12 +342,15 @@ mod tests {
             viewing_key: admin_viewing_key.clone(),
             page: None,
             page_size: Some(1),
+            cursor: None,
+            filter: None,
         };
         let query_result = query(&deps, query_msg);
         let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
         match query_answer {
-            QueryAnswer::TransactionHistory { txs } => {
-                assert_eq!(txs, vec![burn2.clone()]);
+            QueryAnswer::TransactionHistory { total, txs } => {
+                assert_eq!(total, 3);
+assert_eq!(txs, vec![burn2.clone()]);
             }
             _ => panic!("unexpected"),
         }
@@ -350,12 +361,15 @@ mod tests {
             viewing_key: admin_viewing_key.clone(),
             page: Some(1),
             page_size: Some(1),
+            cursor: None,
+            filter: None,
         };
         let query_result = query(&deps, query_msg);
         let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
         match query_answer {
-            QueryAnswer::TransactionHistory { txs } => {
-                assert_eq!(txs, vec![mint2.clone()]);
+            QueryAnswer::TransactionHistory { total, txs } => {
+                assert_eq!(total, 3);
+assert_eq!(txs, vec![mint2.clone()]);
             }
             _ => panic!("unexpected"),
         }
@@ -366,17 +380,150 @@ mod tests {
             viewing_key: alice_viewing_key.clone(),
             page: None,
             page_size: None,
+            cursor: None,
+            filter: None,
         };
         let query_result = query(&deps, query_msg);
         let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
         match query_answer {
-            QueryAnswer::TransactionHistory { txs } => {
-                assert_eq!(txs, vec![burn2.clone()]);
+            QueryAnswer::TransactionHistory { total, txs } => {
+                assert_eq!(total, 1);
+assert_eq!(txs, vec![burn2.clone()]);
             }
             _ => panic!("unexpected"),
         }
     }
 
+    // test TransactionHistory cursor and filters
+    #[test]
+    fn test_transaction_history_cursor_and_filters() {
+        let (init_result, mut deps) =
+            init_helper_with_config(false, false, false, false, true, false, true);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+
+        let admin = HumanAddr("admin".to_string());
+        let handle_msg = HandleMsg::CreateViewingKey {
+            entropy: "key".to_string(),
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let answer: HandleAnswer = from_binary(&result.unwrap().data.unwrap()).unwrap();
+        let admin_viewing_key = match answer {
+            HandleAnswer::ViewingKey { key } => key,
+            _ => panic!("NOPE"),
+        };
+
+        // mint NFT0 through NFT4 at heights 100 through 104 and burn NFT1 at height 105
+        for i in 0..5u64 {
+            let handle_msg = HandleMsg::MintNft {
+                token_id: Some(format!("NFT{}", i)),
+                owner: None,
+                public_metadata: None,
+                private_metadata: None,
+                public_teaser: None,
+                memo: None,
+                padding: None,
+            };
+            let mut env = mock_env("admin", &[]);
+            env.block.height = 100 + i;
+            let result = handle(&mut deps, env, handle_msg);
+            assert!(result.is_ok());
+        }
+        let handle_msg = HandleMsg::BurnNft {
+            token_id: "NFT1".to_string(),
+            memo: None,
+            padding: None,
+        };
+        let mut env = mock_env("admin", &[]);
+        env.block.height = 105;
+        let result = handle(&mut deps, env, handle_msg);
+        assert!(result.is_ok());
+
+        let history = |cursor: Option<u64>, page_size: Option<u32>, filter: Option<TxFilter>| {
+            let query_msg = QueryMsg::TransactionHistory {
+                address: admin.clone(),
+                viewing_key: admin_viewing_key.clone(),
+                page: None,
+                page_size,
+                cursor,
+                filter,
+            };
+            let query_result = query(&deps, query_msg);
+            match from_binary(&query_result.unwrap()).unwrap() {
+                QueryAnswer::TransactionHistory { total, txs } => {
+                    assert_eq!(total, 6);
+                    txs.iter().map(|tx| tx.tx_id).collect::<Vec<u64>>()
+                }
+                _ => panic!("unexpected"),
+            }
+        };
+
+        // test paging with a cursor
+        assert_eq!(history(None, Some(2), None), vec![5, 4]);
+        assert_eq!(history(Some(4), Some(2), None), vec![3, 2]);
+        assert_eq!(history(Some(2), Some(2), None), vec![1, 0]);
+        assert_eq!(history(Some(0), Some(2), None), Vec::<u64>::new());
+        // test a cursor newer than any tx
+        assert_eq!(history(Some(100), Some(1), None), vec![5]);
+
+        // test filtering by action type
+        let filter = TxFilter {
+            action: Some(TxActionType::Burn),
+            ..TxFilter::default()
+        };
+        assert_eq!(history(None, None, Some(filter)), vec![5]);
+        let filter = TxFilter {
+            action: Some(TxActionType::Transfer),
+            ..TxFilter::default()
+        };
+        assert_eq!(history(None, None, Some(filter)), Vec::<u64>::new());
+
+        // test filtering by token id
+        let filter = TxFilter {
+            token_id: Some("NFT1".to_string()),
+            ..TxFilter::default()
+        };
+        assert_eq!(history(None, None, Some(filter)), vec![5, 1]);
+
+        // test filtering by block height range
+        let filter = TxFilter {
+            min_height: Some(101),
+            max_height: Some(103),
+            ..TxFilter::default()
+        };
+        assert_eq!(history(None, None, Some(filter.clone())), vec![3, 2, 1]);
+        assert_eq!(history(Some(3), Some(1), Some(filter)), vec![2]);
+        let filter = TxFilter {
+            max_height: Some(99),
+            ..TxFilter::default()
+        };
+        assert_eq!(history(None, None, Some(filter)), Vec::<u64>::new());
+        let filter = TxFilter {
+            max_height: Some(200),
+            ..TxFilter::default()
+        };
+        assert_eq!(history(None, Some(2), Some(filter)), vec![5, 4]);
+        let filter = TxFilter {
+            max_height: Some(102),
+            ..TxFilter::default()
+        };
+        assert_eq!(history(None, Some(2), Some(filter.clone())), vec![2, 1]);
+        assert_eq!(history(Some(5), Some(2), Some(filter)), vec![2, 1]);
+
+        // test combining filters
+        let filter = TxFilter {
+            action: Some(TxActionType::Mint),
+            token_id: Some("NFT1".to_string()),
+            min_height: Some(100),
+            max_height: None,
+        };
+        assert_eq!(history(None, None, Some(filter)), vec![1]);
+    }
+
     // test RegisteredCodeHash query
     #[test]
     fn test_query_registered_code_hash() {
@@ -391,18 +538,9 @@ mod tests {
         let query_msg = QueryMsg::RegisteredCodeHash {
             contract: HumanAddr("alice".to_string()),
         };
+        // receive registration is not supported when transfers are disabled
         let query_result = query(&deps, query_msg);
-        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
-        match query_answer {
-            QueryAnswer::RegisteredCodeHash {
-                code_hash,
-                also_implements_batch_receive_nft,
-            } => {
-                assert!(code_hash.is_none());
-                assert!(!also_implements_batch_receive_nft)
-            }
-            _ => panic!("unexpected"),
-        }
-
+        let error = extract_error_msg(query_result);
+        assert!(error.contains("ReceiveNft registration not supported."));
     }
 }
@@ -40,6 +40,7 @@ mod tests {
             owner: Some(alice.clone()),
             public_metadata: Some(public_meta.clone()),
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
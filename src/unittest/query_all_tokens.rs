@@ -1,8 +1,10 @@
 #[cfg(test)]
 mod tests {
     use crate::unittest::helpers::helpers::helpers::*;
-    use crate::msg::{QueryMsg};
-    use crate::contract::{query};
+    use crate::msg::{HandleAnswer, HandleMsg, QueryAnswer, QueryMsg, ViewerInfo};
+    use crate::contract::{handle, query};
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::{from_binary, HumanAddr};
 
     #[test]
     fn test_query_all_tokens() {
@@ -23,4 +25,77 @@ mod tests {
         let error = extract_error_msg(query_result);
         assert!(error.contains("Tokens cannot be queried"));
     }
+
+    #[test]
+    fn test_query_all_tokens_full_profile() {
+        let (init_result, mut deps) = init_helper_full_profile();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let handle_msg = HandleMsg::CreateViewingKey {
+            entropy: "akey".to_string(),
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let viewing_key = match from_binary(&result.unwrap().data.unwrap()).unwrap() {
+            HandleAnswer::ViewingKey { key } => key,
+            _ => panic!("unexpected"),
+        };
+        for id in &["NFT1", "NFT2", "NFT3", "NFT4"] {
+            let handle_msg = HandleMsg::MintNft {
+                token_id: Some(id.to_string()),
+                owner: None,
+                public_metadata: None,
+                private_metadata: None,
+                public_teaser: None,
+                memo: None,
+                padding: None,
+            };
+            let result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+            assert!(result.is_ok());
+        }
+        let handle_msg = HandleMsg::BurnNft {
+            token_id: "NFT2".to_string(),
+            memo: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(result.is_ok());
+
+        // token supply is private
+        let query_msg = QueryMsg::AllTokens {
+            viewer: None,
+            start_after: None,
+            limit: None,
+        };
+        let error = extract_error_msg(query(&deps, query_msg));
+        assert!(error.contains("The token supply of this contract is private"));
+
+        let viewer = ViewerInfo {
+            address: HumanAddr("admin".to_string()),
+            viewing_key,
+        };
+        let query_msg = QueryMsg::AllTokens {
+            viewer: Some(viewer.clone()),
+            start_after: None,
+            limit: None,
+        };
+        match from_binary(&query(&deps, query_msg).unwrap()).unwrap() {
+            QueryAnswer::TokenList { tokens } => {
+                assert_eq!(tokens, vec!["NFT1", "NFT3", "NFT4"]);
+            }
+            _ => panic!("unexpected"),
+        }
+        let query_msg = QueryMsg::AllTokens {
+            viewer: Some(viewer),
+            start_after: Some("NFT1".to_string()),
+            limit: Some(1),
+        };
+        match from_binary(&query(&deps, query_msg).unwrap()).unwrap() {
+            QueryAnswer::TokenList { tokens } => assert_eq!(tokens, vec!["NFT3"]),
+            _ => panic!("unexpected"),
+        }
+    }
 }
@@ -0,0 +1,192 @@
+#[cfg(test)]
+mod tests {
+    use crate::unittest::helpers::helpers::helpers::*;
+    use crate::msg::{QueryMsg, QueryAnswer, HandleMsg, HandleAnswer, ViewerInfo, ContractStatus};
+    use crate::contract::{query, handle};
+    use crate::error::ContractError;
+    use cosmwasm_std::{from_binary, HumanAddr, Binary, Extern};
+    use cosmwasm_std::testing::{mock_env, MockStorage, MockApi, MockQuerier};
+    use crate::token::Metadata;
+
+    fn can_retrieve(
+        deps: &Extern<MockStorage, MockApi, MockQuerier>,
+        token_id: &str,
+        viewer: Option<ViewerInfo>,
+    ) -> (bool, bool, Option<String>) {
+        let query_msg = QueryMsg::CanRetrieve {
+            token_id: token_id.to_string(),
+            viewer,
+        };
+        let query_result: Binary = query(deps, query_msg).unwrap();
+        match from_binary(&query_result).unwrap() {
+            QueryAnswer::CanRetrieve {
+                retrievable,
+                sealed,
+                reason,
+            } => (retrievable, sealed, reason),
+            _ => panic!("unexpected"),
+        }
+    }
+
+    // test CanRetrieve query
+    #[test]
+    fn test_can_retrieve() {
+        let (init_result, deps) =
+            init_helper_with_config(true, false, true, false, false, false, true);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+
+        // test token not found when supply is public
+        let (retrievable, sealed, reason) = can_retrieve(&deps, "MyNFT", None);
+        assert!(!retrievable);
+        assert!(!sealed);
+        assert_eq!(
+            reason,
+            Some(
+                ContractError::TokenNotFound {
+                    token_id: "MyNFT".to_string(),
+                }
+                .to_string()
+            )
+        );
+
+        let (init_result, mut deps) =
+            init_helper_with_config(false, false, true, false, false, false, true);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+
+        // test token not found when supply is private
+        let (retrievable, sealed, reason) = can_retrieve(&deps, "MyNFT", None);
+        assert!(!retrievable);
+        assert!(!sealed);
+        assert_eq!(
+            reason,
+            Some(
+                ContractError::TokenUnavailable {
+                    token_id: "MyNFT".to_string(),
+                }
+                .to_string()
+            )
+        );
+
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("MyNFT".to_string()),
+            owner: Some(HumanAddr("alice".to_string())),
+            private_metadata: Some(Metadata {
+                name: Some("MySealedNFT".to_string()),
+                description: Some("Sealed metadata test".to_string()),
+                image: Some("sealed_uri".to_string()),
+            }),
+            public_metadata: None,
+            public_teaser: None,
+            memo: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(result.is_ok());
+
+        // test sealed token looks like a missing one when supply is private
+        let (retrievable, sealed, reason) = can_retrieve(&deps, "MyNFT", None);
+        assert!(!retrievable);
+        assert!(!sealed);
+        assert_eq!(
+            reason,
+            Some(
+                ContractError::TokenUnavailable {
+                    token_id: "MyNFT".to_string(),
+                }
+                .to_string()
+            )
+        );
+
+        // test bad viewing key
+        let viewer = ViewerInfo {
+            address: HumanAddr("alice".to_string()),
+            viewing_key: "key".to_string(),
+        };
+        let query_msg = QueryMsg::CanRetrieve {
+            token_id: "MyNFT".to_string(),
+            viewer: Some(viewer),
+        };
+        let query_result = query(&deps, query_msg);
+        let error = extract_error_msg(query_result);
+        assert!(error.contains("Wrong viewing key for this address or viewing key not set"));
+
+        // test the owner is told to reveal
+        let handle_msg = HandleMsg::CreateViewingKey {
+            entropy: "akey".to_string(),
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let akey = match from_binary(&result.unwrap().data.unwrap()).unwrap() {
+            HandleAnswer::ViewingKey { key } => key,
+            _ => panic!("unexpected"),
+        };
+        let viewer = ViewerInfo {
+            address: HumanAddr("alice".to_string()),
+            viewing_key: akey,
+        };
+        let (retrievable, sealed, reason) = can_retrieve(&deps, "MyNFT", Some(viewer.clone()));
+        assert!(!retrievable);
+        assert!(sealed);
+        assert_eq!(
+            reason,
+            Some(
+                "Sealed metadata must be unwrapped by calling Reveal before it can be viewed"
+                    .to_string()
+            )
+        );
+
+        let handle_msg = HandleMsg::Reveal {
+            token_id: "MyNFT".to_string(),
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(result.is_ok());
+
+        // test contract status prevents burning
+        set_contract_status(&mut deps, ContractStatus::StopTransactions);
+        let (retrievable, sealed, reason) = can_retrieve(&deps, "MyNFT", None);
+        assert!(!retrievable);
+        assert!(!sealed);
+        assert_eq!(
+            reason,
+            Some(ContractError::ContractDisabled.to_string())
+        );
+        set_contract_status(&mut deps, ContractStatus::Normal);
+
+        // sanity check
+        let (retrievable, sealed, reason) = can_retrieve(&deps, "MyNFT", Some(viewer));
+        assert!(retrievable);
+        assert!(!sealed);
+        assert!(reason.is_none());
+        let (retrievable, _sealed, _reason) = can_retrieve(&deps, "MyNFT", None);
+        assert!(retrievable);
+
+        // the answer agrees with an actual burn
+        let handle_msg = HandleMsg::BurnNft {
+            token_id: "MyNFT".to_string(),
+            memo: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        assert!(result.is_ok());
+        let (retrievable, _sealed, reason) = can_retrieve(&deps, "MyNFT", None);
+        assert!(!retrievable);
+        assert_eq!(
+            reason,
+            Some(
+                ContractError::TokenUnavailable {
+                    token_id: "MyNFT".to_string(),
+                }
+                .to_string()
+            )
+        );
+    }
+}
@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod tests {
     use crate::unittest::helpers::helpers::helpers::*;
-    use crate::msg::{QueryMsg, QueryAnswer};
+    use crate::msg::{QueryMsg, QueryAnswer, TxRetention};
     use crate::contract::query;
     use cosmwasm_std::from_binary;
 
@@ -31,6 +31,15 @@ mod tests {
                 minter_may_update_metadata,
                 owner_may_update_metadata,
                 burn_is_enabled,
+                retrieval_window,
+                tx_retention,
+                private_tx_records,
+                secret_padding,
+                transfers_enabled,
+                gifting_enabled,
+                open_minting,
+                metadata_queries_enabled,
+                burn_reveals_secret,
             } => {
                 assert_eq!(token_supply_is_public, false);
                 assert_eq!(owner_is_public, true);
@@ -39,6 +48,44 @@ mod tests {
                 assert_eq!(minter_may_update_metadata, true);
                 assert_eq!(owner_may_update_metadata, false);
                 assert_eq!(burn_is_enabled, true);
+                assert_eq!(retrieval_window, None);
+                assert_eq!(tx_retention, TxRetention::default());
+                assert!(!private_tx_records);
+                assert_eq!(secret_padding, None);
+                // the one-time secret profile is the default
+                assert!(!transfers_enabled);
+                // gifting sealed tokens must be opted in to
+                assert!(!gifting_enabled);
+                assert!(open_minting);
+                assert!(!metadata_queries_enabled);
+                assert!(burn_reveals_secret);
+            }
+            _ => panic!("unexpected"),
+        }
+    }
+
+    #[test]
+    fn test_query_full_profile_config() {
+        let (init_result, deps) = init_helper_full_profile();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let query_result = query(&deps, QueryMsg::ContractConfig {});
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::ContractConfig {
+                transfers_enabled,
+                open_minting,
+                metadata_queries_enabled,
+                burn_reveals_secret,
+                ..
+            } => {
+                assert!(transfers_enabled);
+                assert!(!open_minting);
+                assert!(metadata_queries_enabled);
+                assert!(!burn_reveals_secret);
             }
             _ => panic!("unexpected"),
         }
@@ -0,0 +1,212 @@
+#[cfg(test)]
+mod tests {
+    use crate::unittest::helpers::helpers::helpers::*;
+    use cosmwasm_std::{HumanAddr, from_binary, Extern};
+    use crate::msg::{AccessLevel, HandleMsg, HandleAnswer, InboxSecret, InitConfig, InitMsg, QueryMsg, QueryAnswer};
+    use crate::contract::{handle, init, query};
+    use crate::token::Metadata;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage};
+
+    fn inbox(
+        deps: &Extern<MockStorage, MockApi, MockQuerier>,
+        viewing_key: &str,
+        cursor: Option<&str>,
+        limit: Option<u32>,
+    ) -> Vec<InboxSecret> {
+        let query_msg = QueryMsg::Inbox {
+            address: HumanAddr("alice".to_string()),
+            viewing_key: viewing_key.to_string(),
+            cursor: cursor.map(|id| id.to_string()),
+            limit,
+        };
+        let query_answer: QueryAnswer = from_binary(&query(deps, query_msg).unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::Inbox { secrets } => secrets,
+            _ => panic!("unexpected"),
+        }
+    }
+
+    fn create_key(deps: &mut Extern<MockStorage, MockApi, MockQuerier>, address: &str) -> String {
+        let handle_msg = HandleMsg::CreateViewingKey {
+            entropy: "key".to_string(),
+            padding: None,
+        };
+        let result = handle(deps, mock_env(address, &[]), handle_msg);
+        let answer: HandleAnswer = from_binary(&result.unwrap().data.unwrap()).unwrap();
+        match answer {
+            HandleAnswer::ViewingKey { key } => key,
+            _ => panic!("NOPE"),
+        }
+    }
+
+    fn ids(secrets: &[InboxSecret]) -> Vec<&str> {
+        secrets.iter().map(|s| s.token_id.as_str()).collect()
+    }
+
+    // test Inbox query
+    #[test]
+    fn test_query_inbox() {
+        let (init_result, mut deps) = init_helper_full_profile();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let alice = HumanAddr("alice".to_string());
+        let teaser = Metadata {
+            name: Some("For you".to_string()),
+            description: None,
+            image: None,
+        };
+        for (id, owner) in &[("NFT1", "alice"), ("NFT2", "admin"), ("NFT3", "alice"), ("NFT4", "alice")] {
+            let handle_msg = HandleMsg::MintNft {
+                token_id: Some(id.to_string()),
+                owner: Some(HumanAddr(owner.to_string())),
+                public_metadata: Some(teaser.clone()),
+                private_metadata: None,
+                public_teaser: None,
+                memo: None,
+                padding: None,
+            };
+            let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+            assert!(handle_result.is_ok(), "Mint failed: {}", handle_result.err().unwrap());
+        }
+        // tokens minted for yourself are not in your inbox
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("NFT5".to_string()),
+            owner: None,
+            public_metadata: None,
+            private_metadata: None,
+            public_teaser: None,
+            memo: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(handle_result.is_ok(), "Mint failed: {}", handle_result.err().unwrap());
+        let akey = create_key(&mut deps, "alice");
+        let admin_key = create_key(&mut deps, "admin");
+
+        // the creator is hidden while its ownership is private
+        let secrets = inbox(&deps, &akey, None, None);
+        assert_eq!(ids(&secrets), vec!["NFT1", "NFT3", "NFT4"]);
+        assert_eq!(
+            secrets[0],
+            InboxSecret {
+                token_id: "NFT1".to_string(),
+                creator: None,
+                public_metadata: Some(teaser),
+                minted_at: Some(mock_env("admin", &[]).block.height),
+            }
+        );
+        let query_msg = QueryMsg::Inbox {
+            address: HumanAddr("admin".to_string()),
+            viewing_key: admin_key.clone(),
+            cursor: None,
+            limit: None,
+        };
+        let query_answer: QueryAnswer = from_binary(&query(&deps, query_msg).unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::Inbox { secrets } => assert!(secrets.is_empty()),
+            _ => panic!("unexpected"),
+        }
+        let handle_msg = HandleMsg::SetGlobalApproval {
+            token_id: None,
+            view_owner: Some(AccessLevel::All),
+            view_private_metadata: None,
+            expires: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(handle_result.is_ok(), "Approve failed: {}", handle_result.err().unwrap());
+        let secrets = inbox(&deps, &akey, None, None);
+        assert!(secrets.iter().all(|s| s.creator == Some(HumanAddr("admin".to_string()))));
+
+        // paginate
+        assert_eq!(ids(&inbox(&deps, &akey, None, Some(2))), vec!["NFT1", "NFT3"]);
+        assert_eq!(ids(&inbox(&deps, &akey, Some("NFT3"), None)), vec!["NFT4"]);
+        let query_msg = QueryMsg::Inbox {
+            address: alice.clone(),
+            viewing_key: akey.clone(),
+            cursor: Some("NFT2".to_string()),
+            limit: None,
+        };
+        let error = extract_error_msg(query(&deps, query_msg));
+        assert!(error.contains("Token ID: NFT2 is not in the list of tokens"));
+
+        // burning and transferring remove secrets from the inbox
+        let handle_msg = HandleMsg::BurnNft {
+            token_id: "NFT3".to_string(),
+            memo: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(handle_result.is_ok(), "Burn failed: {}", handle_result.err().unwrap());
+        assert_eq!(ids(&inbox(&deps, &akey, None, None)), vec!["NFT1", "NFT4"]);
+        let handle_msg = HandleMsg::TransferNft {
+            recipient: HumanAddr("bob".to_string()),
+            token_id: "NFT1".to_string(),
+            recipient_only: None,
+            memo: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(handle_result.is_ok(), "Transfer failed: {}", handle_result.err().unwrap());
+        assert_eq!(ids(&inbox(&deps, &akey, None, None)), vec!["NFT4"]);
+
+        // nobody else can read the inbox
+        let query_msg = QueryMsg::Inbox {
+            address: alice,
+            viewing_key: admin_key,
+            cursor: None,
+            limit: None,
+        };
+        let error = extract_error_msg(query(&deps, query_msg));
+        assert!(error.contains("Wrong viewing key"));
+    }
+
+    // test the inbox only shows a teaser the creator allowed viewing before the burn
+    #[test]
+    fn test_query_inbox_hides_private_teaser() {
+        let mut deps = mock_dependencies(20, &[]);
+        let init_msg = InitMsg {
+            name: "sec721".to_string(),
+            symbol: "S721".to_string(),
+            admin: Some(HumanAddr("admin".to_string())),
+            entropy: "We're going to need a bigger boat".to_string(),
+            config: Some(InitConfig {
+                open_minting: Some(false),
+                ..InitConfig::default()
+            }),
+            post_init_callback: None,
+        };
+        let init_result = init(&mut deps, mock_env("instantiator", &[]), init_msg);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let teaser = Metadata {
+            name: Some("For you".to_string()),
+            description: None,
+            image: None,
+        };
+        for (id, public_teaser) in &[("NFT1", None), ("NFT2", Some(true))] {
+            let handle_msg = HandleMsg::MintNft {
+                token_id: Some(id.to_string()),
+                owner: Some(HumanAddr("alice".to_string())),
+                public_metadata: Some(teaser.clone()),
+                private_metadata: None,
+                public_teaser: *public_teaser,
+                memo: None,
+                padding: None,
+            };
+            let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+            assert!(handle_result.is_ok(), "Mint failed: {}", handle_result.err().unwrap());
+        }
+        let akey = create_key(&mut deps, "alice");
+        let secrets = inbox(&deps, &akey, None, None);
+        assert_eq!(ids(&secrets), vec!["NFT1", "NFT2"]);
+        assert!(secrets[0].public_metadata.is_none());
+        assert_eq!(secrets[1].public_metadata, Some(teaser));
+    }
+}
@@ -1,8 +1,9 @@
 #[cfg(test)]
 mod tests {
     use crate::unittest::helpers::helpers::helpers::*;
-    use crate::msg::{QueryMsg};
+    use crate::msg::{QueryAnswer, QueryMsg};
     use crate::contract::{query};
+    use cosmwasm_std::{from_binary, HumanAddr};
 
     // test minters query
     #[test]
@@ -19,4 +20,24 @@ mod tests {
         let error = extract_error_msg(query_result);
         assert!(error.contains("Minting is not restricted, minter list not supported"));
     }
+
+    // test minters query when minting is restricted
+    #[test]
+    fn test_query_minters_full_profile() {
+        let (init_result, deps) = init_helper_full_profile();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+
+        let query_msg = QueryMsg::Minters {};
+        let query_answer: QueryAnswer = from_binary(&query(&deps, query_msg).unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::Minters { minters } => {
+                assert_eq!(minters, vec![HumanAddr("admin".to_string())]);
+            }
+            _ => panic!("unexpected"),
+        }
+    }
 }
@@ -3,6 +3,7 @@ mod tests {
     use crate::unittest::helpers::helpers::helpers::*;
     use crate::msg::{QueryMsg, HandleMsg, AccessLevel, QueryAnswer, ViewerInfo, Snip721Approval, HandleAnswer};
     use crate::contract::{query, handle};
+    use crate::error::ContractError;
     use crate::token::Metadata;
     use cosmwasm_std::{HumanAddr, Env, BlockInfo, MessageInfo, from_binary};
     use cosmwasm_std::testing::{mock_env, MOCK_CONTRACT_ADDR};
@@ -44,7 +45,7 @@ mod tests {
         };
         let query_result = query(&deps, query_msg);
         let error = extract_error_msg(query_result);
-        assert!(error.contains("You are not authorized to perform this action on token NFT1"));
+        assert!(error.contains("Token NFT1 does not exist"));
 
         let public_meta = Metadata {
             name: Some("Name1".to_string()),
@@ -65,6 +66,7 @@ mod tests {
             owner: Some(alice.clone()),
             public_metadata: Some(public_meta.clone()),
             private_metadata: Some(private_meta.clone()),
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -140,7 +142,12 @@ mod tests {
                 assert!(private_metadata.is_none());
                 assert_eq!(
                     display_private_metadata_error,
-                    Some("You are not authorized to perform this action on token NFT1".to_string())
+                    Some(
+                        ContractError::TokenUnavailable {
+                            token_id: "NFT1".to_string(),
+                        }
+                        .to_string()
+                    )
                 );
                 assert!(owner_is_public);
                 assert_eq!(public_ownership_expiration, Some(Expiration::Never));
@@ -167,6 +174,7 @@ mod tests {
             owner: Some(alice.clone()),
             public_metadata: Some(public_meta.clone()),
             private_metadata: Some(private_meta.clone()),
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -270,7 +278,10 @@ mod tests {
             expires: Some(Expiration::AtTime(1000)),
             padding: None,
         };
-        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        // set before public ownership expires so it is not cleaned up
+        let mut env = mock_env("alice", &[]);
+        env.block.height = 1;
+        let result = handle(&mut deps, env, handle_msg);
         assert!(result.is_ok());
 
         let handle_msg = HandleMsg::SetWhitelistedApproval {
@@ -528,6 +539,7 @@ mod tests {
             owner: Some(alice.clone()),
             public_metadata: Some(public_meta.clone()),
             private_metadata: Some(private_meta.clone()),
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -678,7 +690,12 @@ mod tests {
                 assert!(private_metadata.is_none());
                 assert_eq!(
                     display_private_metadata_error,
-                    Some("Access to token NFT1 has expired".to_string())
+                    Some(
+                        ContractError::TokenUnavailable {
+                            token_id: "NFT1".to_string(),
+                        }
+                        .to_string()
+                    )
                 );
                 assert!(!owner_is_public);
                 assert!(public_ownership_expiration.is_none());
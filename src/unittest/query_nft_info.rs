@@ -1,9 +1,10 @@
 #[cfg(test)]
 mod tests {
     use crate::unittest::helpers::helpers::helpers::*;
-    use crate::msg::{QueryMsg, HandleMsg};
+    use crate::msg::{QueryMsg, HandleMsg, QueryAnswer};
     use crate::contract::{query, handle};
-    use cosmwasm_std::{HumanAddr};
+    use crate::error::ContractError;
+    use cosmwasm_std::{HumanAddr, from_binary};
     use crate::token::Metadata;
     use cosmwasm_std::testing::mock_env;
 
@@ -24,7 +25,13 @@ mod tests {
         };
         let query_result = query(&deps, query_msg);
         let err = extract_error_msg(query_result);
-        assert_eq!(err, "Token must be burned to retrieve metadata.");
+        assert_eq!(
+            err,
+            ContractError::TokenNotFound {
+                token_id: "NFT1".to_string(),
+            }
+            .payload()
+        );
 
         let (init_result, mut deps) =
             init_helper_with_config(false, false, false, false, true, false, true);
@@ -34,13 +41,19 @@ mod tests {
             init_result.err().unwrap()
         );
 
-        // test token not found when supply is public
+        // test token not found when supply is private
         let query_msg = QueryMsg::NftInfo {
             token_id: "NFT1".to_string(),
         };
         let query_result = query(&deps, query_msg);
         let err = extract_error_msg(query_result);
-        assert_eq!(err, "Token must be burned to retrieve metadata.");
+        assert_eq!(
+            err,
+            ContractError::TokenUnavailable {
+                token_id: "NFT1".to_string(),
+            }
+            .payload()
+        );
 
         let alice = HumanAddr("alice".to_string());
         let public_meta = Metadata {
@@ -53,17 +66,140 @@ mod tests {
             owner: Some(alice.clone()),
             public_metadata: Some(public_meta.clone()),
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
         let _handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
 
-        // sanity check
+        // test creator did not opt in to showing a teaser
         let query_msg = QueryMsg::NftInfo {
             token_id: "NFT1".to_string(),
         };
         let query_result = query(&deps, query_msg);
         let err = extract_error_msg(query_result);
-        assert_eq!(err, "Token must be burned to retrieve metadata.");
+        assert_eq!(
+            err,
+            ContractError::TokenUnavailable {
+                token_id: "NFT1".to_string(),
+            }
+            .payload()
+        );
+
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("NFT2".to_string()),
+            owner: Some(alice.clone()),
+            public_metadata: Some(public_meta.clone()),
+            private_metadata: Some(Metadata {
+                name: Some("Secret2".to_string()),
+                description: Some("PrivDesc2".to_string()),
+                image: Some("PrivUri2".to_string()),
+            }),
+            public_teaser: Some(true),
+            memo: None,
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+
+        // sanity check, teaser shows only the public metadata
+        let query_msg = QueryMsg::NftInfo {
+            token_id: "NFT2".to_string(),
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::NftInfo {
+                name,
+                description,
+                image,
+            } => {
+                assert_eq!(name, public_meta.name);
+                assert_eq!(description, public_meta.description);
+                assert_eq!(image, public_meta.image);
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // private metadata stays hidden until burn
+        let query_msg = QueryMsg::PrivateMetadata {
+            token_id: "NFT2".to_string(),
+            viewer: None,
+        };
+        let query_result = query(&deps, query_msg);
+        let err = extract_error_msg(query_result);
+        assert_eq!(err, ContractError::NotBurned.payload());
+
+        // teaser without public metadata is empty
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("NFT3".to_string()),
+            owner: Some(alice.clone()),
+            public_metadata: None,
+            private_metadata: None,
+            public_teaser: Some(true),
+            memo: None,
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let query_msg = QueryMsg::NftInfo {
+            token_id: "NFT3".to_string(),
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::NftInfo {
+                name,
+                description,
+                image,
+            } => {
+                assert!(name.is_none());
+                assert!(description.is_none());
+                assert!(image.is_none());
+            }
+            _ => panic!("unexpected"),
+        }
+    }
+
+    // test NftInfo query when metadata queries are enabled
+    #[test]
+    fn test_nft_info_full_profile() {
+        let (init_result, mut deps) = init_helper_full_profile();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let public_meta = Metadata {
+            name: Some("Name1".to_string()),
+            description: Some("PubDesc1".to_string()),
+            image: Some("PubUri1".to_string()),
+        };
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("NFT1".to_string()),
+            owner: None,
+            public_metadata: Some(public_meta.clone()),
+            private_metadata: None,
+            public_teaser: None,
+            memo: None,
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+
+        // public metadata is displayed even though it is not a teaser
+        let query_msg = QueryMsg::NftInfo {
+            token_id: "NFT1".to_string(),
+        };
+        let query_answer: QueryAnswer = from_binary(&query(&deps, query_msg).unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::NftInfo {
+                name,
+                description,
+                image,
+            } => {
+                assert_eq!(name, public_meta.name);
+                assert_eq!(description, public_meta.description);
+                assert_eq!(image, public_meta.image);
+            }
+            _ => panic!("unexpected"),
+        }
     }
 }
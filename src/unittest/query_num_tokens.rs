@@ -6,6 +6,8 @@ mod tests {
     use crate::contract::{handle, query};
     use cosmwasm_std::testing::{mock_env, MockStorage, MockApi, MockQuerier};
     use crate::unittest::helpers::minters::minters::*;
+    use crate::state::{may_load, remove, save, TOKENS_KEY, TOKEN_COUNT_KEY};
+    use std::collections::HashSet;
 
     #[test]
     fn test_query_num_tokens() {
@@ -111,6 +113,59 @@ mod tests {
         }
     }
 
+    // test contracts deployed with the legacy token list keep their count
+    #[test]
+    fn test_query_num_tokens_legacy_list() {
+        let (init_result, mut deps) =
+            init_helper_with_config(true, true, false, false, true, false, true);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+
+        mint_nft1_alice(&mut deps);
+        mint_nft2_alice(&mut deps);
+
+        // replace the count with the list an older contract would have stored
+        let mut legacy: HashSet<String> = HashSet::new();
+        legacy.insert("NFT1".to_string());
+        legacy.insert("NFT2".to_string());
+        remove(&mut deps.storage, TOKEN_COUNT_KEY);
+        save(&mut deps.storage, TOKENS_KEY, &legacy).unwrap();
+
+        let query_msg = QueryMsg::NumTokens { viewer: None };
+        let query_result = query(&deps, query_msg);
+        match from_binary(&query_result.unwrap()).unwrap() {
+            QueryAnswer::NumTokens { count } => {
+                assert_eq!(count, 2);
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test the next burn migrates to the stored count
+        let handle_msg = HandleMsg::BurnNft {
+            token_id: "NFT1".to_string(),
+            memo: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+        let tokens: Option<HashSet<String>> = may_load(&deps.storage, TOKENS_KEY).unwrap();
+        assert!(tokens.is_none());
+        let count: Option<u32> = may_load(&deps.storage, TOKEN_COUNT_KEY).unwrap();
+        assert_eq!(count, Some(1));
+
+        let query_msg = QueryMsg::NumTokens { viewer: None };
+        let query_result = query(&deps, query_msg);
+        match from_binary(&query_result.unwrap()).unwrap() {
+            QueryAnswer::NumTokens { count } => {
+                assert_eq!(count, 1);
+            }
+            _ => panic!("unexpected"),
+        }
+    }
+
     fn create_viewing_key(mut deps: &mut Extern<MockStorage, MockApi, MockQuerier>) -> String {
         let handle_msg = HandleMsg::CreateViewingKey {
             entropy: "blah".to_string(),
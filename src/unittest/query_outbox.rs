@@ -0,0 +1,135 @@
+#[cfg(test)]
+mod tests {
+    use crate::unittest::helpers::helpers::helpers::*;
+    use cosmwasm_std::{HumanAddr, from_binary, Extern};
+    use crate::msg::{AccessLevel, HandleMsg, HandleAnswer, OutboxSecret, QueryMsg, QueryAnswer};
+    use crate::contract::{handle, query};
+    use crate::token::Metadata;
+    use cosmwasm_std::testing::{mock_env, MockApi, MockQuerier, MockStorage};
+
+    fn outbox(
+        deps: &Extern<MockStorage, MockApi, MockQuerier>,
+        viewing_key: &str,
+        start_after: Option<&str>,
+        limit: Option<u32>,
+    ) -> Vec<OutboxSecret> {
+        let query_msg = QueryMsg::Outbox {
+            address: HumanAddr("alice".to_string()),
+            viewing_key: viewing_key.to_string(),
+            start_after: start_after.map(|id| id.to_string()),
+            limit,
+        };
+        let query_answer: QueryAnswer = from_binary(&query(deps, query_msg).unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::Outbox { secrets } => secrets,
+            _ => panic!("unexpected"),
+        }
+    }
+
+    // test Outbox query
+    #[test]
+    fn test_query_outbox() {
+        let (init_result, mut deps) = init_helper_full_profile();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let alice = HumanAddr("alice".to_string());
+        let teaser = Metadata {
+            name: Some("My1".to_string()),
+            description: Some("Public 1".to_string()),
+            image: None,
+        };
+        for (id, owner) in &[("NFT1", "alice"), ("NFT2", "admin"), ("NFT3", "alice")] {
+            let handle_msg = HandleMsg::MintNft {
+                token_id: Some(id.to_string()),
+                owner: Some(HumanAddr(owner.to_string())),
+                public_metadata: Some(teaser.clone()),
+                private_metadata: Some(Metadata {
+                    name: Some("the secret".to_string()),
+                    description: None,
+                    image: None,
+                }),
+                public_teaser: Some(true),
+                memo: None,
+                padding: None,
+            };
+            let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+            assert!(handle_result.is_ok(), "Mint failed: {}", handle_result.err().unwrap());
+        }
+        let handle_msg = HandleMsg::SetWhitelistedApproval {
+            address: HumanAddr("bob".to_string()),
+            token_id: Some("NFT1".to_string()),
+            view_owner: None,
+            view_private_metadata: Some(AccessLevel::ApproveToken),
+            transfer: None,
+            expires: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(handle_result.is_ok(), "Approve failed: {}", handle_result.err().unwrap());
+        let handle_msg = HandleMsg::CreateViewingKey {
+            entropy: "akey".to_string(),
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let answer: HandleAnswer = from_binary(&result.unwrap().data.unwrap()).unwrap();
+        let akey = match answer {
+            HandleAnswer::ViewingKey { key } => key,
+            _ => panic!("NOPE"),
+        };
+
+        let secrets = outbox(&deps, &akey, None, None);
+        let minted_at = Some(mock_env("admin", &[]).block.height);
+        assert_eq!(
+            secrets,
+            vec![
+                OutboxSecret {
+                    token_id: "NFT1".to_string(),
+                    public_metadata: Some(teaser.clone()),
+                    minted_at,
+                    sealed: false,
+                    recipient_only: false,
+                    recipients: vec![HumanAddr("bob".to_string())],
+                },
+                OutboxSecret {
+                    token_id: "NFT3".to_string(),
+                    public_metadata: Some(teaser),
+                    minted_at,
+                    sealed: false,
+                    recipient_only: false,
+                    recipients: vec![],
+                },
+            ]
+        );
+
+        // paginate
+        let secrets = outbox(&deps, &akey, None, Some(1));
+        assert_eq!(secrets.len(), 1);
+        assert_eq!(secrets[0].token_id, "NFT1".to_string());
+        let secrets = outbox(&deps, &akey, Some("NFT1"), Some(1));
+        assert_eq!(secrets.len(), 1);
+        assert_eq!(secrets[0].token_id, "NFT3".to_string());
+        assert!(outbox(&deps, &akey, Some("NFT3"), None).is_empty());
+
+        // the cursor must be in the inventory
+        let query_msg = QueryMsg::Outbox {
+            address: alice.clone(),
+            viewing_key: akey,
+            start_after: Some("NFT2".to_string()),
+            limit: None,
+        };
+        let error = extract_error_msg(query(&deps, query_msg));
+        assert!(error.contains("Token ID: NFT2 is not in the list of tokens"));
+
+        let query_msg = QueryMsg::Outbox {
+            address: alice,
+            viewing_key: "wrong".to_string(),
+            start_after: None,
+            limit: None,
+        };
+        let error = extract_error_msg(query(&deps, query_msg));
+        assert!(error.contains("Wrong viewing key"));
+    }
+}
@@ -170,7 +170,7 @@ mod tests {
         };
         let query_result = query(&deps, query_msg);
         let error = extract_error_msg(query_result);
-        assert!(error.contains("You are not authorized to view the owner of token NFT1"));
+        assert!(error.contains("Token NFT1 does not exist"));
 
         // test owner can see approvals including expired
         let handle_msg = HandleMsg::SetGlobalApproval {
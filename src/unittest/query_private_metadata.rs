@@ -2,8 +2,9 @@
 mod tests {
     use crate::unittest::helpers::helpers::helpers::*;
     use cosmwasm_std::{HumanAddr, from_binary};
-    use crate::msg::{HandleMsg, AccessLevel, QueryMsg, ViewerInfo, HandleAnswer};
+    use crate::msg::{HandleMsg, AccessLevel, QueryAnswer, QueryMsg, ViewerInfo, HandleAnswer};
     use crate::contract::{handle, query};
+    use crate::error::ContractError;
     use cosmwasm_std::testing::mock_env;
     use crate::token::Metadata;
 
@@ -39,6 +40,7 @@ mod tests {
             owner: Some(alice.clone()),
             public_metadata: None,
             private_metadata: Some(private_meta.clone()),
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -60,7 +62,7 @@ mod tests {
         };
         let query_result = query(&deps, query_msg);
         let err = extract_error_msg(query_result);
-        assert_eq!(err, "Token must be burned to retrieve metadata.");
+        assert_eq!(err, ContractError::NotBurned.payload());
 
         let handle_msg = HandleMsg::SetGlobalApproval {
             token_id: Some("NFT1".to_string()),
@@ -78,7 +80,7 @@ mod tests {
         };
         let query_result = query(&deps, query_msg);
         let err = extract_error_msg(query_result);
-        assert_eq!(err, "Token must be burned to retrieve metadata.");
+        assert_eq!(err, ContractError::NotBurned.payload());
 
         let (init_result, mut deps) =
             init_helper_with_config(false, false, true, false, true, false, true);
@@ -121,6 +123,7 @@ mod tests {
             owner: Some(alice.clone()),
             public_metadata: None,
             private_metadata: Some(private_meta.clone()),
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -137,7 +140,7 @@ mod tests {
         };
         let query_result = query(&deps, query_msg);
         let err = extract_error_msg(query_result);
-        assert_eq!(err, "Token must be burned to retrieve metadata.");
+        assert_eq!(err, ContractError::NotBurned.payload());
 
         let handle_msg = HandleMsg::Reveal {
             token_id: "NFT1".to_string(),
@@ -156,7 +159,7 @@ mod tests {
         };
         let query_result = query(&deps, query_msg);
         let err = extract_error_msg(query_result);
-        assert_eq!(err, "Token must be burned to retrieve metadata.");
+        assert_eq!(err, ContractError::NotBurned.payload());
 
         // test viewer not permitted
         let query_msg = QueryMsg::PrivateMetadata {
@@ -168,6 +171,77 @@ mod tests {
         };
         let query_result = query(&deps, query_msg);
         let err = extract_error_msg(query_result);
-        assert_eq!(err, "Token must be burned to retrieve metadata.");
+        assert_eq!(err, ContractError::NotBurned.payload());
+    }
+
+    // test PrivateMetadata query when metadata queries are enabled
+    #[test]
+    fn test_private_metadata_full_profile() {
+        let (init_result, mut deps) = init_helper_full_profile();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let handle_msg = HandleMsg::CreateViewingKey {
+            entropy: "akey".to_string(),
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let answer: HandleAnswer = from_binary(&result.unwrap().data.unwrap()).unwrap();
+        let viewing_key = match answer {
+            HandleAnswer::ViewingKey { key } => key,
+            _ => panic!("NOPE"),
+        };
+        let private_meta = Metadata {
+            name: Some("Name1".to_string()),
+            description: Some("PrivDesc1".to_string()),
+            image: Some("PrivUri1".to_string()),
+        };
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("NFT1".to_string()),
+            owner: None,
+            public_metadata: None,
+            private_metadata: Some(private_meta.clone()),
+            public_teaser: None,
+            memo: None,
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+
+        // not permitted without a viewer
+        let query_msg = QueryMsg::PrivateMetadata {
+            token_id: "NFT1".to_string(),
+            viewer: None,
+        };
+        let err = extract_error_msg(query(&deps, query_msg));
+        assert_eq!(
+            err,
+            ContractError::TokenUnavailable {
+                token_id: "NFT1".to_string(),
+            }
+            .payload()
+        );
+
+        // the owner can view it
+        let query_msg = QueryMsg::PrivateMetadata {
+            token_id: "NFT1".to_string(),
+            viewer: Some(ViewerInfo {
+                address: HumanAddr("admin".to_string()),
+                viewing_key,
+            }),
+        };
+        match from_binary(&query(&deps, query_msg).unwrap()).unwrap() {
+            QueryAnswer::PrivateMetadata {
+                name,
+                description,
+                image,
+            } => {
+                assert_eq!(name, private_meta.name);
+                assert_eq!(description, private_meta.description);
+                assert_eq!(image, private_meta.image);
+            }
+            _ => panic!("unexpected"),
+        }
     }
 }
@@ -56,7 +56,7 @@ mod tests {
         };
         let query_result = query(&deps, query_msg);
         let error = extract_error_msg(query_result);
-        assert!(error.contains("You are not authorized to view approvals for token NFT1"));
+        assert!(error.contains("Token NFT1 does not exist"));
 
         mint_generic_token(&mut deps, "NFT1");
 
@@ -4,7 +4,11 @@ mod tests {
     use cosmwasm_std::{HumanAddr, from_binary};
     use crate::msg::{HandleMsg, QueryMsg, QueryAnswer, AccessLevel, HandleAnswer};
     use crate::contract::{handle, query};
-    use cosmwasm_std::testing::{mock_env};
+    use cosmwasm_std::testing::{mock_env, MockStorage, MockApi, MockQuerier};
+    use cosmwasm_std::{Api, Extern};
+    use cosmwasm_storage::{PrefixedStorage, ReadonlyPrefixedStorage};
+    use crate::state::{may_load, remove, save, PREFIX_INVENTORY, PREFIX_OWNED};
+    use std::collections::HashSet;
 
     // test Tokens query
     #[test]
@@ -165,7 +169,7 @@ mod tests {
             owner: alice.clone(),
             viewer: None,
             viewing_key: Some(akey.clone()),
-            start_after: Some("NFT34".to_string()),
+            start_after: Some("NFT3".to_string()),
             limit: Some(30),
         };
         let query_result = query(&deps, query_msg);
@@ -213,4 +217,187 @@ mod tests {
             _ => panic!("unexpected"),
         }
     }
+
+    fn query_tokens(
+        deps: &Extern<MockStorage, MockApi, MockQuerier>,
+        viewer: Option<HumanAddr>,
+        viewing_key: Option<String>,
+        start_after: Option<&str>,
+        limit: Option<u32>,
+    ) -> Vec<String> {
+        let query_msg = QueryMsg::Tokens {
+            owner: HumanAddr("alice".to_string()),
+            viewer,
+            viewing_key,
+            start_after: start_after.map(|s| s.to_string()),
+            limit,
+        };
+        let query_result = query(deps, query_msg);
+        match from_binary(&query_result.unwrap()).unwrap() {
+            QueryAnswer::TokenList { tokens } => tokens,
+            _ => panic!("unexpected"),
+        }
+    }
+
+    // test paginating the Tokens query with a cursor
+    #[test]
+    fn test_query_tokens_pagination() {
+        let (init_result, mut deps) =
+            init_helper_with_config(false, false, false, false, true, false, true);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let handle_msg = HandleMsg::CreateViewingKey {
+            entropy: "akey".to_string(),
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let akey = match from_binary(&result.unwrap().data.unwrap()).unwrap() {
+            HandleAnswer::ViewingKey { key } => key,
+            _ => panic!("NOPE"),
+        };
+        let handle_msg = HandleMsg::CreateViewingKey {
+            entropy: "bkey".to_string(),
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        let bkey = match from_binary(&result.unwrap().data.unwrap()).unwrap() {
+            HandleAnswer::ViewingKey { key } => key,
+            _ => panic!("NOPE"),
+        };
+
+        mint_generic_token(&mut deps, "NFT1");
+        mint_generic_token(&mut deps, "NFT2");
+        mint_generic_token(&mut deps, "NFT3");
+        mint_generic_token(&mut deps, "NFT4");
+        mint_generic_token(&mut deps, "NFT5");
+
+        // test walking the inventory a page at a time
+        let tokens = query_tokens(&deps, None, Some(akey.clone()), None, Some(2));
+        assert_eq!(tokens, vec!["NFT1".to_string(), "NFT2".to_string()]);
+        let tokens = query_tokens(&deps, None, Some(akey.clone()), Some("NFT2"), Some(2));
+        assert_eq!(tokens, vec!["NFT3".to_string(), "NFT4".to_string()]);
+        let tokens = query_tokens(&deps, None, Some(akey.clone()), Some("NFT4"), Some(2));
+        assert_eq!(tokens, vec!["NFT5".to_string()]);
+        let tokens = query_tokens(&deps, None, Some(akey.clone()), Some("NFT5"), Some(2));
+        assert!(tokens.is_empty());
+
+        // test the last token takes the place of a burned one
+        let handle_msg = HandleMsg::BurnNft {
+            token_id: "NFT2".to_string(),
+            memo: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(result.is_ok());
+        let tokens = query_tokens(&deps, None, Some(akey.clone()), None, None);
+        let expected = vec![
+            "NFT1".to_string(),
+            "NFT5".to_string(),
+            "NFT3".to_string(),
+            "NFT4".to_string(),
+        ];
+        assert_eq!(tokens, expected);
+
+        // test a cursor that is not in the inventory
+        let query_msg = QueryMsg::Tokens {
+            owner: HumanAddr("alice".to_string()),
+            viewer: None,
+            viewing_key: Some(akey.clone()),
+            start_after: Some("NFT2".to_string()),
+            limit: None,
+        };
+        let query_result = query(&deps, query_msg);
+        let error = extract_error_msg(query_result);
+        assert!(error.contains(
+            "Token ID: NFT2 is not in the list of tokens you are permitted to view"
+        ));
+
+        // test a cursor does not leak the ownership of a token the querier can not see
+        let query_msg = QueryMsg::Tokens {
+            owner: HumanAddr("alice".to_string()),
+            viewer: Some(HumanAddr("bob".to_string())),
+            viewing_key: Some(bkey.clone()),
+            start_after: Some("NFT1".to_string()),
+            limit: None,
+        };
+        let query_result = query(&deps, query_msg);
+        let error = extract_error_msg(query_result);
+        assert!(error.contains(
+            "Token ID: NFT1 is not in the list of tokens you are permitted to view"
+        ));
+
+        // test a viewer only pages through the tokens they may see
+        let handle_msg = HandleMsg::SetWhitelistedApproval {
+            address: HumanAddr("bob".to_string()),
+            token_id: Some("NFT5".to_string()),
+            view_owner: Some(AccessLevel::ApproveToken),
+            view_private_metadata: None,
+            transfer: None,
+            expires: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(result.is_ok());
+        let handle_msg = HandleMsg::SetWhitelistedApproval {
+            address: HumanAddr("bob".to_string()),
+            token_id: Some("NFT4".to_string()),
+            view_owner: Some(AccessLevel::ApproveToken),
+            view_private_metadata: None,
+            transfer: None,
+            expires: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(result.is_ok());
+        let bob = Some(HumanAddr("bob".to_string()));
+        let tokens = query_tokens(&deps, bob.clone(), Some(bkey.clone()), None, Some(1));
+        assert_eq!(tokens, vec!["NFT5".to_string()]);
+        let tokens = query_tokens(&deps, bob, Some(bkey), Some("NFT5"), Some(1));
+        assert_eq!(tokens, vec!["NFT4".to_string()]);
+    }
+
+    // test inventories saved as a single list are still listed and get migrated
+    #[test]
+    fn test_query_tokens_legacy_inventory() {
+        let (init_result, mut deps) =
+            init_helper_with_config(false, true, false, false, true, false, true);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        mint_generic_token(&mut deps, "NFT1");
+        mint_generic_token(&mut deps, "NFT2");
+
+        // replace alice's inventory with the list an older contract would have stored
+        let alice_raw = deps
+            .api
+            .canonical_address(&HumanAddr("alice".to_string()))
+            .unwrap();
+        let mut legacy: HashSet<u32> = HashSet::new();
+        legacy.insert(0);
+        legacy.insert(1);
+        let mut inv_store =
+            PrefixedStorage::multilevel(&[PREFIX_INVENTORY, alice_raw.as_slice()], &mut deps.storage);
+        remove(&mut inv_store, b"len");
+        let mut owned_store = PrefixedStorage::new(PREFIX_OWNED, &mut deps.storage);
+        save(&mut owned_store, alice_raw.as_slice(), &legacy).unwrap();
+
+        let tokens = query_tokens(&deps, None, None, None, None);
+        assert_eq!(tokens, vec!["NFT1".to_string(), "NFT2".to_string()]);
+        let tokens = query_tokens(&deps, None, None, Some("NFT1"), None);
+        assert_eq!(tokens, vec!["NFT2".to_string()]);
+
+        // test the next change to the inventory migrates it
+        mint_generic_token(&mut deps, "NFT3");
+        let owned_store = ReadonlyPrefixedStorage::new(PREFIX_OWNED, &deps.storage);
+        let owned: Option<HashSet<u32>> = may_load(&owned_store, alice_raw.as_slice()).unwrap();
+        assert!(owned.is_none());
+        let tokens = query_tokens(&deps, None, None, None, None);
+        let expected = vec!["NFT1".to_string(), "NFT2".to_string(), "NFT3".to_string()];
+        assert_eq!(tokens, expected);
+    }
 }
@@ -1,10 +1,43 @@
 #[cfg(test)]
 mod tests {
     use crate::unittest::helpers::helpers::helpers::*;
-    use cosmwasm_std::{HumanAddr, from_binary};
+    use cosmwasm_std::{HumanAddr, from_binary, Extern};
     use crate::msg::{HandleMsg, QueryMsg, QueryAnswer, HandleAnswer};
     use crate::contract::{handle, query};
-    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::testing::{mock_env, MockApi, MockQuerier, MockStorage};
+
+    fn create_key(deps: &mut Extern<MockStorage, MockApi, MockQuerier>, address: &str) -> String {
+        let handle_msg = HandleMsg::CreateViewingKey {
+            entropy: "ckey".to_string(),
+            padding: None,
+        };
+        let result = handle(deps, mock_env(address, &[]), handle_msg);
+        let answer: HandleAnswer = from_binary(&result.unwrap().data.unwrap()).unwrap();
+        match answer {
+            HandleAnswer::ViewingKey { key } => key,
+            _ => panic!("NOPE"),
+        }
+    }
+
+    fn verify(
+        deps: &Extern<MockStorage, MockApi, MockQuerier>,
+        token_ids: &[&str],
+        viewing_key: &str,
+    ) -> (bool, Option<String>) {
+        let query_msg = QueryMsg::VerifyTransferApproval {
+            token_ids: token_ids.iter().map(|id| id.to_string()).collect(),
+            address: HumanAddr("charlie".to_string()),
+            viewing_key: viewing_key.to_string(),
+        };
+        let query_answer: QueryAnswer = from_binary(&query(deps, query_msg).unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::VerifyTransferApproval {
+                approved_for_all,
+                first_unapproved_token,
+            } => (approved_for_all, first_unapproved_token),
+            _ => panic!("unexpected"),
+        }
+    }
 
     // test VerifyTransferApproval query
     #[test]
@@ -18,43 +51,80 @@ mod tests {
         );
 
         let charlie = HumanAddr("charlie".to_string());
-
-        let handle_msg = HandleMsg::CreateViewingKey {
-            entropy: "ckey".to_string(),
-            padding: None,
-        };
-        let result = handle(&mut deps, mock_env("charlie", &[]), handle_msg);
-        let answer: HandleAnswer = from_binary(&result.unwrap().data.unwrap()).unwrap();
-        let viewing_key = match answer {
-            HandleAnswer::ViewingKey { key } => key,
-            _ => panic!("NOPE"),
-        };
-
-        let nft1 = "NFT1".to_string();
-        let nft2 = "NFT2".to_string();
-        let nft3 = "NFT3".to_string();
-        let nft4 = "NFT4".to_string();
+        let viewing_key = create_key(&mut deps, "charlie");
 
         mint_generic_token(&mut deps,"NFT1");
         mint_generic_token(&mut deps,"NFT2");
 
+        // transfers are not enabled in this deployment profile
         let query_msg = QueryMsg::VerifyTransferApproval {
-            token_ids: vec![nft1.clone(), nft2.clone(), nft3.clone(), nft4.clone()],
-            address: charlie.clone(),
-            viewing_key: viewing_key.clone(),
+            token_ids: vec!["NFT1".to_string(), "NFT2".to_string()],
+            address: charlie,
+            viewing_key,
         };
-        let query_result = query(&deps, query_msg);
-        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
-        match query_answer {
-            QueryAnswer::VerifyTransferApproval {
-                approved_for_all,
-                first_unapproved_token,
-            } => {
-                assert!(!approved_for_all);
-                assert_eq!(first_unapproved_token, Some(nft1));
-            }
-            _ => panic!("unexpected"),
+        let error = extract_error_msg(query(&deps, query_msg));
+        assert!(error.contains("Token transfer not permitted"));
+    }
+
+    #[test]
+    fn test_verify_transfer_approval_full_profile() {
+        let (init_result, mut deps) = init_helper_full_profile();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let viewing_key = create_key(&mut deps, "charlie");
+        for id in &["NFT1", "NFT2", "NFT3"] {
+            let handle_msg = HandleMsg::MintNft {
+                token_id: Some(id.to_string()),
+                owner: None,
+                public_metadata: None,
+                private_metadata: None,
+                public_teaser: None,
+                memo: None,
+                padding: None,
+            };
+            let result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+            assert!(result.is_ok(), "Mint failed: {}", result.err().unwrap());
         }
+        let handle_msg = HandleMsg::Approve {
+            spender: HumanAddr("charlie".to_string()),
+            token_id: "NFT1".to_string(),
+            expires: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(result.is_ok(), "Approve failed: {}", result.err().unwrap());
+        let handle_msg = HandleMsg::Approve {
+            spender: HumanAddr("charlie".to_string()),
+            token_id: "NFT3".to_string(),
+            expires: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(result.is_ok(), "Approve failed: {}", result.err().unwrap());
+
+        // an empty list is trivially approved
+        assert_eq!(verify(&deps, &[], &viewing_key), (true, None));
+        assert_eq!(verify(&deps, &["NFT1", "NFT3"], &viewing_key), (true, None));
+        assert_eq!(
+            verify(&deps, &["NFT1", "NFT2", "NFT3"], &viewing_key),
+            (false, Some("NFT2".to_string()))
+        );
+        // nonexistent tokens are not approved
+        assert_eq!(
+            verify(&deps, &["NFT1", "NFT4"], &viewing_key),
+            (false, Some("NFT4".to_string()))
+        );
 
+        // a wrong viewing key fails
+        let query_msg = QueryMsg::VerifyTransferApproval {
+            token_ids: vec!["NFT1".to_string()],
+            address: HumanAddr("charlie".to_string()),
+            viewing_key: "wrong".to_string(),
+        };
+        let error = extract_error_msg(query(&deps, query_msg));
+        assert!(error.contains("Wrong viewing key"));
     }
 }
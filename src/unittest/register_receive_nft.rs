@@ -1,9 +1,10 @@
 #[cfg(test)]
 mod tests {
     use crate::unittest::helpers::helpers::helpers::*;
-    use crate::msg::{HandleMsg};
-    use crate::contract::handle;
+    use crate::msg::{HandleMsg, QueryAnswer, QueryMsg};
+    use crate::contract::{handle, query};
     use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::{from_binary, HumanAddr};
 
     // test register receive_nft
     #[test]
@@ -23,5 +24,60 @@ mod tests {
         let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
         let error = extract_error_msg(handle_result);
         assert!(error.contains("Token transfer not permitted. ReceiveNft registration not supported."));
+
+        let query_msg = QueryMsg::RegisteredCodeHash {
+            contract: HumanAddr("alice".to_string()),
+        };
+        let error = extract_error_msg(query(&deps, query_msg));
+        assert!(error.contains("ReceiveNft registration not supported."));
+    }
+
+    #[test]
+    fn test_registered_code_hash_full_profile() {
+        let (init_result, mut deps) = init_helper_full_profile();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+
+        let handle_msg = HandleMsg::RegisterReceiveNft {
+            code_hash: "alice code hash".to_string(),
+            also_implements_batch_receive_nft: Some(true),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(handle_result.is_ok(), "Register failed: {}", handle_result.err().unwrap());
+
+        let query_msg = QueryMsg::RegisteredCodeHash {
+            contract: HumanAddr("alice".to_string()),
+        };
+        let query_answer: QueryAnswer = from_binary(&query(&deps, query_msg).unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::RegisteredCodeHash {
+                code_hash,
+                also_implements_batch_receive_nft,
+            } => {
+                assert_eq!(code_hash, Some("alice code hash".to_string()));
+                assert!(also_implements_batch_receive_nft);
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // unregistered contracts have no code hash
+        let query_msg = QueryMsg::RegisteredCodeHash {
+            contract: HumanAddr("bob".to_string()),
+        };
+        let query_answer: QueryAnswer = from_binary(&query(&deps, query_msg).unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::RegisteredCodeHash {
+                code_hash,
+                also_implements_batch_receive_nft,
+            } => {
+                assert_eq!(code_hash, None);
+                assert!(!also_implements_batch_receive_nft);
+            }
+            _ => panic!("unexpected"),
+        }
     }
 }
@@ -0,0 +1,182 @@
+#[cfg(test)]
+mod tests {
+    use crate::unittest::helpers::helpers::helpers::*;
+    use crate::msg::{
+        Burn, ContractStatus, HandleAnswer, HandleMsg, InitConfig, InitMsg, QueryAnswer, QueryMsg,
+        RetrievedSecret, TokenData,
+    };
+    use crate::contract::{handle, init, query};
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{from_binary, Binary, Extern, HumanAddr, InitResponse, StdResult};
+
+    fn init_helper_with_window(
+        retrieval_window: u64,
+    ) -> (
+        StdResult<InitResponse>,
+        Extern<MockStorage, MockApi, MockQuerier>,
+    ) {
+        let mut deps = mock_dependencies(20, &[]);
+        let env = mock_env("instantiator", &[]);
+        let init_config: InitConfig = from_binary(&Binary::from(
+            format!(
+                "{{\"enable_burn\":true,\"retrieval_window\":{}}}",
+                retrieval_window
+            )
+            .as_bytes(),
+        ))
+        .unwrap();
+        let init_msg = InitMsg {
+            name: "sec721".to_string(),
+            symbol: "S721".to_string(),
+            admin: Some(HumanAddr("admin".to_string())),
+            entropy: "We're going to need a bigger boat".to_string(),
+            config: Some(init_config),
+            post_init_callback: None,
+        };
+
+        (init(&mut deps, env, init_msg), deps)
+    }
+
+    fn create_key(deps: &mut Extern<MockStorage, MockApi, MockQuerier>, address: &str) -> String {
+        let handle_msg = HandleMsg::CreateViewingKey {
+            entropy: "entropy".to_string(),
+            padding: None,
+        };
+        let result = handle(deps, mock_env(address, &[]), handle_msg);
+        match from_binary(&result.unwrap().data.unwrap()).unwrap() {
+            HandleAnswer::ViewingKey { key } => key,
+            _ => panic!("unexpected"),
+        }
+    }
+
+    fn retrieved_secrets(
+        deps: &Extern<MockStorage, MockApi, MockQuerier>,
+        address: &str,
+        viewing_key: &str,
+    ) -> Vec<RetrievedSecret> {
+        let query_msg = QueryMsg::RetrievedSecrets {
+            address: HumanAddr(address.to_string()),
+            viewing_key: viewing_key.to_string(),
+        };
+        let query_result = query(deps, query_msg);
+        match from_binary(&query_result.unwrap()).unwrap() {
+            QueryAnswer::RetrievedSecrets { secrets } => secrets,
+            _ => panic!("unexpected"),
+        }
+    }
+
+    // test secrets retained within the retrieval window
+    #[test]
+    fn test_retrieved_secrets() {
+        let (init_result, mut deps) = init_helper_with_window(10);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        mint_nft1_alice_grant_bob_charlie(&mut deps);
+        mint_nft3_alice_grant_bob(&mut deps);
+        let bkey = create_key(&mut deps, "bob");
+        let akey = create_key(&mut deps, "alice");
+
+        // test nothing retained before burning
+        assert!(retrieved_secrets(&deps, "bob", &bkey).is_empty());
+
+        let handle_msg = HandleMsg::BurnNft {
+            token_id: "NFT3".to_string(),
+            memo: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        assert!(result.is_ok());
+
+        // test bad viewing key
+        let query_msg = QueryMsg::RetrievedSecrets {
+            address: HumanAddr("bob".to_string()),
+            viewing_key: akey.clone(),
+        };
+        let query_result = query(&deps, query_msg);
+        let error = extract_error_msg(query_result);
+        assert!(error.contains("Wrong viewing key for this address or viewing key not set"));
+
+        // test only the burner can retrieve
+        assert!(retrieved_secrets(&deps, "alice", &akey).is_empty());
+
+        let nft3 = RetrievedSecret {
+            secret: TokenData {
+                token_id: "NFT3".to_string(),
+                name: Some("MyNFT3".to_string()),
+                description: Some("privmetadata3".to_string()),
+                image: Some("privuri3".to_string()),
+            },
+            expires_at: 12_355,
+        };
+        assert_eq!(retrieved_secrets(&deps, "bob", &bkey), vec![nft3.clone()]);
+
+        // test later burns are added with their own expiration
+        let mut env = mock_env("bob", &[]);
+        env.block.height = 12_350;
+        let handle_msg = HandleMsg::BatchBurnNft {
+            burns: vec![Burn {
+                token_ids: vec!["NFT1".to_string()],
+                memo: None,
+            }],
+            best_effort: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, env, handle_msg);
+        assert!(result.is_ok());
+        let secrets = retrieved_secrets(&deps, "bob", &bkey);
+        assert_eq!(secrets.len(), 2);
+        assert_eq!(secrets[0], nft3);
+        assert_eq!(secrets[1].secret.token_id, "NFT1".to_string());
+        assert_eq!(secrets[1].expires_at, 12_360);
+
+        // test expired secrets are no longer displayed
+        let mut env = mock_env("bob", &[]);
+        env.block.height = 12_355;
+        let handle_msg = HandleMsg::MakeOwnershipPrivate { padding: None };
+        let result = handle(&mut deps, env, handle_msg);
+        assert!(result.is_ok());
+        let secrets = retrieved_secrets(&deps, "bob", &bkey);
+        assert_eq!(secrets.len(), 1);
+        assert_eq!(secrets[0].secret.token_id, "NFT1".to_string());
+
+        // test forgetting when status prevents it
+        set_contract_status(&mut deps, ContractStatus::StopAll);
+        let handle_msg = HandleMsg::ForgetRetrieved { padding: None };
+        let result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        let error = extract_error_msg(result);
+        assert!(error.contains("The contract admin has temporarily disabled this action"));
+        set_contract_status(&mut deps, ContractStatus::StopTransactions);
+
+        // test forgetting early
+        let handle_msg = HandleMsg::ForgetRetrieved { padding: None };
+        let result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        assert!(result.is_ok());
+        assert!(retrieved_secrets(&deps, "bob", &bkey).is_empty());
+    }
+
+    // test secrets are not retained unless the retrieval window is enabled
+    #[test]
+    fn test_retrieved_secrets_disabled() {
+        let (init_result, mut deps) =
+            init_helper_with_config(false, false, false, false, false, false, true);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        mint_nft3_alice_grant_bob(&mut deps);
+        let bkey = create_key(&mut deps, "bob");
+
+        let handle_msg = HandleMsg::BurnNft {
+            token_id: "NFT3".to_string(),
+            memo: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        assert!(result.is_ok());
+        assert!(retrieved_secrets(&deps, "bob", &bkey).is_empty());
+    }
+}
@@ -6,8 +6,7 @@ mod tests {
     use cosmwasm_std::testing::mock_env;
     use cosmwasm_std::HumanAddr;
     use crate::token::Metadata;
-    use std::collections::HashSet;
-    use crate::state::{load, TOKENS_KEY, PREFIX_MAP_TO_INDEX, PREFIX_MAP_TO_ID, PREFIX_PRIV_META, PREFIX_PUB_META, may_load};
+    use crate::state::{load, PREFIX_MAP_TO_INDEX, PREFIX_MAP_TO_ID, PREFIX_PRIV_META, PREFIX_PUB_META, may_load};
     use cosmwasm_storage::ReadonlyPrefixedStorage;
 
     // test Reveal
@@ -45,7 +44,7 @@ mod tests {
         };
         let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
         let error = extract_error_msg(handle_result);
-        assert!(error.contains("You do not own token MyNFT"));
+        assert!(error.contains("Token MyNFT does not exist"));
 
         let (init_result, mut deps) =
             init_helper_with_config(false, false, false, false, false, false, false);
@@ -64,6 +63,7 @@ mod tests {
                 image: Some("sealed_uri".to_string()),
             }),
             public_metadata: None,
+            public_teaser: None,
             memo: Some("Mint it baby!".to_string()),
             padding: None,
         };
@@ -109,6 +109,7 @@ mod tests {
                 image: Some("sealed_uri".to_string()),
             }),
             public_metadata: None,
+            public_teaser: None,
             memo: Some("Mint it baby!".to_string()),
             padding: None,
         };
@@ -121,7 +122,7 @@ mod tests {
         };
         let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
         let error = extract_error_msg(handle_result);
-        assert!(error.contains("You do not own token MyNFT"));
+        assert!(error.contains("Token MyNFT does not exist"));
 
         // sanity check, unwrap to public metadata
         let handle_msg = HandleMsg::Reveal {
@@ -131,8 +132,6 @@ mod tests {
         let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
         assert!(result.is_ok());
 
-        let tokens: HashSet<String> = load(&deps.storage, TOKENS_KEY).unwrap();
-        assert!(tokens.contains("MyNFT"));
         let map2idx = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_INDEX, &deps.storage);
         let index: u32 = load(&map2idx, "MyNFT".as_bytes()).unwrap();
         let token_key = index.to_le_bytes();
@@ -156,7 +155,7 @@ mod tests {
             token_id: "MyNFT".to_string(),
             padding: None,
         };
-        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
         let error = extract_error_msg(handle_result);
         assert!(error.contains("This token has already been unwrapped"));
 
@@ -177,6 +176,7 @@ mod tests {
                 image: Some("sealed_uri".to_string()),
             }),
             public_metadata: None,
+            public_teaser: None,
             memo: Some("Mint it baby!".to_string()),
             padding: None,
         };
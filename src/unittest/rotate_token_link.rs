@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod tests {
+    use crate::unittest::helpers::helpers::helpers::*;
+    use crate::msg::{HandleMsg, HandleAnswer, ContractStatus};
+    use crate::contract::handle;
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::{HumanAddr, from_binary};
+    use crate::token::Metadata;
+    use crate::state::{load, may_load, TOKEN_COUNT_KEY, PREFIX_MAP_TO_INDEX, PREFIX_MAP_TO_ID, PREFIX_PRIV_META};
+    use cosmwasm_storage::ReadonlyPrefixedStorage;
+
+    // test RotateTokenLink
+    #[test]
+    fn test_rotate_token_link() {
+        let (init_result, mut deps) =
+            init_helper_with_config(true, false, true, false, false, false, true);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+
+        // test token does not exist when supply is public
+        let handle_msg = HandleMsg::RotateTokenLink {
+            token_id: "MyNFT".to_string(),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Token ID: MyNFT not found"));
+
+        let (init_result, mut deps) =
+            init_helper_with_config(false, false, true, false, false, false, true);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+
+        // test token does not exist when supply is private
+        let handle_msg = HandleMsg::RotateTokenLink {
+            token_id: "MyNFT".to_string(),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Token MyNFT does not exist"));
+
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("MyNFT".to_string()),
+            owner: Some(HumanAddr("alice".to_string())),
+            private_metadata: Some(Metadata {
+                name: Some("MySealedNFT".to_string()),
+                description: Some("Sealed metadata test".to_string()),
+                image: Some("sealed_uri".to_string()),
+            }),
+            public_metadata: None,
+            public_teaser: None,
+            memo: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(result.is_ok());
+
+        // test someone other than the owner tries to rotate
+        let handle_msg = HandleMsg::RotateTokenLink {
+            token_id: "MyNFT".to_string(),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Token MyNFT does not exist"));
+
+        // test rotating when status prevents it
+        set_contract_status(&mut deps, ContractStatus::StopAll);
+        let handle_msg = HandleMsg::RotateTokenLink {
+            token_id: "MyNFT".to_string(),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("The contract admin has temporarily disabled this action"));
+        set_contract_status(&mut deps, ContractStatus::StopTransactions);
+
+        let map2idx = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_INDEX, &deps.storage);
+        let index: u32 = load(&map2idx, "MyNFT".as_bytes()).unwrap();
+
+        // sanity check
+        let handle_msg = HandleMsg::RotateTokenLink {
+            token_id: "MyNFT".to_string(),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let response = handle_result.unwrap();
+        assert!(response.log.is_empty());
+        let new_id = match from_binary(&response.data.unwrap()).unwrap() {
+            HandleAnswer::RotateTokenLink { token_id } => token_id,
+            _ => panic!("unexpected"),
+        };
+        assert_ne!(new_id, "MyNFT".to_string());
+        assert_eq!(new_id.len(), 32);
+
+        // the new id points to the same index and the old id is gone
+        let count: u32 = load(&deps.storage, TOKEN_COUNT_KEY).unwrap();
+        assert_eq!(count, 1);
+        let map2idx = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_INDEX, &deps.storage);
+        let old_idx: Option<u32> = may_load(&map2idx, "MyNFT".as_bytes()).unwrap();
+        assert!(old_idx.is_none());
+        let new_idx: u32 = load(&map2idx, new_id.as_bytes()).unwrap();
+        assert_eq!(new_idx, index);
+        let map2id = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_ID, &deps.storage);
+        let id: String = load(&map2id, &index.to_le_bytes()).unwrap();
+        assert_eq!(id, new_id);
+        let priv_store = ReadonlyPrefixedStorage::new(PREFIX_PRIV_META, &deps.storage);
+        let priv_meta: Option<Metadata> = may_load(&priv_store, &index.to_le_bytes()).unwrap();
+        assert!(priv_meta.is_some());
+
+        // the old id now behaves like a nonexistent token
+        let handle_msg = HandleMsg::RotateTokenLink {
+            token_id: "MyNFT".to_string(),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Token MyNFT does not exist"));
+
+        // rotating again yields yet another id
+        let handle_msg = HandleMsg::RotateTokenLink {
+            token_id: new_id.clone(),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let newer_id = match from_binary(&handle_result.unwrap().data.unwrap()).unwrap() {
+            HandleAnswer::RotateTokenLink { token_id } => token_id,
+            _ => panic!("unexpected"),
+        };
+        assert_ne!(newer_id, new_id);
+        let map2idx = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_INDEX, &deps.storage);
+        let old_idx: Option<u32> = may_load(&map2idx, new_id.as_bytes()).unwrap();
+        assert!(old_idx.is_none());
+    }
+}
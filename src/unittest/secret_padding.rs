@@ -0,0 +1,179 @@
+#[cfg(test)]
+mod tests {
+    use crate::unittest::helpers::helpers::helpers::*;
+    use crate::msg::{
+        Burn, HandleAnswer, HandleMsg, InitConfig, InitMsg, QueryAnswer, QueryMsg, SecretPadding,
+    };
+    use crate::contract::{handle, init, query};
+    use crate::token::Metadata;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{from_binary, Extern, HumanAddr, InitResponse, StdResult};
+
+    fn init_helper_padded(
+        min_bucket: u32,
+        max_secret_size: u32,
+    ) -> (
+        StdResult<InitResponse>,
+        Extern<MockStorage, MockApi, MockQuerier>,
+    ) {
+        let mut deps = mock_dependencies(20, &[]);
+        let env = mock_env("instantiator", &[]);
+        let init_msg = InitMsg {
+            name: "sec721".to_string(),
+            symbol: "S721".to_string(),
+            admin: Some(HumanAddr("admin".to_string())),
+            entropy: "We're going to need a bigger boat".to_string(),
+            config: Some(InitConfig {
+                enable_burn: Some(true),
+                retrieval_window: Some(100),
+                secret_padding: Some(SecretPadding {
+                    min_bucket,
+                    max_secret_size,
+                }),
+                ..InitConfig::default()
+            }),
+            post_init_callback: None,
+        };
+
+        (init(&mut deps, env, init_msg), deps)
+    }
+
+    fn secret(len: usize) -> Metadata {
+        Metadata {
+            name: Some("key".to_string()),
+            description: Some("x".repeat(len)),
+            image: None,
+        }
+    }
+
+    fn mint_secret(
+        deps: &mut Extern<MockStorage, MockApi, MockQuerier>,
+        token_id: &str,
+        len: usize,
+    ) -> StdResult<()> {
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some(token_id.to_string()),
+            owner: None,
+            public_metadata: None,
+            private_metadata: Some(secret(len)),
+            public_teaser: None,
+            memo: None,
+            padding: None,
+        };
+        handle(deps, mock_env("alice", &[]), handle_msg).map(|_| ())
+    }
+
+    fn burn_len(deps: &mut Extern<MockStorage, MockApi, MockQuerier>, token_id: &str) -> usize {
+        let handle_msg = HandleMsg::BurnNft {
+            token_id: token_id.to_string(),
+            memo: None,
+            padding: None,
+        };
+        let result = handle(deps, mock_env("alice", &[]), handle_msg);
+        result.unwrap().data.unwrap().len()
+    }
+
+    #[test]
+    fn test_secret_padding_init() {
+        let (init_result, _deps) = init_helper_padded(0, 1024);
+        let error = extract_error_msg(init_result);
+        assert!(error.contains("must be greater than zero"));
+
+        let (init_result, _deps) = init_helper_padded(512, 0);
+        let error = extract_error_msg(init_result);
+        assert!(error.contains("must be greater than zero"));
+    }
+
+    #[test]
+    fn test_burn_responses_padded_to_buckets() {
+        let (init_result, mut deps) = init_helper_padded(512, 2048);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        mint_secret(&mut deps, "password", 12).unwrap();
+        mint_secret(&mut deps, "short", 1).unwrap();
+        mint_secret(&mut deps, "private key", 1600).unwrap();
+
+        // secrets of different sizes in the same bucket are indistinguishable
+        assert_eq!(burn_len(&mut deps, "password"), 512);
+        assert_eq!(burn_len(&mut deps, "short"), 512);
+        // larger secrets are padded to the next power of two bucket
+        assert_eq!(burn_len(&mut deps, "private key"), 2048);
+
+        // batch burn responses are padded as well
+        mint_secret(&mut deps, "batch", 700).unwrap();
+        let handle_msg = HandleMsg::BatchBurnNft {
+            burns: vec![Burn {
+                token_ids: vec!["batch".to_string()],
+                memo: None,
+            }],
+            best_effort: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert_eq!(result.unwrap().data.unwrap().len(), 1024);
+    }
+
+    #[test]
+    fn test_retrieved_secrets_query_padded() {
+        let (init_result, mut deps) = init_helper_padded(1024, 2048);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let handle_msg = HandleMsg::CreateViewingKey {
+            entropy: "entropy".to_string(),
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let key = match from_binary(&result.unwrap().data.unwrap()).unwrap() {
+            HandleAnswer::ViewingKey { key } => key,
+            _ => panic!("unexpected"),
+        };
+        mint_secret(&mut deps, "password", 12).unwrap();
+        assert_eq!(burn_len(&mut deps, "password"), 1024);
+
+        let query_msg = QueryMsg::RetrievedSecrets {
+            address: HumanAddr("alice".to_string()),
+            viewing_key: key,
+        };
+        let query_result = query(&deps, query_msg).unwrap();
+        assert_eq!(query_result.len(), 1024);
+        let answer: QueryAnswer = from_binary(&query_result).unwrap();
+        match answer {
+            QueryAnswer::RetrievedSecrets { secrets } => {
+                assert_eq!(secrets.len(), 1);
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // responses without secrets keep the standard block padding
+        let query_result = query(&deps, QueryMsg::ContractInfo {}).unwrap();
+        assert_eq!(query_result.len(), 256);
+    }
+
+    #[test]
+    fn test_max_secret_size() {
+        let (init_result, mut deps) = init_helper_padded(512, 256);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let error = extract_error_msg(mint_secret(&mut deps, "too big", 300));
+        assert!(error.contains("Private metadata can not be larger than 256 bytes"));
+        mint_secret(&mut deps, "fits", 100).unwrap();
+
+        let handle_msg = HandleMsg::SetPrivateMetadata {
+            token_id: "fits".to_string(),
+            metadata: secret(300),
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let error = extract_error_msg(result);
+        assert!(error.contains("Private metadata can not be larger than 256 bytes"));
+    }
+}
@@ -0,0 +1,215 @@
+#[cfg(test)]
+mod tests {
+    use crate::unittest::helpers::helpers::helpers::*;
+    use cosmwasm_std::{HumanAddr, from_binary, Extern};
+    use crate::expiration::Expiration;
+    use crate::msg::{HandleMsg, HandleAnswer, QueryMsg, QueryAnswer, SecretRequestInfo, ViewerInfo};
+    use crate::contract::{handle, query};
+    use crate::token::Metadata;
+    use cosmwasm_std::testing::{mock_env, MockApi, MockQuerier, MockStorage};
+
+    fn create_key(deps: &mut Extern<MockStorage, MockApi, MockQuerier>, address: &str) -> String {
+        let handle_msg = HandleMsg::CreateViewingKey {
+            entropy: "key".to_string(),
+            padding: None,
+        };
+        let result = handle(deps, mock_env(address, &[]), handle_msg);
+        let answer: HandleAnswer = from_binary(&result.unwrap().data.unwrap()).unwrap();
+        match answer {
+            HandleAnswer::ViewingKey { key } => key,
+            _ => panic!("NOPE"),
+        }
+    }
+
+    fn secret() -> Metadata {
+        Metadata {
+            name: Some("API key".to_string()),
+            description: Some("hunter2".to_string()),
+            image: None,
+        }
+    }
+
+    fn fulfill(request_id: u32, code: &str) -> HandleMsg {
+        HandleMsg::FulfillSecretRequest {
+            request_id,
+            code: code.to_string(),
+            private_metadata: secret(),
+            padding: None,
+        }
+    }
+
+    // test creating, fulfilling and listing secret requests
+    #[test]
+    fn test_secret_requests() {
+        let (init_result, mut deps) = init_helper_full_profile();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let alice = HumanAddr("alice".to_string());
+        let bob = HumanAddr("bob".to_string());
+        let handle_msg = HandleMsg::CreateSecretRequest {
+            description: "please send us your API key".to_string(),
+            responder: Some(bob.clone()),
+            expires: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let answer: HandleAnswer = from_binary(&result.unwrap().data.unwrap()).unwrap();
+        let (request_id, code) = match answer {
+            HandleAnswer::CreateSecretRequest { request_id, code } => (request_id, code),
+            _ => panic!("NOPE"),
+        };
+        assert_eq!(request_id, 0);
+
+        // only the responder may fulfill it, and only with the code
+        let error = extract_error_msg(handle(&mut deps, mock_env("charlie", &[]), fulfill(0, &code)));
+        assert!(error.contains("Secret request 0 may only be fulfilled by its responder"));
+        let error = extract_error_msg(handle(&mut deps, mock_env("bob", &[]), fulfill(0, "wrong")));
+        assert!(error.contains("Secret request 0 does not exist or the code is incorrect"));
+        let error = extract_error_msg(handle(&mut deps, mock_env("bob", &[]), fulfill(7, &code)));
+        assert!(error.contains("Secret request 7 does not exist or the code is incorrect"));
+
+        // bob is not a minter, but may fulfill the request
+        let result = handle(&mut deps, mock_env("bob", &[]), fulfill(0, &code));
+        let answer: HandleAnswer = from_binary(&result.unwrap().data.unwrap()).unwrap();
+        let token_id = match answer {
+            HandleAnswer::FulfillSecretRequest { token_id } => token_id,
+            _ => panic!("NOPE"),
+        };
+        let error = extract_error_msg(handle(&mut deps, mock_env("bob", &[]), fulfill(0, &code)));
+        assert!(error.contains("Secret request 0 was already fulfilled"));
+        let akey = create_key(&mut deps, "alice");
+        let query_msg = QueryMsg::PrivateMetadata {
+            token_id: token_id.clone(),
+            viewer: Some(ViewerInfo {
+                address: alice.clone(),
+                viewing_key: akey.clone(),
+            }),
+        };
+        let query_answer: QueryAnswer = from_binary(&query(&deps, query_msg).unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::PrivateMetadata { description, .. } => {
+                assert_eq!(description, Some("hunter2".to_string()))
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // expired requests can not be fulfilled
+        let handle_msg = HandleMsg::CreateSecretRequest {
+            description: "password".to_string(),
+            responder: None,
+            expires: Some(Expiration::AtHeight(10)),
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let answer: HandleAnswer = from_binary(&result.unwrap().data.unwrap()).unwrap();
+        let code = match answer {
+            HandleAnswer::CreateSecretRequest { code, .. } => code,
+            _ => panic!("NOPE"),
+        };
+        let error = extract_error_msg(handle(&mut deps, mock_env("charlie", &[]), fulfill(1, &code)));
+        assert!(error.contains("Secret request 1 has expired"));
+
+        // the requester can list open and fulfilled requests
+        let query_msg = QueryMsg::SecretRequests {
+            address: alice.clone(),
+            viewing_key: akey.clone(),
+            page: None,
+            page_size: None,
+        };
+        let query_answer: QueryAnswer = from_binary(&query(&deps, query_msg).unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::SecretRequests { total, requests } => {
+                assert_eq!(total, 2);
+                assert_eq!(
+                    requests,
+                    vec![
+                        SecretRequestInfo {
+                            request_id: 1,
+                            description: "password".to_string(),
+                            responder: None,
+                            expires: Expiration::AtHeight(10),
+                            token_id: None,
+                        },
+                        SecretRequestInfo {
+                            request_id: 0,
+                            description: "please send us your API key".to_string(),
+                            responder: Some(bob),
+                            expires: Expiration::Never,
+                            token_id: Some(token_id),
+                        },
+                    ]
+                );
+            }
+            _ => panic!("unexpected"),
+        }
+        let query_msg = QueryMsg::SecretRequests {
+            address: alice.clone(),
+            viewing_key: akey,
+            page: Some(1),
+            page_size: Some(1),
+        };
+        let query_answer: QueryAnswer = from_binary(&query(&deps, query_msg).unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::SecretRequests { total, requests } => {
+                assert_eq!(total, 2);
+                assert_eq!(requests.len(), 1);
+                assert_eq!(requests[0].request_id, 0);
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // nobody else can list them
+        let bkey = create_key(&mut deps, "bob");
+        let query_msg = QueryMsg::SecretRequests {
+            address: alice,
+            viewing_key: bkey,
+            page: None,
+            page_size: None,
+        };
+        let error = extract_error_msg(query(&deps, query_msg));
+        assert!(error.contains("Wrong viewing key"));
+    }
+
+    // a fulfilled secret must not be reachable by guessing its token id
+    #[test]
+    fn test_fulfilled_secret_id_is_not_guessable() {
+        let (init_result, mut deps) = init_helper_default();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let handle_msg = HandleMsg::CreateSecretRequest {
+            description: "please send us your API key".to_string(),
+            responder: None,
+            expires: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let answer: HandleAnswer = from_binary(&result.unwrap().data.unwrap()).unwrap();
+        let (request_id, code) = match answer {
+            HandleAnswer::CreateSecretRequest { request_id, code } => (request_id, code),
+            _ => panic!("NOPE"),
+        };
+        let result = handle(&mut deps, mock_env("bob", &[]), fulfill(request_id, &code));
+        let answer: HandleAnswer = from_binary(&result.unwrap().data.unwrap()).unwrap();
+        let token_id = match answer {
+            HandleAnswer::FulfillSecretRequest { token_id } => token_id,
+            _ => panic!("NOPE"),
+        };
+        assert_eq!(token_id.len(), 32);
+        assert_ne!(token_id, "0".to_string());
+
+        // counting upward does not find the secret
+        let handle_msg = HandleMsg::BurnNft {
+            token_id: "0".to_string(),
+            memo: None,
+            padding: None,
+        };
+        let error = extract_error_msg(handle(&mut deps, mock_env("mallory", &[]), handle_msg));
+        assert!(error.contains("Token 0 does not exist or you are not authorized to access it"));
+    }
+}
@@ -1,12 +1,77 @@
 #[cfg(test)]
 mod tests {
-    use cosmwasm_std::{HumanAddr};
-    use cosmwasm_std::testing::{mock_env};
+    use cosmwasm_std::{from_binary, to_binary, CosmosMsg, HumanAddr, WasmMsg};
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{Extern, InitResponse, StdResult};
 
-    use crate::contract::handle;
-    use crate::msg::{HandleMsg, Transfer};
+    use crate::contract::{handle, init, query};
+    use crate::msg::{
+        HandleAnswer, HandleMsg, InitConfig, InitMsg, QueryAnswer, QueryMsg, Send, Transfer,
+        ViewerInfo,
+    };
+    use crate::receiver::Snip721ReceiveMsg;
+    use crate::token::Metadata;
     use crate::unittest::helpers::helpers::helpers::*;
 
+    fn mint_admin(deps: &mut Extern<MockStorage, MockApi, MockQuerier>, token_id: &str) {
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some(token_id.to_string()),
+            owner: None,
+            public_metadata: None,
+            private_metadata: None,
+            public_teaser: None,
+            memo: None,
+            padding: None,
+        };
+        let result = handle(deps, mock_env("admin", &[]), handle_msg);
+        assert!(result.is_ok(), "Mint failed: {}", result.err().unwrap());
+    }
+
+    fn owned_tokens(
+        deps: &mut Extern<MockStorage, MockApi, MockQuerier>,
+        owner: &str,
+    ) -> Vec<String> {
+        let handle_msg = HandleMsg::CreateViewingKey {
+            entropy: owner.to_string(),
+            padding: None,
+        };
+        let result = handle(deps, mock_env(owner, &[]), handle_msg);
+        let key = match from_binary(&result.unwrap().data.unwrap()).unwrap() {
+            HandleAnswer::ViewingKey { key } => key,
+            _ => panic!("unexpected"),
+        };
+        let query_msg = QueryMsg::Tokens {
+            owner: HumanAddr(owner.to_string()),
+            viewer: None,
+            viewing_key: Some(key),
+            start_after: None,
+            limit: None,
+        };
+        match from_binary(&query(deps, query_msg).unwrap()).unwrap() {
+            QueryAnswer::TokenList { tokens } => tokens,
+            _ => panic!("unexpected"),
+        }
+    }
+
+    fn transfer(
+        deps: &mut Extern<MockStorage, MockApi, MockQuerier>,
+        sender: &str,
+        recipient: &str,
+        token_id: &str,
+    ) -> String {
+        let handle_msg = HandleMsg::TransferNft {
+            recipient: HumanAddr(recipient.to_string()),
+            token_id: token_id.to_string(),
+            recipient_only: None,
+            memo: None,
+            padding: None,
+        };
+        match handle(deps, mock_env(sender, &[]), handle_msg) {
+            Ok(_) => String::new(),
+            Err(err) => extract_error_msg::<()>(Err(err)),
+        }
+    }
+
     #[test]
     fn test_send() {
         let (init_result, mut deps) =
@@ -20,6 +85,7 @@ mod tests {
             contract: HumanAddr("bob".to_string()),
             token_id: "MyNFT".to_string(),
             msg: None,
+            recipient_only: None,
             memo: None,
             padding: None,
         };
@@ -58,6 +124,7 @@ mod tests {
         let handle_msg = HandleMsg::TransferNft {
             recipient: HumanAddr("bob".to_string()),
             token_id: "MyNFT".to_string(),
+            recipient_only: None,
             memo: None,
             padding: None,
         };
@@ -78,6 +145,7 @@ mod tests {
         let transfers = vec![Transfer {
             recipient: HumanAddr("bob".to_string()),
             token_ids: vec!["MyNFT".to_string()],
+            recipient_only: None,
             memo: None,
         }];
         let handle_msg = HandleMsg::BatchTransferNft {
@@ -88,4 +156,398 @@ mod tests {
         let error = extract_error_msg(handle_result);
         assert!(error.contains("Token transfer not permitted"));
     }
+
+    #[test]
+    fn test_transfer_full_profile() {
+        let (init_result, mut deps) = init_helper_full_profile();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        mint_admin(&mut deps, "NFT1");
+        mint_admin(&mut deps, "NFT2");
+        let handle_msg = HandleMsg::Approve {
+            spender: HumanAddr("bob".to_string()),
+            token_id: "NFT1".to_string(),
+            expires: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(result.is_ok());
+
+        // an address without approval can not transfer the token
+        let error = transfer(&mut deps, "charlie", "charlie", "NFT1");
+        assert!(error.contains("Token NFT1 does not exist or you are not authorized"));
+        // an approved address can
+        assert_eq!(transfer(&mut deps, "bob", "charlie", "NFT1"), "");
+        // approvals are cleared by the transfer
+        let error = transfer(&mut deps, "bob", "bob", "NFT1");
+        assert!(error.contains("Token NFT1 does not exist or you are not authorized"));
+        let error = transfer(&mut deps, "charlie", "charlie", "NFT1");
+        assert!(error.contains("to the address that already owns it"));
+
+        let handle_msg = HandleMsg::BatchTransferNft {
+            transfers: vec![Transfer {
+                recipient: HumanAddr("alice".to_string()),
+                token_ids: vec!["NFT2".to_string()],
+                recipient_only: None,
+                memo: Some("gift".to_string()),
+            }],
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(result.is_ok());
+        assert!(owned_tokens(&mut deps, "admin").is_empty());
+        assert_eq!(owned_tokens(&mut deps, "alice"), vec!["NFT2".to_string()]);
+        assert_eq!(owned_tokens(&mut deps, "charlie"), vec!["NFT1".to_string()]);
+    }
+
+    fn init_helper_gifting(
+        gifting_enabled: bool,
+    ) -> (
+        StdResult<InitResponse>,
+        Extern<MockStorage, MockApi, MockQuerier>,
+    ) {
+        let mut deps = mock_dependencies(20, &[]);
+        let env = mock_env("instantiator", &[]);
+        let init_msg = InitMsg {
+            name: "sec721".to_string(),
+            symbol: "S721".to_string(),
+            admin: Some(HumanAddr("admin".to_string())),
+            entropy: "We're going to need a bigger boat".to_string(),
+            config: Some(InitConfig {
+                enable_sealed_metadata: Some(true),
+                enable_burn: Some(true),
+                gifting_enabled: Some(gifting_enabled),
+                ..InitConfig::default()
+            }),
+            post_init_callback: None,
+        };
+        (init(&mut deps, env, init_msg), deps)
+    }
+
+    #[test]
+    fn test_gifting_requires_opt_in() {
+        let (init_result, mut deps) = init_helper_gifting(false);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("NFT1".to_string()),
+            owner: None,
+            public_metadata: None,
+            private_metadata: None,
+            public_teaser: None,
+            memo: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(result.is_ok(), "Mint failed: {}", result.err().unwrap());
+
+        // sealed metadata alone does not enable transfers
+        let error = transfer(&mut deps, "alice", "bob", "NFT1");
+        assert!(error.contains("Token transfer not permitted"));
+        let handle_msg = HandleMsg::RegisterReceiveNft {
+            code_hash: "receiver hash".to_string(),
+            also_implements_batch_receive_nft: None,
+            padding: None,
+        };
+        let error = extract_error_msg(handle(&mut deps, mock_env("receiver", &[]), handle_msg));
+        assert!(error.contains("ReceiveNft registration not supported."));
+    }
+
+    #[test]
+    fn test_gift_sealed_token() {
+        let (init_result, mut deps) = init_helper_gifting(true);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        for id in &["NFT1", "NFT2"] {
+            let handle_msg = HandleMsg::MintNft {
+                token_id: Some(id.to_string()),
+                owner: None,
+                public_metadata: None,
+                private_metadata: Some(Metadata {
+                    name: Some("secret".to_string()),
+                    description: None,
+                    image: None,
+                }),
+                public_teaser: None,
+                memo: None,
+                padding: None,
+            };
+            let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+            assert!(result.is_ok(), "Mint failed: {}", result.err().unwrap());
+        }
+
+        // an unread token can be gifted
+        let handle_msg = HandleMsg::TransferNft {
+            recipient: HumanAddr("bob".to_string()),
+            token_id: "NFT1".to_string(),
+            recipient_only: Some(true),
+            memo: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(result.is_ok(), "Transfer failed: {}", result.err().unwrap());
+
+        // only the new holder can reveal it
+        let reveal = HandleMsg::Reveal {
+            token_id: "NFT1".to_string(),
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), reveal);
+        let error = extract_error_msg(result);
+        assert!(error.contains("Token NFT1 does not exist or you are not authorized"));
+        let reveal = HandleMsg::Reveal {
+            token_id: "NFT1".to_string(),
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("bob", &[]), reveal);
+        assert!(result.is_ok(), "Reveal failed: {}", result.err().unwrap());
+
+        // once read it can no longer be transferred
+        let error = transfer(&mut deps, "bob", "charlie", "NFT1");
+        assert!(error.contains("Token NFT1 has been unwrapped and can no longer be transferred"));
+
+        // only the recipient can burn a recipient-restricted token
+        let burn = |sender: &str, deps: &mut Extern<MockStorage, MockApi, MockQuerier>| {
+            let handle_msg = HandleMsg::BurnNft {
+                token_id: "NFT1".to_string(),
+                memo: None,
+                padding: None,
+            };
+            handle(deps, mock_env(sender, &[]), handle_msg)
+        };
+        let error = extract_error_msg(burn("charlie", &mut deps));
+        assert!(error.contains("Token NFT1 does not exist or you are not authorized"));
+        let result = burn("bob", &mut deps);
+        match from_binary(&result.unwrap().data.unwrap()).unwrap() {
+            HandleAnswer::BurnNft {
+                secret: Some(secret),
+            } => assert_eq!(secret.name, Some("secret".to_string())),
+            _ => panic!("unexpected"),
+        }
+
+        // unread tokens can be sent to registered contracts
+        let handle_msg = HandleMsg::RegisterReceiveNft {
+            code_hash: "receiver hash".to_string(),
+            also_implements_batch_receive_nft: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("receiver", &[]), handle_msg);
+        assert!(result.is_ok());
+        let handle_msg = HandleMsg::SendNft {
+            contract: HumanAddr("receiver".to_string()),
+            token_id: "NFT2".to_string(),
+            msg: None,
+            recipient_only: None,
+            memo: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let messages = result.unwrap().messages;
+        assert_eq!(messages.len(), 1);
+        assert_eq!(
+            owned_tokens(&mut deps, "receiver"),
+            vec!["NFT2".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_can_retrieve_gifted_token() {
+        let (init_result, mut deps) = init_helper_gifting(true);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("NFT1".to_string()),
+            owner: None,
+            public_metadata: None,
+            private_metadata: Some(Metadata {
+                name: Some("secret".to_string()),
+                description: None,
+                image: None,
+            }),
+            public_teaser: None,
+            memo: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(result.is_ok(), "Mint failed: {}", result.err().unwrap());
+        let handle_msg = HandleMsg::TransferNft {
+            recipient: HumanAddr("bob".to_string()),
+            token_id: "NFT1".to_string(),
+            recipient_only: Some(true),
+            memo: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(result.is_ok(), "Transfer failed: {}", result.err().unwrap());
+        let handle_msg = HandleMsg::Reveal {
+            token_id: "NFT1".to_string(),
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        assert!(result.is_ok(), "Reveal failed: {}", result.err().unwrap());
+        let handle_msg = HandleMsg::CreateViewingKey {
+            entropy: "key".to_string(),
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        let bob_key = match from_binary(&result.unwrap().data.unwrap()).unwrap() {
+            HandleAnswer::ViewingKey { key } => key,
+            _ => panic!("unexpected"),
+        };
+
+        // anyone but the recipient is told the burn would fail
+        let error = extract_error_msg(handle(
+            &mut deps,
+            mock_env("charlie", &[]),
+            HandleMsg::BurnNft {
+                token_id: "NFT1".to_string(),
+                memo: None,
+                padding: None,
+            },
+        ));
+        assert!(error.contains("Token NFT1 does not exist or you are not authorized to access it"));
+        let can_retrieve = |viewer: Option<ViewerInfo>| {
+            let query_msg = QueryMsg::CanRetrieve {
+                token_id: "NFT1".to_string(),
+                viewer,
+            };
+            match from_binary(&query(&deps, query_msg).unwrap()).unwrap() {
+                QueryAnswer::CanRetrieve {
+                    retrievable,
+                    reason,
+                    ..
+                } => (retrievable, reason),
+                _ => panic!("unexpected"),
+            }
+        };
+        let (retrievable, reason) = can_retrieve(None);
+        assert!(!retrievable);
+        assert_eq!(
+            reason,
+            Some("Token NFT1 does not exist or you are not authorized to access it".to_string())
+        );
+
+        // the recipient can retrieve it
+        let (retrievable, reason) = can_retrieve(Some(ViewerInfo {
+            address: HumanAddr("bob".to_string()),
+            viewing_key: bob_key,
+        }));
+        assert!(retrievable);
+        assert!(reason.is_none());
+    }
+
+    #[test]
+    fn test_send_full_profile() {
+        let (init_result, mut deps) = init_helper_full_profile();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        mint_admin(&mut deps, "NFT1");
+        mint_admin(&mut deps, "NFT2");
+        mint_admin(&mut deps, "NFT3");
+
+        // contracts that did not register do not get a callback
+        let handle_msg = HandleMsg::SendNft {
+            contract: HumanAddr("unregistered".to_string()),
+            token_id: "NFT1".to_string(),
+            msg: None,
+            recipient_only: None,
+            memo: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(result.unwrap().messages.is_empty());
+
+        let handle_msg = HandleMsg::RegisterReceiveNft {
+            code_hash: "receiver hash".to_string(),
+            also_implements_batch_receive_nft: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("receiver", &[]), handle_msg);
+        assert!(result.is_ok());
+        let msg = Some(to_binary(&"hello").unwrap());
+        let handle_msg = HandleMsg::SendNft {
+            contract: HumanAddr("receiver".to_string()),
+            token_id: "NFT2".to_string(),
+            msg: msg.clone(),
+            recipient_only: None,
+            memo: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let messages = result.unwrap().messages;
+        assert_eq!(messages.len(), 1);
+        match &messages[0] {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr,
+                callback_code_hash,
+                msg: callback,
+                ..
+            }) => {
+                assert_eq!(contract_addr, &HumanAddr("receiver".to_string()));
+                assert_eq!(callback_code_hash, "receiver hash");
+                let received: Snip721ReceiveMsg = from_binary(callback).unwrap();
+                assert_eq!(
+                    received,
+                    Snip721ReceiveMsg::ReceiveNft {
+                        sender: HumanAddr("admin".to_string()),
+                        token_id: "NFT2".to_string(),
+                        msg: msg.clone(),
+                    }
+                );
+            }
+            _ => panic!("unexpected"),
+        }
+
+        let handle_msg = HandleMsg::RegisterReceiveNft {
+            code_hash: "batch hash".to_string(),
+            also_implements_batch_receive_nft: Some(true),
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("batcher", &[]), handle_msg);
+        assert!(result.is_ok());
+        let handle_msg = HandleMsg::BatchSendNft {
+            sends: vec![Send {
+                contract: HumanAddr("batcher".to_string()),
+                token_ids: vec!["NFT3".to_string()],
+                msg: None,
+                recipient_only: None,
+                memo: None,
+            }],
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let messages = result.unwrap().messages;
+        assert_eq!(messages.len(), 1);
+        match &messages[0] {
+            CosmosMsg::Wasm(WasmMsg::Execute { msg: callback, .. }) => {
+                let received: Snip721ReceiveMsg = from_binary(callback).unwrap();
+                assert_eq!(
+                    received,
+                    Snip721ReceiveMsg::BatchReceiveNft {
+                        sender: HumanAddr("admin".to_string()),
+                        from: HumanAddr("admin".to_string()),
+                        token_ids: vec!["NFT3".to_string()],
+                        msg: None,
+                    }
+                );
+            }
+            _ => panic!("unexpected"),
+        }
+        assert_eq!(owned_tokens(&mut deps, "batcher"), vec!["NFT3".to_string()]);
+    }
 }
@@ -7,7 +7,7 @@ mod tests {
     use cosmwasm_std::{HumanAddr, CanonicalAddr, Api, Binary};
     use crate::token::{Metadata, Token};
     use crate::expiration::Expiration;
-    use crate::state::{PermissionType, PREFIX_ALL_PERMISSIONS, Permission, json_load, PREFIX_INFOS, PREFIX_PUB_META, load, PREFIX_PRIV_META, may_load, PREFIX_AUTHLIST, AuthList};
+    use crate::state::{PermissionType, get_all_perms, PREFIX_INFOS, PREFIX_PUB_META, load, PREFIX_PRIV_META, may_load, get_auth_lists, load_token};
     use cosmwasm_storage::ReadonlyPrefixedStorage;
 
     // test owner setting global approvals
@@ -51,7 +51,7 @@ mod tests {
         };
         let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
         let error = extract_error_msg(handle_result);
-        assert!(error.contains("You do not own token NFT1"));
+        assert!(error.contains("Token NFT1 does not exist"));
 
         let handle_msg = HandleMsg::MintNft {
             token_id: Some("NFT1".to_string()),
@@ -62,6 +62,7 @@ mod tests {
                 image: Some("URI 1".to_string()),
             }),
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -95,7 +96,7 @@ mod tests {
         };
         let handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
         let error = extract_error_msg(handle_result);
-        assert!(error.contains("You do not own token NFT1"));
+        assert!(error.contains("Token NFT1 does not exist"));
 
         // try approving a token without specifying which token
         let handle_msg = HandleMsg::SetGlobalApproval {
@@ -141,13 +142,11 @@ mod tests {
             .api
             .canonical_address(&HumanAddr("alice".to_string()))
             .unwrap();
-        let alice_key = alice_raw.as_slice();
         let view_owner_idx = PermissionType::ViewOwner.to_usize();
         let view_meta_idx = PermissionType::ViewMetadata.to_usize();
         let transfer_idx = PermissionType::Transfer.to_usize();
         // confirm ALL permission
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Vec<Permission> = json_load(&all_store, alice_key).unwrap();
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
         assert_eq!(all_perm.len(), 1);
         let global_perm = all_perm.iter().find(|p| p.address == global_raw).unwrap();
         assert_eq!(
@@ -159,7 +158,7 @@ mod tests {
         // confirm NFT1 permissions and that the token data did not get modified
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
         let nft1_key = 0u32.to_le_bytes();
-        let token: Token = json_load(&info_store, &nft1_key).unwrap();
+        let token: Token = load_token(&info_store, &nft1_key).unwrap();
         assert_eq!(token.owner, alice_raw);
         assert!(token.unwrapped);
         let pub_store = ReadonlyPrefixedStorage::new(PREFIX_PUB_META, &deps.storage);
@@ -183,8 +182,7 @@ mod tests {
         assert_eq!(global_tok_perm.expirations[transfer_idx], None);
         assert_eq!(global_tok_perm.expirations[view_owner_idx], None);
         // confirm AuthLists has public with NFT1 permission
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Vec<AuthList> = load(&auth_store, alice_key).unwrap();
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
         assert_eq!(auth_list.len(), 1);
         let global_auth = auth_list.iter().find(|a| a.address == global_raw).unwrap();
         assert_eq!(global_auth.tokens[view_meta_idx].len(), 1);
@@ -210,8 +208,7 @@ mod tests {
             .canonical_address(&HumanAddr("bob".to_string()))
             .unwrap();
         // confirm ALL permission
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Vec<Permission> = json_load(&all_store, alice_key).unwrap();
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
         assert_eq!(all_perm.len(), 2);
         let bob_oper_perm = all_perm.iter().find(|p| p.address == bob_raw).unwrap();
         assert_eq!(
@@ -229,7 +226,7 @@ mod tests {
         assert_eq!(global_perm.expirations[transfer_idx], None);
         // confirm NFT1 permissions
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft1_key).unwrap();
+        let token: Token = load_token(&info_store, &nft1_key).unwrap();
         assert_eq!(token.permissions.len(), 2);
         let bob_tok_perm = token
             .permissions
@@ -249,8 +246,7 @@ mod tests {
         assert_eq!(global_tok_perm.expirations[transfer_idx], None);
         assert_eq!(global_tok_perm.expirations[view_owner_idx], None);
         // confirm AuthLists has bob with NFT1 permission
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Vec<AuthList> = load(&auth_store, alice_key).unwrap();
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
         assert_eq!(auth_list.len(), 2);
         let bob_auth = auth_list.iter().find(|a| a.address == bob_raw).unwrap();
         assert_eq!(bob_auth.tokens[transfer_idx].len(), 1);
@@ -264,8 +260,7 @@ mod tests {
         assert!(global_auth.tokens[view_owner_idx].is_empty());
 
         // confirm ALL permission
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Vec<Permission> = json_load(&all_store, alice_key).unwrap();
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
         assert_eq!(all_perm.len(), 2);
         let bob_oper_perm = all_perm.iter().find(|p| p.address == bob_raw).unwrap();
         assert_eq!(
@@ -283,7 +278,7 @@ mod tests {
         assert_eq!(global_perm.expirations[transfer_idx], None);
         // confirm NFT1 permissions
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft1_key).unwrap();
+        let token: Token = load_token(&info_store, &nft1_key).unwrap();
         assert_eq!(token.permissions.len(), 2);
         let bob_tok_perm = token
             .permissions
@@ -303,8 +298,7 @@ mod tests {
         assert_eq!(global_tok_perm.expirations[transfer_idx], None);
         assert_eq!(global_tok_perm.expirations[view_owner_idx], None);
         // confirm AuthLists has bob with NFT1 permission
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Vec<AuthList> = load(&auth_store, alice_key).unwrap();
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
         assert_eq!(auth_list.len(), 2);
         let bob_auth = auth_list.iter().find(|a| a.address == bob_raw).unwrap();
         assert_eq!(bob_auth.tokens[transfer_idx].len(), 1);
@@ -329,8 +323,7 @@ mod tests {
         assert!(result.is_ok());
 
         // confirm ALL permission
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Vec<Permission> = json_load(&all_store, alice_key).unwrap();
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
         assert_eq!(all_perm.len(), 2);
         let bob_oper_perm = all_perm.iter().find(|p| p.address == bob_raw).unwrap();
         assert_eq!(
@@ -348,7 +341,7 @@ mod tests {
         assert_eq!(global_perm.expirations[transfer_idx], None);
         // confirm NFT1 permissions
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft1_key).unwrap();
+        let token: Token = load_token(&info_store, &nft1_key).unwrap();
         assert_eq!(token.permissions.len(), 1);
         let bob_tok_perm = token
             .permissions
@@ -364,8 +357,7 @@ mod tests {
         let global_tok_perm = token.permissions.iter().find(|p| p.address == global_raw);
         assert!(global_tok_perm.is_none());
         // confirm AuthLists has bob with NFT1 permission
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Vec<AuthList> = load(&auth_store, alice_key).unwrap();
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
         assert_eq!(auth_list.len(), 1);
         let bob_auth = auth_list.iter().find(|a| a.address == bob_raw).unwrap();
         assert_eq!(bob_auth.tokens[transfer_idx].len(), 1);
@@ -0,0 +1,151 @@
+#[cfg(test)]
+mod tests {
+    use crate::unittest::helpers::helpers::helpers::*;
+    use crate::msg::HandleMsg;
+    use cosmwasm_std::{Api, CanonicalAddr, HumanAddr, ReadonlyStorage};
+    use crate::contract::handle;
+    use cosmwasm_std::testing::mock_env;
+    use crate::expiration::Expiration;
+    use crate::token::{Token, TOKEN_FORMAT_COMPACT};
+    use crate::state::{json_save, load, load_token, may_load_token, save, save_token, Config, Permission, CONFIG_KEY, PREFIX_INFOS};
+    use cosmwasm_storage::{PrefixedStorage, ReadonlyPrefixedStorage};
+    use secret_toolkit::serialization::{Json, Serde};
+
+    fn test_token(api_addr: &dyn Fn(&str) -> CanonicalAddr, num_perms: usize) -> Token {
+        let exps = [
+            Some(Expiration::AtHeight(1_000_000)),
+            Some(Expiration::AtTime(1_600_000_000)),
+            Some(Expiration::Never),
+            None,
+        ];
+        let permissions = (0..num_perms)
+            .map(|i| Permission {
+                address: api_addr(&format!("address{}", i)),
+                expirations: [exps[i % 4], exps[(i + 1) % 4], exps[(i + 2) % 4]],
+            })
+            .collect();
+        Token {
+            owner: api_addr("alice"),
+            permissions,
+            unwrapped: num_perms > 2,
+            public_teaser: true,
+            recipient_only: num_perms % 2 == 1,
+        }
+    }
+
+    #[test]
+    fn test_token_encoding_round_trip() {
+        let deps = init_helper_verified();
+        let addr = |name: &str| {
+            deps.api
+                .canonical_address(&HumanAddr(name.to_string()))
+                .unwrap()
+        };
+        for num_perms in 0..6 {
+            let token = test_token(&addr, num_perms);
+            let bytes = token.to_bytes().unwrap();
+            assert_eq!(bytes[0], TOKEN_FORMAT_COMPACT);
+            let decoded = Token::from_bytes(&bytes).unwrap();
+            assert_eq!(decoded.owner, token.owner);
+            assert_eq!(decoded.permissions, token.permissions);
+            assert_eq!(decoded.unwrapped, token.unwrapped);
+            assert_eq!(decoded.public_teaser, token.public_teaser);
+            assert_eq!(decoded.recipient_only, token.recipient_only);
+
+            // test truncated or padded records are rejected
+            assert!(Token::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+            let mut padded = bytes.clone();
+            padded.push(0);
+            assert!(Token::from_bytes(&padded).is_err());
+        }
+    }
+
+    #[test]
+    fn test_dual_format_reader() {
+        let (init_result, mut deps) = init_helper_default();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        mint_generic_token(&mut deps, "NFT1");
+        let alice_raw = deps
+            .api
+            .canonical_address(&HumanAddr("alice".to_string()))
+            .unwrap();
+        let bob_raw = deps
+            .api
+            .canonical_address(&HumanAddr("bob".to_string()))
+            .unwrap();
+        let token_key = 0u32.to_le_bytes();
+
+        // test new tokens are saved with the compact encoding
+        let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
+        let raw = info_store.get(&token_key).unwrap();
+        assert_eq!(raw[0], TOKEN_FORMAT_COMPACT);
+        let token = load_token(&info_store, &token_key).unwrap();
+        assert_eq!(token.owner, alice_raw);
+        assert!(may_load_token(&info_store, &1u32.to_le_bytes()).unwrap().is_none());
+        assert!(load_token(&info_store, &1u32.to_le_bytes()).is_err());
+
+        // save the token and config a schema version 1 contract would have stored
+        let legacy = Token {
+            owner: alice_raw.clone(),
+            permissions: vec![Permission {
+                address: bob_raw.clone(),
+                expirations: [None, Some(Expiration::AtHeight(100000)), None],
+            }],
+            unwrapped: false,
+            public_teaser: false,
+            recipient_only: false,
+        };
+        let mut info_store = PrefixedStorage::new(PREFIX_INFOS, &mut deps.storage);
+        json_save(&mut info_store, &token_key, &legacy).unwrap();
+        let mut config: Config = load(&deps.storage, CONFIG_KEY).unwrap();
+        config.schema_version = 1;
+        save(&mut deps.storage, CONFIG_KEY, &config).unwrap();
+
+        // test the legacy token is still readable
+        let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
+        let token = load_token(&info_store, &token_key).unwrap();
+        assert_eq!(token.permissions, legacy.permissions);
+
+        // test migrating rewrites it with the compact encoding
+        let handle_msg = HandleMsg::Migrate {
+            batch_size: 10,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+        let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
+        let raw = info_store.get(&token_key).unwrap();
+        assert_eq!(raw[0], TOKEN_FORMAT_COMPACT);
+        let token = load_token(&info_store, &token_key).unwrap();
+        assert_eq!(token.owner, legacy.owner);
+        assert_eq!(token.permissions, legacy.permissions);
+
+        // test saving over a legacy token
+        let mut info_store = PrefixedStorage::new(PREFIX_INFOS, &mut deps.storage);
+        json_save(&mut info_store, &token_key, &legacy).unwrap();
+        save_token(&mut info_store, &token_key, &legacy).unwrap();
+        let raw = info_store.get(&token_key).unwrap();
+        assert_eq!(raw[0], TOKEN_FORMAT_COMPACT);
+    }
+
+    #[test]
+    fn test_token_storage_size() {
+        let deps = init_helper_verified();
+        let addr = |name: &str| {
+            deps.api
+                .canonical_address(&HumanAddr(name.to_string()))
+                .unwrap()
+        };
+        for num_perms in [0usize, 1, 4, 10].iter() {
+            let token = test_token(&addr, *num_perms);
+            let json_len = Json::serialize(&token).unwrap().len();
+            let compact_len = token.to_bytes().unwrap().len();
+            // the compact encoding must be at most half the size of the json
+            assert!(compact_len * 2 <= json_len);
+        }
+    }
+}
@@ -0,0 +1,162 @@
+#[cfg(test)]
+mod tests {
+    use crate::unittest::helpers::helpers::helpers::*;
+    use crate::contract::handle;
+    use crate::error::ContractError;
+    use crate::msg::{HandleMsg, QueryAnswer, TokenData};
+    use crate::token::Metadata;
+    use cosmwasm_std::testing::{mock_env, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{Extern, StdError, StdResult};
+
+    fn mint_sealed(deps: &mut Extern<MockStorage, MockApi, MockQuerier>) {
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("NFT1".to_string()),
+            owner: None,
+            public_metadata: None,
+            private_metadata: Some(Metadata {
+                name: Some("MySealedNFT".to_string()),
+                description: None,
+                image: None,
+            }),
+            public_teaser: None,
+            memo: None,
+            padding: None,
+        };
+        let result = handle(deps, mock_env("alice", &[]), handle_msg);
+        assert!(result.is_ok());
+    }
+
+    fn reveal(
+        deps: &mut Extern<MockStorage, MockApi, MockQuerier>,
+        sender: &str,
+        token_id: &str,
+    ) -> StdResult<()> {
+        let handle_msg = HandleMsg::Reveal {
+            token_id: token_id.to_string(),
+            padding: None,
+        };
+        handle(deps, mock_env(sender, &[]), handle_msg).map(|_| ())
+    }
+
+    fn set_private(
+        deps: &mut Extern<MockStorage, MockApi, MockQuerier>,
+        sender: &str,
+        token_id: &str,
+    ) -> StdResult<()> {
+        let handle_msg = HandleMsg::SetPrivateMetadata {
+            token_id: token_id.to_string(),
+            metadata: Metadata {
+                name: Some("New".to_string()),
+                description: None,
+                image: None,
+            },
+            padding: None,
+        };
+        handle(deps, mock_env(sender, &[]), handle_msg).map(|_| ())
+    }
+
+    fn code(result: StdResult<()>) -> Option<String> {
+        match result {
+            Err(err) => ContractError::code_of(&err),
+            Ok(_) => panic!("Expected error, but had Ok response"),
+        }
+    }
+
+    #[test]
+    fn test_private_supply_errors_are_indistinguishable() {
+        let (init_result, mut deps) =
+            init_helper_with_config(false, false, true, false, true, false, true);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        mint_sealed(&mut deps);
+
+        let expected = "{\"code\":\"token_unavailable\",\"msg\":\"Token NFT1 does not exist or you \
+                        are not authorized to access it\"}";
+        // token does not exist
+        let missing = extract_error_msg(reveal(&mut deps, "bob", "NFT2"));
+        assert_eq!(missing, expected.replace("NFT1", "NFT2"));
+        // token is not yours
+        let not_owner = extract_error_msg(reveal(&mut deps, "bob", "NFT1"));
+        assert_eq!(not_owner, expected);
+        // token is sealed
+        let sealed = extract_error_msg(set_private(&mut deps, "admin", "NFT1"));
+        assert_eq!(sealed, expected);
+        // not permitted to update it
+        let unauthorized = extract_error_msg(set_private(&mut deps, "bob", "NFT1"));
+        assert_eq!(unauthorized, expected);
+        assert_eq!(
+            code(reveal(&mut deps, "bob", "NFT2")),
+            Some("token_unavailable".to_string())
+        );
+    }
+
+    #[test]
+    fn test_public_supply_error_codes() {
+        let (init_result, mut deps) =
+            init_helper_with_config(true, false, true, false, true, false, true);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        mint_sealed(&mut deps);
+
+        assert_eq!(
+            code(reveal(&mut deps, "bob", "NFT2")),
+            Some("token_not_found".to_string())
+        );
+        assert_eq!(
+            code(reveal(&mut deps, "bob", "NFT1")),
+            Some("token_not_owner".to_string())
+        );
+        assert_eq!(
+            code(set_private(&mut deps, "admin", "NFT1")),
+            Some("token_sealed".to_string())
+        );
+        assert_eq!(
+            code(set_private(&mut deps, "bob", "NFT1")),
+            Some("token_unauthorized".to_string())
+        );
+        let error = extract_error_msg(set_private(&mut deps, "bob", "NFT1"));
+        assert_eq!(
+            error,
+            "{\"code\":\"token_unauthorized\",\"msg\":\"Not authorized to update metadata of \
+             token NFT1\"}"
+        );
+
+        // errors that did not come from a ContractError have no code
+        let err = StdError::generic_err("Token ID MyNFT is already in use");
+        assert_eq!(ContractError::code_of(&err), None);
+    }
+
+    #[test]
+    fn test_unexpected_metadata_is_an_error() {
+        let wrong = || QueryAnswer::IsUnwrapped {
+            token_is_unwrapped: true,
+        };
+        let err = TokenData::from_public_metadata("NFT1", wrong()).unwrap_err();
+        assert_eq!(
+            ContractError::code_of(&err),
+            Some("unreadable_metadata".to_string())
+        );
+
+        let mut data = TokenData::from_public_metadata(
+            "NFT1",
+            QueryAnswer::NftInfo {
+                name: Some("Name".to_string()),
+                description: None,
+                image: None,
+            },
+        )
+        .unwrap();
+        let err = data.update_with_private_metadata(wrong()).unwrap_err();
+        assert_eq!(
+            extract_error_msg::<()>(Err(err)),
+            "{\"code\":\"unreadable_metadata\",\"msg\":\"Unable to read the private metadata \
+             of token NFT1\"}"
+        );
+    }
+}
@@ -0,0 +1,143 @@
+#[cfg(test)]
+mod tests {
+    use crate::msg::{
+        HandleAnswer, HandleMsg, InitConfig, InitMsg, QueryAnswer, QueryMsg, Tx, TxAction, TxFilter,
+    };
+    use crate::contract::{handle, init, query};
+    use crate::state::{
+        hash_token_id, json_load, load, StoredTx, StoredTxAction, PREFIX_TXS, PRNG_SEED_KEY,
+    };
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{from_binary, Extern, HumanAddr, InitResponse, StdResult};
+    use cosmwasm_storage::ReadonlyPrefixedStorage;
+
+    fn init_helper_private() -> (
+        StdResult<InitResponse>,
+        Extern<MockStorage, MockApi, MockQuerier>,
+    ) {
+        let mut deps = mock_dependencies(20, &[]);
+        let env = mock_env("instantiator", &[]);
+        let init_msg = InitMsg {
+            name: "sec721".to_string(),
+            symbol: "S721".to_string(),
+            admin: Some(HumanAddr("admin".to_string())),
+            entropy: "We're going to need a bigger boat".to_string(),
+            config: Some(InitConfig {
+                enable_burn: Some(true),
+                private_tx_records: Some(true),
+                ..InitConfig::default()
+            }),
+            post_init_callback: None,
+        };
+
+        (init(&mut deps, env, init_msg), deps)
+    }
+
+    fn create_key(deps: &mut Extern<MockStorage, MockApi, MockQuerier>, address: &str) -> String {
+        let handle_msg = HandleMsg::CreateViewingKey {
+            entropy: "entropy".to_string(),
+            padding: None,
+        };
+        let result = handle(deps, mock_env(address, &[]), handle_msg);
+        match from_binary(&result.unwrap().data.unwrap()).unwrap() {
+            HandleAnswer::ViewingKey { key } => key,
+            _ => panic!("unexpected"),
+        }
+    }
+
+    fn history(
+        deps: &Extern<MockStorage, MockApi, MockQuerier>,
+        address: &str,
+        viewing_key: &str,
+        filter: Option<TxFilter>,
+    ) -> Vec<Tx> {
+        let query_msg = QueryMsg::TransactionHistory {
+            address: HumanAddr(address.to_string()),
+            viewing_key: viewing_key.to_string(),
+            page: None,
+            page_size: None,
+            cursor: None,
+            filter,
+        };
+        match from_binary(&query(deps, query_msg).unwrap()).unwrap() {
+            QueryAnswer::TransactionHistory { txs, .. } => txs,
+            _ => panic!("unexpected"),
+        }
+    }
+
+    #[test]
+    fn test_private_tx_records() {
+        let (init_result, mut deps) = init_helper_private();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let alice_key = create_key(&mut deps, "alice");
+        let bob_key = create_key(&mut deps, "bob");
+
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("NFT1".to_string()),
+            owner: None,
+            public_metadata: None,
+            private_metadata: None,
+            public_teaser: None,
+            memo: Some("for bob".to_string()),
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(result.is_ok());
+        let handle_msg = HandleMsg::BurnNft {
+            token_id: "NFT1".to_string(),
+            memo: Some("read it".to_string()),
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        assert!(result.is_ok());
+
+        // test the stored txs hold neither the token id nor the memo
+        let seed: Vec<u8> = load(&deps.storage, PRNG_SEED_KEY).unwrap();
+        let hashed_id = hash_token_id(&seed, "NFT1");
+        assert_ne!(hashed_id, "NFT1");
+        let tx_store = ReadonlyPrefixedStorage::new(PREFIX_TXS, &deps.storage);
+        for tx_id in 0..2u64 {
+            let stored: StoredTx = json_load(&tx_store, &tx_id.to_le_bytes()).unwrap();
+            assert_eq!(stored.token_id, hashed_id);
+            assert!(stored.memo.is_none());
+            let sealed = stored.sealed_memo.unwrap();
+            assert!(!String::from_utf8_lossy(sealed.as_slice()).contains("bob"));
+            assert!(!String::from_utf8_lossy(sealed.as_slice()).contains("read"));
+        }
+
+        // test the owner sees the memos
+        let txs = history(&deps, "alice", &alice_key, None);
+        assert_eq!(txs.len(), 2);
+        assert!(txs.iter().all(|tx| tx.token_id == hashed_id));
+        assert_eq!(txs[0].memo, Some("read it".to_string()));
+        assert_eq!(txs[1].memo, Some("for bob".to_string()));
+
+        // test the burner is not recorded and the burn stays out of the burner's history
+        let stored: StoredTx = json_load(&tx_store, &1u64.to_le_bytes()).unwrap();
+        match stored.action {
+            StoredTxAction::Burn { burner, .. } => assert!(burner.is_none()),
+            _ => panic!("unexpected"),
+        }
+        match &txs[0].action {
+            TxAction::Burn { burner, .. } => assert!(burner.is_none()),
+            _ => panic!("unexpected"),
+        }
+        assert!(history(&deps, "bob", &bob_key, None).is_empty());
+
+        // test filtering by the unhashed token id
+        let filter = TxFilter {
+            token_id: Some("NFT1".to_string()),
+            ..TxFilter::default()
+        };
+        assert_eq!(history(&deps, "alice", &alice_key, Some(filter)).len(), 2);
+        let filter = TxFilter {
+            token_id: Some("NFT2".to_string()),
+            ..TxFilter::default()
+        };
+        assert!(history(&deps, "alice", &alice_key, Some(filter)).is_empty());
+    }
+}
@@ -0,0 +1,358 @@
+#[cfg(test)]
+mod tests {
+    use crate::msg::{
+        HandleAnswer, HandleMsg, InitConfig, InitMsg, QueryAnswer, QueryMsg, TxRetention,
+    };
+    use crate::contract::{handle, init, query};
+    use crate::unittest::helpers::helpers::helpers::*;
+    use crate::state::{may_load, PREFIX_TXS, PREFIX_TX_IDS, PREFIX_TX_REFS};
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{from_binary, Api, Extern, HumanAddr, InitResponse, ReadonlyStorage, StdResult};
+    use cosmwasm_storage::ReadonlyPrefixedStorage;
+
+    fn init_helper_with_retention(
+        retention: TxRetention,
+    ) -> (
+        StdResult<InitResponse>,
+        Extern<MockStorage, MockApi, MockQuerier>,
+    ) {
+        let mut deps = mock_dependencies(20, &[]);
+        let env = mock_env("instantiator", &[]);
+        let init_msg = InitMsg {
+            name: "sec721".to_string(),
+            symbol: "S721".to_string(),
+            admin: Some(HumanAddr("admin".to_string())),
+            entropy: "We're going to need a bigger boat".to_string(),
+            config: Some(InitConfig {
+                enable_burn: Some(true),
+                transfers_enabled: Some(true),
+                tx_retention: Some(retention),
+                ..InitConfig::default()
+            }),
+            post_init_callback: None,
+        };
+
+        (init(&mut deps, env, init_msg), deps)
+    }
+
+    fn mint_at(
+        deps: &mut Extern<MockStorage, MockApi, MockQuerier>,
+        token_id: &str,
+        owner: &str,
+        height: u64,
+    ) {
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some(token_id.to_string()),
+            owner: Some(HumanAddr(owner.to_string())),
+            public_metadata: None,
+            private_metadata: None,
+            public_teaser: None,
+            memo: None,
+            padding: None,
+        };
+        let mut env = mock_env(owner, &[]);
+        env.block.height = height;
+        let result = handle(deps, env, handle_msg);
+        assert!(result.is_ok());
+    }
+
+    fn create_key(deps: &mut Extern<MockStorage, MockApi, MockQuerier>, address: &str) -> String {
+        let handle_msg = HandleMsg::CreateViewingKey {
+            entropy: "entropy".to_string(),
+            padding: None,
+        };
+        let result = handle(deps, mock_env(address, &[]), handle_msg);
+        match from_binary(&result.unwrap().data.unwrap()).unwrap() {
+            HandleAnswer::ViewingKey { key } => key,
+            _ => panic!("unexpected"),
+        }
+    }
+
+    // returns the total and the ids of the displayed txs
+    fn history(
+        deps: &Extern<MockStorage, MockApi, MockQuerier>,
+        address: &str,
+        viewing_key: &str,
+    ) -> (u64, Vec<u64>) {
+        let query_msg = QueryMsg::TransactionHistory {
+            address: HumanAddr(address.to_string()),
+            viewing_key: viewing_key.to_string(),
+            page: None,
+            page_size: None,
+            cursor: None,
+            filter: None,
+        };
+        match from_binary(&query(deps, query_msg).unwrap()).unwrap() {
+            QueryAnswer::TransactionHistory { total, txs } => {
+                (total, txs.iter().map(|tx| tx.tx_id).collect())
+            }
+            _ => panic!("unexpected"),
+        }
+    }
+
+    fn prune(
+        deps: &mut Extern<MockStorage, MockApi, MockQuerier>,
+        address: &str,
+        keep: Option<u32>,
+        height: u64,
+    ) -> u32 {
+        let handle_msg = HandleMsg::PruneHistory {
+            keep,
+            padding: None,
+        };
+        let mut env = mock_env(address, &[]);
+        env.block.height = height;
+        let result = handle(deps, env, handle_msg);
+        match from_binary(&result.unwrap().data.unwrap()).unwrap() {
+            HandleAnswer::PruneHistory { pruned, .. } => pruned,
+            _ => panic!("unexpected"),
+        }
+    }
+
+    fn tx_exists(deps: &Extern<MockStorage, MockApi, MockQuerier>, tx_id: u64) -> bool {
+        let tx_store = ReadonlyPrefixedStorage::new(PREFIX_TXS, &deps.storage);
+        tx_store.get(&tx_id.to_le_bytes()).is_some()
+    }
+
+    fn id_store_len(deps: &Extern<MockStorage, MockApi, MockQuerier>, address: &str) -> u32 {
+        let address_raw = deps
+            .api
+            .canonical_address(&HumanAddr(address.to_string()))
+            .unwrap();
+        let id_store = ReadonlyPrefixedStorage::multilevel(
+            &[PREFIX_TX_IDS, address_raw.as_slice()],
+            &deps.storage,
+        );
+        let len: Option<u32> = id_store
+            .get(b"len")
+            .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]));
+        len.unwrap_or(0)
+    }
+
+    #[test]
+    fn test_keep_latest_txs() {
+        let (init_result, mut deps) = init_helper_with_retention(TxRetention {
+            max_txs: Some(2),
+            max_blocks: None,
+        });
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let key = create_key(&mut deps, "admin");
+        mint_at(&mut deps, "NFT0", "admin", 100);
+        mint_at(&mut deps, "NFT1", "admin", 100);
+        mint_at(&mut deps, "NFT2", "admin", 100);
+        assert_eq!(history(&deps, "admin", &key), (2, vec![2, 1]));
+        assert_eq!(id_store_len(&deps, "admin"), 3);
+        assert!(tx_exists(&deps, 0));
+
+        // test the index is compacted once it holds twice the limit
+        mint_at(&mut deps, "NFT3", "admin", 100);
+        assert_eq!(id_store_len(&deps, "admin"), 2);
+        assert!(!tx_exists(&deps, 0));
+        assert!(!tx_exists(&deps, 1));
+        assert_eq!(history(&deps, "admin", &key), (2, vec![3, 2]));
+        // test the vacated slots were removed
+        let admin_raw = deps
+            .api
+            .canonical_address(&HumanAddr("admin".to_string()))
+            .unwrap();
+        let id_store = ReadonlyPrefixedStorage::multilevel(
+            &[PREFIX_TX_IDS, admin_raw.as_slice()],
+            &deps.storage,
+        );
+        assert!(id_store.get(&2u32.to_be_bytes()).is_none());
+        assert!(id_store.get(&1u32.to_be_bytes()).is_some());
+    }
+
+    #[test]
+    fn test_keep_no_txs() {
+        let (init_result, mut deps) = init_helper_with_retention(TxRetention {
+            max_txs: Some(0),
+            max_blocks: None,
+        });
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let key = create_key(&mut deps, "admin");
+        mint_at(&mut deps, "NFT0", "alice", 100);
+        let handle_msg = HandleMsg::BurnNft {
+            token_id: "NFT0".to_string(),
+            memo: Some("private".to_string()),
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(result.is_ok());
+        assert_eq!(history(&deps, "admin", &key), (0, vec![]));
+        assert!(!tx_exists(&deps, 0));
+        assert!(!tx_exists(&deps, 1));
+        assert_eq!(id_store_len(&deps, "alice"), 0);
+    }
+
+    #[test]
+    fn test_keep_txs_for_blocks() {
+        let (init_result, mut deps) = init_helper_with_retention(TxRetention {
+            max_txs: None,
+            max_blocks: Some(2),
+        });
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let key = create_key(&mut deps, "admin");
+        for i in 0..5u64 {
+            mint_at(&mut deps, &format!("NFT{}", i), "admin", 100 + i);
+        }
+        // the last handle saved block 104, so only txs from blocks 103 and 104 are displayed
+        assert_eq!(history(&deps, "admin", &key), (2, vec![4, 3]));
+
+        // test expired txs were pruned as new txs were added
+        assert_eq!(id_store_len(&deps, "admin"), 2);
+        assert!(!tx_exists(&deps, 0));
+        assert!(!tx_exists(&deps, 2));
+        assert!(tx_exists(&deps, 3));
+        assert_eq!(prune(&mut deps, "admin", None, 104), 0);
+        assert_eq!(history(&deps, "admin", &key), (2, vec![4, 3]));
+    }
+
+    #[test]
+    fn test_expired_txs_are_deleted() {
+        let (init_result, mut deps) = init_helper_with_retention(TxRetention {
+            max_txs: None,
+            max_blocks: Some(10),
+        });
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let bob_key = create_key(&mut deps, "bob");
+        mint_at(&mut deps, "NFT0", "alice", 100);
+        let handle_msg = HandleMsg::TransferNft {
+            recipient: HumanAddr("bob".to_string()),
+            token_id: "NFT0".to_string(),
+            recipient_only: None,
+            memo: None,
+            padding: None,
+        };
+        let mut env = mock_env("alice", &[]);
+        env.block.height = 100;
+        let result = handle(&mut deps, env, handle_msg);
+        assert!(result.is_ok());
+        assert!(tx_exists(&deps, 1));
+
+        // test the record of the transfer is deleted once it expires, even though bob never
+        // transacts again
+        mint_at(&mut deps, "NFT1", "alice", 110);
+        assert!(!tx_exists(&deps, 0));
+        assert!(!tx_exists(&deps, 1));
+        let refs_store = ReadonlyPrefixedStorage::new(PREFIX_TX_REFS, &deps.storage);
+        let refs: Option<u32> = may_load(&refs_store, &1u64.to_le_bytes()).unwrap();
+        assert!(refs.is_none());
+        assert_eq!(id_store_len(&deps, "alice"), 1);
+
+        // test bob's history skips the deleted tx and can still be pruned
+        assert_eq!(history(&deps, "bob", &bob_key), (0, vec![]));
+        assert_eq!(prune(&mut deps, "bob", None, 110), 1);
+        assert_eq!(id_store_len(&deps, "bob"), 0);
+    }
+
+    #[test]
+    fn test_prune_history() {
+        let (init_result, mut deps) = init_helper_with_retention(TxRetention::default());
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let admin_key = create_key(&mut deps, "admin");
+        let alice_key = create_key(&mut deps, "alice");
+        mint_at(&mut deps, "NFT0", "admin", 100);
+        mint_at(&mut deps, "NFT1", "admin", 100);
+        let handle_msg = HandleMsg::BurnNft {
+            token_id: "NFT0".to_string(),
+            memo: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(result.is_ok());
+        assert_eq!(history(&deps, "admin", &admin_key), (3, vec![2, 1, 0]));
+        assert_eq!(history(&deps, "alice", &alice_key), (1, vec![2]));
+
+        // test an address without history
+        assert_eq!(prune(&mut deps, "bob", None, 100), 0);
+
+        // test pruning with the default retention only removes what the owner asks
+        assert_eq!(prune(&mut deps, "admin", None, 100), 0);
+        assert_eq!(prune(&mut deps, "admin", Some(1), 100), 2);
+        assert_eq!(history(&deps, "admin", &admin_key), (1, vec![2]));
+        assert!(!tx_exists(&deps, 0));
+        assert!(!tx_exists(&deps, 1));
+
+        // test pruning keeps txs other addresses still have
+        assert_eq!(prune(&mut deps, "admin", Some(0), 100), 1);
+        assert_eq!(history(&deps, "admin", &admin_key), (0, vec![]));
+        assert_eq!(history(&deps, "alice", &alice_key), (1, vec![2]));
+        assert!(tx_exists(&deps, 2));
+        let refs_store = ReadonlyPrefixedStorage::new(PREFIX_TX_REFS, &deps.storage);
+        let refs: Option<u32> = may_load(&refs_store, &2u64.to_le_bytes()).unwrap();
+        assert_eq!(refs, Some(1));
+
+        // test the tx is deleted once every address has pruned it
+        assert_eq!(prune(&mut deps, "alice", Some(0), 100), 1);
+        assert_eq!(history(&deps, "alice", &alice_key), (0, vec![]));
+        assert!(!tx_exists(&deps, 2));
+        let refs_store = ReadonlyPrefixedStorage::new(PREFIX_TX_REFS, &deps.storage);
+        let refs: Option<u32> = may_load(&refs_store, &2u64.to_le_bytes()).unwrap();
+        assert!(refs.is_none());
+    }
+
+    #[test]
+    fn test_set_tx_retention() {
+        let (init_result, mut deps) = init_helper_with_retention(TxRetention::default());
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let retention = TxRetention {
+            max_txs: Some(1),
+            max_blocks: Some(1000),
+        };
+
+        // test non-admin attempt
+        let handle_msg = HandleMsg::SetTxRetention {
+            retention: retention.clone(),
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let error = extract_error_msg(result);
+        assert!(error.contains("This is an admin command and can only be run from the admin address"));
+
+        let key = create_key(&mut deps, "admin");
+        mint_at(&mut deps, "NFT0", "admin", 100);
+        mint_at(&mut deps, "NFT1", "admin", 100);
+        let handle_msg = HandleMsg::SetTxRetention {
+            retention: retention.clone(),
+            padding: None,
+        };
+        let mut env = mock_env("admin", &[]);
+        env.block.height = 100;
+        let result = handle(&mut deps, env, handle_msg);
+        assert!(result.is_ok());
+        let query_result = query(&deps, QueryMsg::ContractConfig {});
+        match from_binary(&query_result.unwrap()).unwrap() {
+            QueryAnswer::ContractConfig { tx_retention, .. } => {
+                assert_eq!(tx_retention, retention);
+            }
+            _ => panic!("unexpected"),
+        }
+        // test existing history outside the new limits is hidden
+        assert_eq!(history(&deps, "admin", &key), (1, vec![1]));
+    }
+}
@@ -1,8 +1,9 @@
 #[cfg(test)]
 mod tests {
     use crate::unittest::helpers::helpers::helpers::*;
-    use crate::msg::{ContractStatus, HandleMsg, HandleAnswer};
-    use crate::contract::handle;
+    use crate::msg::{ContractStatus, HandleMsg, HandleAnswer, QueryAnswer, QueryMsg, ScopedQuery, ViewerInfo};
+    use crate::contract::{handle, query};
+    use crate::expiration::Expiration;
     use cosmwasm_std::testing::mock_env;
     use cosmwasm_std::{from_binary, Api, HumanAddr};
     use cosmwasm_storage::ReadonlyPrefixedStorage;
@@ -79,4 +80,147 @@ mod tests {
         let error = extract_error_msg(handle_result);
         assert!(error.contains("Viewing keys cannot be set, they must be generated"));
     }
+
+    #[test]
+    fn test_scoped_viewing_key() {
+        let (init_result, mut deps) = init_helper_full_profile();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let alice = HumanAddr("alice".to_string());
+        for id in &["NFT1", "NFT2"] {
+            let handle_msg = HandleMsg::MintNft {
+                token_id: Some(id.to_string()),
+                owner: Some(alice.clone()),
+                public_metadata: None,
+                private_metadata: None,
+                public_teaser: None,
+                memo: None,
+                padding: None,
+            };
+            let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+            assert!(handle_result.is_ok(), "Mint failed: {}", handle_result.err().unwrap());
+        }
+        let handle_msg = HandleMsg::CreateViewingKey {
+            entropy: "blah".to_string(),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let full_key = match from_binary(&handle_result.unwrap().data.unwrap()).unwrap() {
+            HandleAnswer::ViewingKey { key } => key,
+            _ => panic!("NOPE"),
+        };
+
+        // a scoped key must allow something
+        let handle_msg = HandleMsg::CreateScopedKey {
+            name: "monitor".to_string(),
+            entropy: "blah".to_string(),
+            queries: vec![],
+            token_ids: None,
+            expires: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("A scoped viewing key must allow at least one query"));
+
+        let expiry = mock_env("alice", &[]).block.height + 10;
+        let create_monitor = || HandleMsg::CreateScopedKey {
+            name: "monitor".to_string(),
+            entropy: "blah".to_string(),
+            queries: vec![ScopedQuery::CanRetrieve],
+            token_ids: Some(vec!["NFT1".to_string()]),
+            expires: Some(Expiration::AtHeight(expiry)),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), create_monitor());
+        let scoped_key = match from_binary(&handle_result.unwrap().data.unwrap()).unwrap() {
+            HandleAnswer::ViewingKey { key } => key,
+            _ => panic!("NOPE"),
+        };
+        assert_ne!(scoped_key, full_key);
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), create_monitor());
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("A viewing key named monitor already exists"));
+
+        let can_retrieve = |token_id: &str, key: &str| QueryMsg::CanRetrieve {
+            token_id: token_id.to_string(),
+            viewer: Some(ViewerInfo {
+                address: alice.clone(),
+                viewing_key: key.to_string(),
+            }),
+        };
+        let query_result = query(&deps, can_retrieve("NFT1", &scoped_key));
+        assert!(query_result.is_ok(), "Query failed: {}", query_result.err().unwrap());
+        // other tokens and other queries are out of scope
+        let error = extract_error_msg(query(&deps, can_retrieve("NFT2", &scoped_key)));
+        assert!(error.contains("Viewing key monitor does not grant access to this query"));
+        let query_msg = QueryMsg::TransactionHistory {
+            address: alice.clone(),
+            viewing_key: scoped_key.clone(),
+            page: None,
+            page_size: None,
+            cursor: None,
+            filter: None,
+        };
+        let error = extract_error_msg(query(&deps, query_msg));
+        assert!(error.contains("Viewing key monitor does not grant access to this query"));
+        // the full key still works for everything
+        let query_result = query(&deps, can_retrieve("NFT2", &full_key));
+        assert!(query_result.is_ok(), "Query failed: {}", query_result.err().unwrap());
+
+        // only the full key can list the scoped keys
+        let query_msg = QueryMsg::ScopedKeys {
+            address: alice.clone(),
+            viewing_key: scoped_key.clone(),
+        };
+        let error = extract_error_msg(query(&deps, query_msg));
+        assert!(error.contains("Wrong viewing key"));
+        let query_msg = QueryMsg::ScopedKeys {
+            address: alice.clone(),
+            viewing_key: full_key.clone(),
+        };
+        let query_answer: QueryAnswer = from_binary(&query(&deps, query_msg).unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::ScopedKeys { keys } => {
+                assert_eq!(keys.len(), 1);
+                assert_eq!(keys[0].name, "monitor".to_string());
+                assert_eq!(keys[0].queries, vec![ScopedQuery::CanRetrieve]);
+                assert_eq!(keys[0].token_ids, Some(vec!["NFT1".to_string()]));
+                assert_eq!(keys[0].expires, Expiration::AtHeight(expiry));
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // the key stops working once it expires
+        let mut env = mock_env("alice", &[]);
+        env.block.height = expiry;
+        let handle_msg = HandleMsg::CreateScopedKey {
+            name: "history".to_string(),
+            entropy: "blah".to_string(),
+            queries: vec![ScopedQuery::TransactionHistory],
+            token_ids: None,
+            expires: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, env, handle_msg);
+        assert!(handle_result.is_ok(), "Create failed: {}", handle_result.err().unwrap());
+        let error = extract_error_msg(query(&deps, can_retrieve("NFT1", &scoped_key)));
+        assert!(error.contains("Viewing key monitor has expired"));
+
+        // revoked keys are unknown
+        let revoke_monitor = || HandleMsg::RevokeScopedKey {
+            name: "monitor".to_string(),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), revoke_monitor());
+        assert!(handle_result.is_ok(), "Revoke failed: {}", handle_result.err().unwrap());
+        let error = extract_error_msg(query(&deps, can_retrieve("NFT1", &scoped_key)));
+        assert!(error.contains("Wrong viewing key"));
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), revoke_monitor());
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("There is no viewing key named monitor"));
+    }
 }
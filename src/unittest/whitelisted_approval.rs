@@ -7,8 +7,9 @@ mod tests {
     use cosmwasm_std::testing::{mock_env, MOCK_CONTRACT_ADDR};
     use crate::token::{Metadata, Token};
     use crate::expiration::Expiration;
-    use crate::state::{PermissionType, PREFIX_ALL_PERMISSIONS, Permission, json_load, PREFIX_INFOS, PREFIX_PUB_META, PREFIX_PRIV_META, load, may_load, PREFIX_AUTHLIST, AuthList, json_may_load};
-    use cosmwasm_storage::ReadonlyPrefixedStorage;
+    use crate::state::{PermissionType, get_all_perms, PREFIX_INFOS, PREFIX_PUB_META, PREFIX_PRIV_META, load, may_load, get_auth_lists, json_may_load, json_save, save, AuthList, Permission, PREFIX_ALL_PERMISSIONS, PREFIX_AUTHLIST, load_token};
+    use crate::contract::check_permission;
+    use cosmwasm_storage::{PrefixedStorage, ReadonlyPrefixedStorage};
 
     // test owner setting approval for specific addresses
     #[test]
@@ -55,7 +56,7 @@ mod tests {
         };
         let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
         let error = extract_error_msg(handle_result);
-        assert!(error.contains("You do not own token NFT1"));
+        assert!(error.contains("Token NFT1 does not exist"));
 
         let handle_msg = HandleMsg::MintNft {
             token_id: Some("NFT1".to_string()),
@@ -66,6 +67,7 @@ mod tests {
                 image: Some("URI 1".to_string()),
             }),
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -81,6 +83,7 @@ mod tests {
                 image: Some("URI 2".to_string()),
             }),
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -96,6 +99,7 @@ mod tests {
                 image: Some("URI 3".to_string()),
             }),
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -111,6 +115,7 @@ mod tests {
                 image: Some("URI 4".to_string()),
             }),
             private_metadata: None,
+            public_teaser: None,
             memo: None,
             padding: None,
         };
@@ -146,7 +151,7 @@ mod tests {
         };
         let handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
         let error = extract_error_msg(handle_result);
-        assert!(error.contains("You do not own token NFT1"));
+        assert!(error.contains("Token NFT1 does not exist"));
 
         // try approving a token without specifying which token
         let handle_msg = HandleMsg::SetWhitelistedApproval {
@@ -217,13 +222,11 @@ mod tests {
             .api
             .canonical_address(&HumanAddr("alice".to_string()))
             .unwrap();
-        let alice_key = alice_raw.as_slice();
         let view_owner_idx = PermissionType::ViewOwner.to_usize();
         let view_meta_idx = PermissionType::ViewMetadata.to_usize();
         let transfer_idx = PermissionType::Transfer.to_usize();
         // confirm ALL permission
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Vec<Permission> = json_load(&all_store, alice_key).unwrap();
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
         assert_eq!(all_perm.len(), 1);
         let bob_oper_perm = all_perm.iter().find(|p| p.address == bob_raw).unwrap();
         assert_eq!(
@@ -238,7 +241,7 @@ mod tests {
         let nft2_key = 1u32.to_le_bytes();
         let nft3_key = 2u32.to_le_bytes();
         let nft4_key = 3u32.to_le_bytes();
-        let token: Token = json_load(&info_store, &nft1_key).unwrap();
+        let token: Token = load_token(&info_store, &nft1_key).unwrap();
         assert_eq!(token.owner, alice_raw);
         assert!(token.unwrapped);
         let pub_store = ReadonlyPrefixedStorage::new(PREFIX_PUB_META, &deps.storage);
@@ -262,8 +265,7 @@ mod tests {
         assert_eq!(bob_tok_perm.expirations[view_meta_idx], None);
         assert_eq!(bob_tok_perm.expirations[view_owner_idx], None);
         // confirm AuthLists has bob with NFT1 permission
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Vec<AuthList> = load(&auth_store, alice_key).unwrap();
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
         assert_eq!(auth_list.len(), 1);
         let bob_auth = auth_list.iter().find(|a| a.address == bob_raw).unwrap();
         assert_eq!(bob_auth.tokens[transfer_idx].len(), 1);
@@ -286,8 +288,7 @@ mod tests {
         assert!(result.is_ok());
 
         // confirm ALL permission
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Vec<Permission> = json_load(&all_store, alice_key).unwrap();
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
         assert_eq!(all_perm.len(), 1);
         let bob_oper_perm = all_perm.iter().find(|p| p.address == bob_raw).unwrap();
         assert_eq!(
@@ -298,7 +299,7 @@ mod tests {
         assert_eq!(bob_oper_perm.expirations[transfer_idx], None);
         // confirm NFT1 permissions and that the token data did not get modified
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft1_key).unwrap();
+        let token: Token = load_token(&info_store, &nft1_key).unwrap();
         assert_eq!(token.owner, alice_raw);
         assert!(token.unwrapped);
         let pub_store = ReadonlyPrefixedStorage::new(PREFIX_PUB_META, &deps.storage);
@@ -322,8 +323,7 @@ mod tests {
         assert_eq!(bob_tok_perm.expirations[view_meta_idx], None);
         assert_eq!(bob_tok_perm.expirations[view_owner_idx], None);
         // confirm AuthLists has bob with NFT1 permission
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Vec<AuthList> = load(&auth_store, alice_key).unwrap();
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
         assert_eq!(auth_list.len(), 1);
         let bob_auth = auth_list.iter().find(|a| a.address == bob_raw).unwrap();
         assert_eq!(bob_auth.tokens[transfer_idx].len(), 1);
@@ -338,26 +338,25 @@ mod tests {
             view_owner: Some(AccessLevel::All),
             view_private_metadata: None,
             transfer: Some(AccessLevel::ApproveToken),
-            expires: Some(Expiration::AtHeight(1000)),
+            expires: Some(Expiration::AtHeight(100000)),
             padding: None,
         };
         let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
         assert!(result.is_ok());
 
         // confirm ALL permission with new expiration
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Vec<Permission> = json_load(&all_store, alice_key).unwrap();
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
         assert_eq!(all_perm.len(), 1);
         let bob_oper_perm = all_perm.iter().find(|p| p.address == bob_raw).unwrap();
         assert_eq!(
             bob_oper_perm.expirations[view_owner_idx],
-            Some(Expiration::AtHeight(1000))
+            Some(Expiration::AtHeight(100000))
         );
         assert_eq!(bob_oper_perm.expirations[view_meta_idx], None);
         assert_eq!(bob_oper_perm.expirations[transfer_idx], None);
         // confirm NFT2 permissions and that the token data did not get modified
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft2_key).unwrap();
+        let token: Token = load_token(&info_store, &nft2_key).unwrap();
         assert_eq!(token.owner, alice_raw);
         assert!(token.unwrapped);
         let pub_store = ReadonlyPrefixedStorage::new(PREFIX_PUB_META, &deps.storage);
@@ -376,13 +375,12 @@ mod tests {
             .unwrap();
         assert_eq!(
             bob_tok_perm.expirations[transfer_idx],
-            Some(Expiration::AtHeight(1000))
+            Some(Expiration::AtHeight(100000))
         );
         assert_eq!(bob_tok_perm.expirations[view_meta_idx], None);
         assert_eq!(bob_tok_perm.expirations[view_owner_idx], None);
         // confirm AuthLists added bob's NFT2 transfer permission
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Vec<AuthList> = load(&auth_store, alice_key).unwrap();
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
         assert_eq!(auth_list.len(), 1);
         let bob_auth = auth_list.iter().find(|a| a.address == bob_raw).unwrap();
         assert_eq!(bob_auth.tokens[transfer_idx].len(), 2);
@@ -406,7 +404,7 @@ mod tests {
 
         // confirm NFT3 permissions
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft3_key).unwrap();
+        let token: Token = load_token(&info_store, &nft3_key).unwrap();
         assert_eq!(token.permissions.len(), 1);
         let bob_tok_perm = token
             .permissions
@@ -420,8 +418,7 @@ mod tests {
         assert_eq!(bob_tok_perm.expirations[view_meta_idx], None);
         assert_eq!(bob_tok_perm.expirations[view_owner_idx], None);
         // confirm AuthLists added bob's nft3 transfer permission
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Vec<AuthList> = load(&auth_store, alice_key).unwrap();
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
         assert_eq!(auth_list.len(), 1);
         let bob_auth = auth_list.iter().find(|a| a.address == bob_raw).unwrap();
         assert_eq!(bob_auth.tokens[transfer_idx].len(), 3);
@@ -446,11 +443,10 @@ mod tests {
 
         // confirm NFT4 permissions
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft4_key).unwrap();
+        let token: Token = load_token(&info_store, &nft4_key).unwrap();
         assert!(token.permissions.is_empty());
         // confirm AuthLists are correct
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Vec<AuthList> = load(&auth_store, alice_key).unwrap();
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
         assert_eq!(auth_list.len(), 1);
         let bob_auth = auth_list.iter().find(|a| a.address == bob_raw).unwrap();
         assert_eq!(bob_auth.tokens[transfer_idx].len(), 3);
@@ -509,13 +505,12 @@ mod tests {
         assert!(result.is_ok());
 
         // confirm didn't affect ALL permissions
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Vec<Permission> = json_load(&all_store, alice_key).unwrap();
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
         assert_eq!(all_perm.len(), 3);
         let bob_oper_perm = all_perm.iter().find(|p| p.address == bob_raw).unwrap();
         assert_eq!(
             bob_oper_perm.expirations[view_owner_idx],
-            Some(Expiration::AtHeight(1000))
+            Some(Expiration::AtHeight(100000))
         );
         assert_eq!(bob_oper_perm.expirations[view_meta_idx], None);
         assert_eq!(bob_oper_perm.expirations[transfer_idx], None);
@@ -535,7 +530,7 @@ mod tests {
         assert_eq!(edmund_oper_perm.expirations[view_owner_idx], None);
         // confirm NFT2 permissions and that the token data did not get modified
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft2_key).unwrap();
+        let token: Token = load_token(&info_store, &nft2_key).unwrap();
         assert_eq!(token.owner, alice_raw);
         assert!(token.unwrapped);
         let pub_store = ReadonlyPrefixedStorage::new(PREFIX_PUB_META, &deps.storage);
@@ -564,8 +559,7 @@ mod tests {
         assert_eq!(charlie_tok_perm.expirations[view_meta_idx], None);
         assert_eq!(charlie_tok_perm.expirations[view_owner_idx], None);
         // confirm AuthLists still has bob, but not with NFT2 transfer permission
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Vec<AuthList> = load(&auth_store, alice_key).unwrap();
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
         assert_eq!(auth_list.len(), 2);
         let bob_auth = auth_list.iter().find(|a| a.address == bob_raw).unwrap();
         assert_eq!(bob_auth.tokens[transfer_idx].len(), 2);
@@ -615,8 +609,7 @@ mod tests {
         assert!(result.is_ok());
 
         // confirm only bob's ALL permission is gone
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Vec<Permission> = json_load(&all_store, alice_key).unwrap();
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
         assert_eq!(all_perm.len(), 2);
         assert!(all_perm.iter().find(|p| p.address == bob_raw).is_none());
         let david_oper_perm = all_perm.iter().find(|p| p.address == david_raw).unwrap();
@@ -636,7 +629,7 @@ mod tests {
         // confirm NFT1 permission added view_owner for bob with the old ALL permission
         // expiration, and did not touch the existing transfer permission for bob
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft1_key).unwrap();
+        let token: Token = load_token(&info_store, &nft1_key).unwrap();
         assert_eq!(token.permissions.len(), 1);
         let bob_tok_perm = token
             .permissions
@@ -645,7 +638,7 @@ mod tests {
             .unwrap();
         assert_eq!(
             bob_tok_perm.expirations[view_owner_idx],
-            Some(Expiration::AtHeight(1000))
+            Some(Expiration::AtHeight(100000))
         );
         assert_eq!(bob_tok_perm.expirations[view_meta_idx], None);
         assert_eq!(
@@ -664,7 +657,7 @@ mod tests {
         assert!(priv_meta.is_none());
         // confirm NFT2 permission for bob and charlie
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft2_key).unwrap();
+        let token: Token = load_token(&info_store, &nft2_key).unwrap();
         assert_eq!(token.permissions.len(), 2);
         let bob_tok_perm = token
             .permissions
@@ -673,7 +666,7 @@ mod tests {
             .unwrap();
         assert_eq!(
             bob_tok_perm.expirations[view_owner_idx],
-            Some(Expiration::AtHeight(1000))
+            Some(Expiration::AtHeight(100000))
         );
         assert_eq!(bob_tok_perm.expirations[view_meta_idx], None);
         assert_eq!(bob_tok_perm.expirations[transfer_idx], None);
@@ -690,7 +683,7 @@ mod tests {
         assert_eq!(charlie_tok_perm.expirations[view_owner_idx], None);
         // confirm NFT3 permissions and that the token data did not get modified
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft3_key).unwrap();
+        let token: Token = load_token(&info_store, &nft3_key).unwrap();
         assert_eq!(token.owner, alice_raw);
         assert!(token.unwrapped);
         let pub_store = ReadonlyPrefixedStorage::new(PREFIX_PUB_META, &deps.storage);
@@ -715,7 +708,7 @@ mod tests {
         );
         // confirm NFT4 permission for bob
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft4_key).unwrap();
+        let token: Token = load_token(&info_store, &nft4_key).unwrap();
         assert_eq!(token.permissions.len(), 1);
         let bob_tok_perm = token
             .permissions
@@ -724,13 +717,12 @@ mod tests {
             .unwrap();
         assert_eq!(
             bob_tok_perm.expirations[view_owner_idx],
-            Some(Expiration::AtHeight(1000))
+            Some(Expiration::AtHeight(100000))
         );
         assert_eq!(bob_tok_perm.expirations[view_meta_idx], None);
         assert_eq!(bob_tok_perm.expirations[transfer_idx], None);
         // confirm AuthLists still has bob, but not with NFT3 view_owner permission
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Vec<AuthList> = load(&auth_store, alice_key).unwrap();
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
         assert_eq!(auth_list.len(), 2);
         let bob_auth = auth_list.iter().find(|a| a.address == bob_raw).unwrap();
         assert_eq!(bob_auth.tokens[transfer_idx].len(), 2);
@@ -764,8 +756,7 @@ mod tests {
         assert!(result.is_ok());
 
         // confirm only bob's ALL permission is gone
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Vec<Permission> = json_load(&all_store, alice_key).unwrap();
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
         assert_eq!(all_perm.len(), 2);
         assert!(all_perm.iter().find(|p| p.address == bob_raw).is_none());
         let david_oper_perm = all_perm.iter().find(|p| p.address == david_raw).unwrap();
@@ -785,7 +776,7 @@ mod tests {
         // confirm NFT1 removed view_owner permission for bob, and did not touch the existing
         // transfer permission for bob
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft1_key).unwrap();
+        let token: Token = load_token(&info_store, &nft1_key).unwrap();
         assert_eq!(token.permissions.len(), 1);
         let bob_tok_perm = token
             .permissions
@@ -810,7 +801,7 @@ mod tests {
         assert!(priv_meta.is_none());
         // confirm NFT2 permission removed bob but left and charlie
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft2_key).unwrap();
+        let token: Token = load_token(&info_store, &nft2_key).unwrap();
         assert_eq!(token.permissions.len(), 1);
         assert!(token
             .permissions
@@ -830,7 +821,7 @@ mod tests {
         assert_eq!(charlie_tok_perm.expirations[view_owner_idx], None);
         // confirm NFT3 permissions and that the token data did not get modified
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft3_key).unwrap();
+        let token: Token = load_token(&info_store, &nft3_key).unwrap();
         assert_eq!(token.owner, alice_raw);
         assert!(token.unwrapped);
         let pub_store = ReadonlyPrefixedStorage::new(PREFIX_PUB_META, &deps.storage);
@@ -855,11 +846,10 @@ mod tests {
         );
         // confirm NFT4 permission removed bob
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft4_key).unwrap();
+        let token: Token = load_token(&info_store, &nft4_key).unwrap();
         assert!(token.permissions.is_empty());
         // confirm AuthLists still has bob, but only for NFT1 and 3 transfer permission
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Vec<AuthList> = load(&auth_store, alice_key).unwrap();
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
         assert_eq!(auth_list.len(), 2);
         let bob_auth = auth_list.iter().find(|a| a.address == bob_raw).unwrap();
         assert_eq!(bob_auth.tokens[transfer_idx].len(), 2);
@@ -907,8 +897,7 @@ mod tests {
         assert!(result.is_ok());
 
         // confirm edmund still has ALL permission
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Vec<Permission> = json_load(&all_store, alice_key).unwrap();
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
         assert_eq!(all_perm.len(), 2);
         let edmund_oper_perm = all_perm.iter().find(|p| p.address == edmund_raw).unwrap();
         assert_eq!(
@@ -919,7 +908,7 @@ mod tests {
         assert_eq!(edmund_oper_perm.expirations[view_owner_idx], None);
         // confirm NFT4 permissions did not add edmund
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft4_key).unwrap();
+        let token: Token = load_token(&info_store, &nft4_key).unwrap();
         assert_eq!(token.owner, alice_raw);
         assert!(token.unwrapped);
         let pub_store = ReadonlyPrefixedStorage::new(PREFIX_PUB_META, &deps.storage);
@@ -932,8 +921,7 @@ mod tests {
         assert!(priv_meta.is_none());
         assert!(token.permissions.is_empty());
         // confirm edmund did not get added to AuthList
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Vec<AuthList> = load(&auth_store, alice_key).unwrap();
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
         assert_eq!(auth_list.len(), 2);
         assert!(auth_list.iter().find(|a| a.address == edmund_raw).is_none());
 
@@ -972,8 +960,7 @@ mod tests {
         assert!(result.is_ok());
 
         // confirm edmund's ALL permission is gone
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Vec<Permission> = json_load(&all_store, alice_key).unwrap();
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
         assert_eq!(all_perm.len(), 1);
         assert!(all_perm.iter().find(|p| p.address == edmund_raw).is_none());
         let david_oper_perm = all_perm.iter().find(|p| p.address == david_raw).unwrap();
@@ -985,7 +972,7 @@ mod tests {
         assert_eq!(david_oper_perm.expirations[view_owner_idx], None);
         // confirm NFT1 added permission for edmund,
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft1_key).unwrap();
+        let token: Token = load_token(&info_store, &nft1_key).unwrap();
         assert_eq!(token.permissions.len(), 2);
         let bob_tok_perm = token
             .permissions
@@ -1011,7 +998,7 @@ mod tests {
         );
         // confirm NFT2 added permission for edmund,
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft2_key).unwrap();
+        let token: Token = load_token(&info_store, &nft2_key).unwrap();
         assert_eq!(token.permissions.len(), 2);
         assert!(token
             .permissions
@@ -1043,7 +1030,7 @@ mod tests {
         // confirm NFT3 added permission for edmund and that the token data did not get modified
         // and did not touch the existing transfer permission for bob
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft3_key).unwrap();
+        let token: Token = load_token(&info_store, &nft3_key).unwrap();
         assert_eq!(token.owner, alice_raw);
         assert!(token.unwrapped);
         let pub_store = ReadonlyPrefixedStorage::new(PREFIX_PUB_META, &deps.storage);
@@ -1079,7 +1066,7 @@ mod tests {
         );
         // confirm NFT4 permission added edmund with input expiration
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft4_key).unwrap();
+        let token: Token = load_token(&info_store, &nft4_key).unwrap();
         assert_eq!(token.permissions.len(), 1);
         let edmund_tok_perm = token
             .permissions
@@ -1093,8 +1080,7 @@ mod tests {
             Some(Expiration::AtHeight(3000))
         );
         // confirm AuthLists added edmund for transferring on every tokens
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Vec<AuthList> = load(&auth_store, alice_key).unwrap();
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
         assert_eq!(auth_list.len(), 3);
         let bob_auth = auth_list.iter().find(|a| a.address == bob_raw).unwrap();
         assert_eq!(bob_auth.tokens[transfer_idx].len(), 2);
@@ -1151,13 +1137,12 @@ mod tests {
         assert!(result.is_ok());
 
         // confirm davids's ALL permission is gone
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let may_oper: Option<Vec<Permission>> = json_may_load(&all_store, alice_key).unwrap();
-        assert!(may_oper.is_none());
+        let may_oper = get_all_perms(&deps.storage, &alice_raw).unwrap();
+        assert!(may_oper.is_empty());
         // confirm NFT3 did not add permission for david and that the token data did not get modified
         // and did not touch the existing transfer permission for bob
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft3_key).unwrap();
+        let token: Token = load_token(&info_store, &nft3_key).unwrap();
         assert_eq!(token.owner, alice_raw);
         assert!(token.unwrapped);
         let pub_store = ReadonlyPrefixedStorage::new(PREFIX_PUB_META, &deps.storage);
@@ -1193,7 +1178,7 @@ mod tests {
         );
         // confirm NFT4 permission added david with input expiration
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft4_key).unwrap();
+        let token: Token = load_token(&info_store, &nft4_key).unwrap();
         assert_eq!(token.permissions.len(), 2);
         let edmund_tok_perm = token
             .permissions
@@ -1218,8 +1203,7 @@ mod tests {
             Some(Expiration::Never)
         );
         // confirm AuthLists added david for transferring on NFT4
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Vec<AuthList> = load(&auth_store, alice_key).unwrap();
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
         assert_eq!(auth_list.len(), 4);
         let bob_auth = auth_list.iter().find(|a| a.address == bob_raw).unwrap();
         assert_eq!(bob_auth.tokens[transfer_idx].len(), 2);
@@ -1261,8 +1245,7 @@ mod tests {
         assert!(result.is_ok());
 
         // confirm frank's ALL permission
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Vec<Permission> = json_load(&all_store, alice_key).unwrap();
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
         assert_eq!(all_perm.len(), 1);
         let frank_oper_perm = all_perm.iter().find(|p| p.address == frank_raw).unwrap();
         assert_eq!(
@@ -1273,7 +1256,7 @@ mod tests {
         assert_eq!(frank_oper_perm.expirations[transfer_idx], None);
         // confirm NFT4 did not add permission for frank
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft4_key).unwrap();
+        let token: Token = load_token(&info_store, &nft4_key).unwrap();
         assert_eq!(token.owner, alice_raw);
         assert!(token.unwrapped);
         let pub_store = ReadonlyPrefixedStorage::new(PREFIX_PUB_META, &deps.storage);
@@ -1313,8 +1296,7 @@ mod tests {
             Some(Expiration::Never)
         );
         // confirm AuthLists did not add frank
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Vec<AuthList> = load(&auth_store, alice_key).unwrap();
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
         assert_eq!(auth_list.len(), 4);
         assert!(auth_list.iter().find(|a| a.address == frank_raw).is_none());
         let bob_auth = auth_list.iter().find(|a| a.address == bob_raw).unwrap();
@@ -1379,13 +1361,12 @@ mod tests {
         assert!(result.is_ok());
 
         // confirm frank's ALL permission is gone
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Option<Vec<Permission>> = json_may_load(&all_store, alice_key).unwrap();
-        assert!(all_perm.is_none());
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
+        assert!(all_perm.is_empty());
         // confirm NFT1 permission added view_owner for frank with the old ALL permission
         // expiration
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft1_key).unwrap();
+        let token: Token = load_token(&info_store, &nft1_key).unwrap();
         assert_eq!(token.permissions.len(), 3);
         let bob_tok_perm = token
             .permissions
@@ -1432,7 +1413,7 @@ mod tests {
         assert!(priv_meta.is_none());
         // confirm NFT2 permission
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft2_key).unwrap();
+        let token: Token = load_token(&info_store, &nft2_key).unwrap();
         assert_eq!(token.permissions.len(), 3);
         let edmund_tok_perm = token
             .permissions
@@ -1470,7 +1451,7 @@ mod tests {
         // confirm NFT3 permissions do not include frank and that the token data did not get
         // modified
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft3_key).unwrap();
+        let token: Token = load_token(&info_store, &nft3_key).unwrap();
         assert_eq!(token.owner, alice_raw);
         assert!(token.unwrapped);
         let pub_store = ReadonlyPrefixedStorage::new(PREFIX_PUB_META, &deps.storage);
@@ -1511,7 +1492,7 @@ mod tests {
         );
         // confirm NFT4 permission
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft4_key).unwrap();
+        let token: Token = load_token(&info_store, &nft4_key).unwrap();
         assert_eq!(token.permissions.len(), 3);
         let frank_tok_perm = token
             .permissions
@@ -1547,8 +1528,7 @@ mod tests {
         assert_eq!(david_tok_perm.expirations[view_meta_idx], None);
         assert_eq!(david_tok_perm.expirations[view_owner_idx], None);
         // confirm AuthLists added frank with view_owner permissions for all butNFT3
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Vec<AuthList> = load(&auth_store, alice_key).unwrap();
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
         assert_eq!(auth_list.len(), 5);
         let bob_auth = auth_list.iter().find(|a| a.address == bob_raw).unwrap();
         assert_eq!(bob_auth.tokens[transfer_idx].len(), 2);
@@ -1597,8 +1577,7 @@ mod tests {
         assert!(result.is_ok());
 
         // confirm frank's ALL permission
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Vec<Permission> = json_load(&all_store, alice_key).unwrap();
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
         assert_eq!(all_perm.len(), 1);
         let frank_oper_perm = all_perm.iter().find(|p| p.address == frank_raw).unwrap();
         assert_eq!(
@@ -1609,7 +1588,7 @@ mod tests {
         assert_eq!(frank_oper_perm.expirations[transfer_idx], None);
         // confirm NFT1 permission removed frank
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft1_key).unwrap();
+        let token: Token = load_token(&info_store, &nft1_key).unwrap();
         assert_eq!(token.permissions.len(), 2);
         assert!(token
             .permissions
@@ -1640,7 +1619,7 @@ mod tests {
         );
         // confirm NFT2 permission removed frank
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft2_key).unwrap();
+        let token: Token = load_token(&info_store, &nft2_key).unwrap();
         assert_eq!(token.permissions.len(), 2);
         let edmund_tok_perm = token
             .permissions
@@ -1671,7 +1650,7 @@ mod tests {
             .is_none());
         // confirm NFT4 permission removed frank
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft4_key).unwrap();
+        let token: Token = load_token(&info_store, &nft4_key).unwrap();
         assert_eq!(token.permissions.len(), 2);
         assert!(token
             .permissions
@@ -1701,8 +1680,7 @@ mod tests {
         assert_eq!(david_tok_perm.expirations[view_meta_idx], None);
         assert_eq!(david_tok_perm.expirations[view_owner_idx], None);
         // confirm AuthLists removed frank
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Vec<AuthList> = load(&auth_store, alice_key).unwrap();
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
         assert_eq!(auth_list.len(), 4);
         assert!(auth_list.iter().find(|a| a.address == frank_raw).is_none());
         let bob_auth = auth_list.iter().find(|a| a.address == bob_raw).unwrap();
@@ -1743,12 +1721,11 @@ mod tests {
         let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
         assert!(result.is_ok());
         // confirm frank's ALL permission is gone
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Option<Vec<Permission>> = json_may_load(&all_store, alice_key).unwrap();
-        assert!(all_perm.is_none());
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
+        assert!(all_perm.is_empty());
         // confirm NFT1 permission removed frank
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft1_key).unwrap();
+        let token: Token = load_token(&info_store, &nft1_key).unwrap();
         assert_eq!(token.permissions.len(), 2);
         assert!(token
             .permissions
@@ -1784,7 +1761,7 @@ mod tests {
             .find(|p| p.address == frank_raw)
             .is_none());
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft2_key).unwrap();
+        let token: Token = load_token(&info_store, &nft2_key).unwrap();
         assert_eq!(token.permissions.len(), 2);
         let edmund_tok_perm = token
             .permissions
@@ -1810,7 +1787,7 @@ mod tests {
         assert_eq!(charlie_tok_perm.expirations[view_owner_idx], None);
         // confirm NFT4 permission removed frank
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft4_key).unwrap();
+        let token: Token = load_token(&info_store, &nft4_key).unwrap();
         assert_eq!(token.permissions.len(), 2);
         assert!(token
             .permissions
@@ -1840,8 +1817,7 @@ mod tests {
         assert_eq!(david_tok_perm.expirations[view_meta_idx], None);
         assert_eq!(david_tok_perm.expirations[view_owner_idx], None);
         // confirm AuthLists removed frank
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Vec<AuthList> = load(&auth_store, alice_key).unwrap();
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
         assert_eq!(auth_list.len(), 4);
         assert!(auth_list.iter().find(|a| a.address == frank_raw).is_none());
         let bob_auth = auth_list.iter().find(|a| a.address == bob_raw).unwrap();
@@ -1884,7 +1860,7 @@ mod tests {
 
         // confirm NFT2 permission removed charlie
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft2_key).unwrap();
+        let token: Token = load_token(&info_store, &nft2_key).unwrap();
         assert_eq!(token.permissions.len(), 1);
         assert!(token
             .permissions
@@ -1903,8 +1879,7 @@ mod tests {
             Some(Expiration::AtHeight(2000))
         );
         // confirm AuthLists removed charlie
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Vec<AuthList> = load(&auth_store, alice_key).unwrap();
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
         assert!(auth_list
             .iter()
             .find(|a| a.address == charlie_raw)
@@ -1948,20 +1923,19 @@ mod tests {
         assert!(result.is_ok());
 
         // verify no ALL permissions left
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Option<Vec<Permission>> = json_may_load(&all_store, alice_key).unwrap();
-        assert!(all_perm.is_none());
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
+        assert!(all_perm.is_empty());
         // confirm NFT1 permissions are empty
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft1_key).unwrap();
+        let token: Token = load_token(&info_store, &nft1_key).unwrap();
         assert!(token.permissions.is_empty());
         // confirm NFT2 permissions are empty
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft2_key).unwrap();
+        let token: Token = load_token(&info_store, &nft2_key).unwrap();
         assert!(token.permissions.is_empty());
         // confirm NFT3 permissions are empty (and info is intact)
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft3_key).unwrap();
+        let token: Token = load_token(&info_store, &nft3_key).unwrap();
         assert_eq!(token.owner, alice_raw);
         assert!(token.unwrapped);
         let pub_store = ReadonlyPrefixedStorage::new(PREFIX_PUB_META, &deps.storage);
@@ -1975,12 +1949,11 @@ mod tests {
         assert!(token.permissions.is_empty());
         // confirm NFT4 permissions are empty
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft4_key).unwrap();
+        let token: Token = load_token(&info_store, &nft4_key).unwrap();
         assert!(token.permissions.is_empty());
         // verify no AuthLists left
-        let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Option<Vec<AuthList>> = may_load(&auth_store, alice_key).unwrap();
-        assert!(auth_list.is_none());
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
+        assert!(auth_list.is_empty());
 
         // verify revoking doesn't break anything when there are no permissions
         let handle_msg = HandleMsg::SetWhitelistedApproval {
@@ -1996,20 +1969,19 @@ mod tests {
         assert!(result.is_ok());
 
         // verify no ALL permissions left
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let all_perm: Option<Vec<Permission>> = json_may_load(&all_store, alice_key).unwrap();
-        assert!(all_perm.is_none());
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
+        assert!(all_perm.is_empty());
         // confirm NFT1 permissions are empty
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft1_key).unwrap();
+        let token: Token = load_token(&info_store, &nft1_key).unwrap();
         assert!(token.permissions.is_empty());
         // confirm NFT2 permissions are empty
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft2_key).unwrap();
+        let token: Token = load_token(&info_store, &nft2_key).unwrap();
         assert!(token.permissions.is_empty());
         // confirm NFT3 permissions are empty (and info is intact)
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft3_key).unwrap();
+        let token: Token = load_token(&info_store, &nft3_key).unwrap();
         assert_eq!(token.owner, alice_raw);
         assert!(token.unwrapped);
         let pub_store = ReadonlyPrefixedStorage::new(PREFIX_PUB_META, &deps.storage);
@@ -2023,11 +1995,162 @@ mod tests {
         assert!(token.permissions.is_empty());
         // confirm NFT4 permissions are empty
         let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
-        let token: Token = json_load(&info_store, &nft4_key).unwrap();
+        let token: Token = load_token(&info_store, &nft4_key).unwrap();
         assert!(token.permissions.is_empty());
         // verify no AuthLists left
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
+        assert!(auth_list.is_empty());
+    }
+
+    // test expired ALL permissions are cleaned up when the address' permissions are altered
+    #[test]
+    fn test_expired_all_permission_cleanup() {
+        let (init_result, mut deps) = init_helper_default();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        mint_generic_token(&mut deps, "NFT1");
+        let alice_raw = deps
+            .api
+            .canonical_address(&HumanAddr("alice".to_string()))
+            .unwrap();
+        let david_raw = deps
+            .api
+            .canonical_address(&HumanAddr("david".to_string()))
+            .unwrap();
+        let transfer_idx = PermissionType::Transfer.to_usize();
+
+        let mut env = mock_env("alice", &[]);
+        env.block.height = 1;
+        for address in &["bob", "david"] {
+            let handle_msg = HandleMsg::SetWhitelistedApproval {
+                address: HumanAddr(address.to_string()),
+                token_id: None,
+                view_owner: Some(AccessLevel::All),
+                view_private_metadata: None,
+                transfer: None,
+                expires: Some(Expiration::AtHeight(100)),
+                padding: None,
+            };
+            let result = handle(&mut deps, env.clone(), handle_msg);
+            assert!(result.is_ok());
+        }
+        assert_eq!(get_all_perms(&deps.storage, &alice_raw).unwrap().len(), 2);
+
+        // after both expire, altering bob's permissions removes his expired ALL permission
+        let handle_msg = HandleMsg::SetWhitelistedApproval {
+            address: HumanAddr("bob".to_string()),
+            token_id: Some("NFT1".to_string()),
+            view_owner: None,
+            view_private_metadata: None,
+            transfer: Some(AccessLevel::ApproveToken),
+            expires: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(result.is_ok());
+        let all_perm = get_all_perms(&deps.storage, &alice_raw).unwrap();
+        assert_eq!(all_perm.len(), 1);
+        assert_eq!(all_perm[0].address, david_raw);
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
+        assert_eq!(auth_list.len(), 1);
+        assert_eq!(auth_list[0].tokens[transfer_idx], vec![0u32]);
+
+        // verify removing david's last permission removes him from the list
+        let handle_msg = HandleMsg::SetWhitelistedApproval {
+            address: HumanAddr("david".to_string()),
+            token_id: None,
+            view_owner: None,
+            view_private_metadata: None,
+            transfer: None,
+            expires: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(result.is_ok());
+        assert!(get_all_perms(&deps.storage, &alice_raw).unwrap().is_empty());
+    }
+
+    // test approvals saved in the legacy lists are used and migrated on the next change
+    #[test]
+    fn test_legacy_approvals() {
+        let (init_result, mut deps) = init_helper_default();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        mint_generic_token(&mut deps, "NFT1");
+        mint_generic_token(&mut deps, "NFT2");
+        let alice_raw = deps
+            .api
+            .canonical_address(&HumanAddr("alice".to_string()))
+            .unwrap();
+        let bob_raw = deps
+            .api
+            .canonical_address(&HumanAddr("bob".to_string()))
+            .unwrap();
+        let charlie_raw = deps
+            .api
+            .canonical_address(&HumanAddr("charlie".to_string()))
+            .unwrap();
+        let view_meta_idx = PermissionType::ViewMetadata.to_usize();
+
+        // save the lists an older contract would have stored
+        let legacy_perms = vec![Permission {
+            address: bob_raw.clone(),
+            expirations: [None, None, Some(Expiration::Never)],
+        }];
+        let mut all_store = PrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &mut deps.storage);
+        json_save(&mut all_store, alice_raw.as_slice(), &legacy_perms).unwrap();
+        let legacy_auth = vec![AuthList {
+            address: charlie_raw.clone(),
+            tokens: [vec![], vec![1, 0], vec![]],
+        }];
+        let mut auth_store = PrefixedStorage::new(PREFIX_AUTHLIST, &mut deps.storage);
+        save(&mut auth_store, alice_raw.as_slice(), &legacy_auth).unwrap();
+
+        let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
+        let token: Token = load_token(&info_store, &0u32.to_le_bytes()).unwrap();
+        let check_perm = check_permission(
+            &deps,
+            &mock_env("alice", &[]).block,
+            &token,
+            "NFT1",
+            Some(&bob_raw),
+            PermissionType::Transfer,
+            &mut Vec::new(),
+            "not approved",
+            false,
+        );
+        assert!(check_perm.is_ok());
+        assert_eq!(get_all_perms(&deps.storage, &alice_raw).unwrap(), legacy_perms);
+
+        // test the next change migrates both lists
+        let handle_msg = HandleMsg::SetWhitelistedApproval {
+            address: HumanAddr("charlie".to_string()),
+            token_id: Some("NFT2".to_string()),
+            view_owner: None,
+            view_private_metadata: Some(AccessLevel::RevokeToken),
+            transfer: None,
+            expires: None,
+            padding: None,
+        };
+        let result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(result.is_ok());
+        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
+        let may_perms: Option<Vec<Permission>> =
+            json_may_load(&all_store, alice_raw.as_slice()).unwrap();
+        assert!(may_perms.is_none());
         let auth_store = ReadonlyPrefixedStorage::new(PREFIX_AUTHLIST, &deps.storage);
-        let auth_list: Option<Vec<AuthList>> = may_load(&auth_store, alice_key).unwrap();
-        assert!(auth_list.is_none());
+        let may_auth: Option<Vec<AuthList>> = may_load(&auth_store, alice_raw.as_slice()).unwrap();
+        assert!(may_auth.is_none());
+        assert_eq!(get_all_perms(&deps.storage, &alice_raw).unwrap(), legacy_perms);
+        let auth_list = get_auth_lists(&deps.storage, &alice_raw).unwrap();
+        assert_eq!(auth_list.len(), 1);
+        assert_eq!(auth_list[0].address, charlie_raw);
+        assert_eq!(auth_list[0].tokens[view_meta_idx], vec![0u32]);
     }
 }